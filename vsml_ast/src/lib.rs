@@ -1,2 +1,3 @@
+pub mod position;
 pub mod vsml;
 pub mod vss;
@@ -0,0 +1,86 @@
+use std::fmt;
+
+/// ソースファイル上の位置
+/// offsetはバイト単位で0始まり、行・列は1始まりで列は文字数単位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// ファイルの先頭を表す位置
+    pub const START: Position = Position::new(0, 1, 1);
+
+    pub const fn new(offset: usize, line: usize, column: usize) -> Position {
+        Position {
+            offset,
+            line,
+            column,
+        }
+    }
+
+    /// この位置から `text` を読み進めた後の位置を返す
+    pub fn advance(self, text: &str) -> Position {
+        let offset = self.offset + text.len();
+        match text.rsplit_once('\n') {
+            None => Position {
+                offset,
+                line: self.line,
+                column: self.column + text.chars().count(),
+            },
+            Some((before, after)) => Position {
+                offset,
+                line: self.line + before.matches('\n').count() + 1,
+                column: after.chars().count() + 1,
+            },
+        }
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position::START
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// 範囲が記述されたソースファイル
+/// `Meta::sources` の何番目のファイルかを表し、0番目はVSMLファイル自身
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SourceId(pub usize);
+
+impl SourceId {
+    pub const VSML: SourceId = SourceId(0);
+}
+
+/// ソースファイル上の範囲
+/// startを含み、endを含まない
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub source: SourceId,
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    /// VSMLファイル上の範囲
+    pub const fn new(start: Position, end: Position) -> Span {
+        Span {
+            source: SourceId::VSML,
+            start,
+            end,
+        }
+    }
+
+    /// 同じ位置の、`source` のファイル上の範囲を返す
+    pub const fn with_source(self, source: SourceId) -> Span {
+        Span { source, ..self }
+    }
+}
@@ -1,4 +1,5 @@
-use crate::vss::VSSItem;
+use crate::position::Span;
+use crate::vss::{Rule, VSSItem, VSSKeyframes};
 use std::collections::HashMap;
 
 /// VSMLファイル全体のAST構造体
@@ -11,6 +12,11 @@ pub struct VSML {
 #[derive(Debug, PartialEq)]
 pub struct Meta {
     pub vss_items: Vec<VSSItem>,
+    /// `@keyframes` の定義で、同じ名前の定義は後に読み込まれたものが優先される
+    pub keyframes: Vec<VSSKeyframes>,
+    /// ソースファイルのパスで、`SourceId` はこの位置を表す
+    /// 先頭はVSMLファイル自身で、以降は読み込んだ順の外部のVSSファイル
+    pub sources: Vec<String>,
 }
 /// contタグ内のデータの構造体
 #[derive(Debug, PartialEq)]
@@ -25,6 +31,8 @@ pub struct Content {
     pub sampling_rate: Option<u32>,
     /// contタグの子孫のElement
     pub elements: Vec<Element>,
+    /// contタグのVSMLファイル上の範囲
+    pub span: Span,
 }
 /// タグやその中のテキストを表す構造体
 #[derive(Debug, PartialEq, Clone)]
@@ -33,6 +41,11 @@ pub enum Element {
         name: String,
         attributes: HashMap<String, String>,
         children: Vec<Element>,
+        /// `style` アトリビュートに記述されたルール
+        style: Vec<Rule>,
+        /// 開始タグから終了タグまでのVSMLファイル上の範囲
+        span: Span,
     },
-    Text(String),
+    /// テキストと、そのテキストノードのVSMLファイル上の範囲
+    Text(String, Span),
 }
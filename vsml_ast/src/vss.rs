@@ -1,4 +1,6 @@
-#[derive(Debug, PartialEq)]
+use crate::position::Span;
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum VSSSelectorAttributeValue {
     /// `[src]` のようなアトリビュート名のみの指定
     None,
@@ -14,7 +16,7 @@ pub enum VSSSelectorAttributeValue {
     Include(String),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum VSSSelector {
     /// `*` のセレクタ
     All,
@@ -24,13 +26,18 @@ pub enum VSSSelector {
     Class(String),
     /// `#id` のようなID名指定のセレクタ
     Id(String),
-    /// `:after` のような擬似クラスセレクタ
+    /// `:first-child` のような擬似クラスセレクタ
     PseudoClass(String),
+    /// `:nth-child(2n+1)` のような擬似クラスセレクタ
+    /// `an+b` の `a` と `b` を持つ
+    NthChild(i32, i32),
+    /// `:not(.bgm)` のような否定の擬似クラスセレクタ
+    Not(Vec<VSSSelector>),
     /// `[src="video.mp4"]` のようなアトリビュートを指定するセレクタ
     Attribute(String, VSSSelectorAttributeValue),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum VSSSelectorTree {
     /// `.selector1.selector2` のような単一のエレメントを指すセレクタ
     Selectors(Vec<VSSSelector>),
@@ -38,17 +45,21 @@ pub enum VSSSelectorTree {
     Descendant(Vec<VSSSelector>, Box<VSSSelectorTree>),
     /// `.selector > .selector` のような子エレメントを指すセレクタ
     Child(Vec<VSSSelector>, Box<VSSSelectorTree>),
-    /// `.selector + .selector` のような後続の弟エレメントを指すセレクタ
+    /// `.selector ~ .selector` のような後続の弟エレメントを指すセレクタ
     Sibling(Vec<VSSSelector>, Box<VSSSelectorTree>),
-    /// `.selector ~ .selector` のような直後の弟エレメントを指すセレクタ
+    /// `.selector + .selector` のような直後の弟エレメントを指すセレクタ
     AdjSibling(Vec<VSSSelector>, Box<VSSSelectorTree>),
 }
 
 /// `background-color: red` のような単一のルール
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Rule {
     pub property: String,
     pub value: String,
+    /// `!important` が指定されているか
+    pub important: bool,
+    /// プロパティ名の先頭から値の末尾までの範囲
+    pub span: Span,
 }
 
 /// セレクタとそのルールのセット
@@ -57,4 +68,27 @@ pub struct VSSItem {
     /// `<SelectorTree>, <SelectorTree>` のように複数のセレクタを表現するためVecとなっている
     pub selectors: Vec<VSSSelectorTree>,
     pub rules: Vec<Rule>,
+    /// セレクタの先頭から閉じ括弧までの範囲
+    /// 位置はVSSが記述されたファイル(VSMLファイルまたは外部の.vssファイル)上の位置
+    pub span: Span,
 }
+
+/// `@keyframes <name> { ... }` で定義されたアニメーション
+#[derive(Debug, PartialEq, Clone)]
+pub struct VSSKeyframes {
+    pub name: String,
+    pub keyframes: Vec<VSSKeyframe>,
+    /// `@keyframes` から閉じ括弧までの範囲
+    pub span: Span,
+}
+
+/// `50% { x: 100px; }` のような1つのキーフレーム
+#[derive(Debug, PartialEq, Clone)]
+pub struct VSSKeyframe {
+    /// キーフレームの位置(%)で、`from` は0、`to` は100になる
+    /// `0%, 100%` のように複数指定された場合は記述順に並ぶ
+    pub offsets: Vec<f64>,
+    pub rules: Vec<Rule>,
+    /// 位置の指定の先頭から閉じ括弧までの範囲
+    pub span: Span,
+}
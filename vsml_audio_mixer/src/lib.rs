@@ -1,6 +1,9 @@
-use dasp::{Signal, interpolate::sinc::Sinc, ring_buffer, signal};
+#[cfg(test)]
+mod tests;
+
+use dasp::{Frame, Signal, envelope::Detector, interpolate::sinc::Sinc, ring_buffer, signal};
 use vsml_common_audio::Audio as VsmlAudio;
-use vsml_core::AudioEffectStyle;
+use vsml_core::{AudioEffectStyle, AudioFade, AudioFilter};
 
 pub struct MixerImpl {
     audio: VsmlAudio,
@@ -11,7 +14,14 @@ pub struct MixingContextImpl {}
 impl vsml_core::Mixer for MixerImpl {
     type Audio = VsmlAudio;
 
-    fn mix_audio(&mut self, audio: Self::Audio, offset_time: f64, duration: f64, volume: f64) {
+    fn mix_audio(
+        &mut self,
+        audio: Self::Audio,
+        offset_time: f64,
+        duration: f64,
+        volume: f64,
+        fade: AudioFade,
+    ) {
         let signal = signal::from_iter(audio.samples);
 
         let ring_buffer = ring_buffer::Fixed::from([[0.0, 0.0]; 100]);
@@ -34,12 +44,25 @@ impl vsml_core::Mixer for MixerImpl {
                 .resize(offset_sample + duration_sample + 1, [0.0, 0.0]);
         }
 
-        let volume = volume as f32;
         let target = &mut self.audio.samples[offset_sample..][..=duration_sample];
         let source = &resampled_samples[..=duration_sample];
-        for (target_sample, source_sample) in target.iter_mut().zip(source.iter()) {
-            target_sample[0] += source_sample[0] * volume;
-            target_sample[1] += source_sample[1] * volume;
+        for (i, (target_sample, source_sample)) in target.iter_mut().zip(source.iter()).enumerate()
+        {
+            // フェードイン、フェードアウトの間は音量を線形に変化させる
+            let time = i as f64 / sampling_rate;
+            let fade_in_gain = if fade.fade_in > 0.0 {
+                (time / fade.fade_in).min(1.0)
+            } else {
+                1.0
+            };
+            let fade_out_gain = if fade.fade_out > 0.0 {
+                ((duration - time) / fade.fade_out).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            let gain = (volume * fade_in_gain * fade_out_gain) as f32;
+            target_sample[0] += source_sample[0] * gain;
+            target_sample[1] += source_sample[1] * gain;
         }
     }
 
@@ -77,7 +100,159 @@ impl vsml_core::MixingContext for MixingContextImpl {
         }
     }
 
-    fn apply_style(&mut self, audio: Self::Audio, _style: AudioEffectStyle) -> Self::Audio {
+    fn apply_style(&mut self, mut audio: Self::Audio, style: AudioEffectStyle) -> Self::Audio {
+        let sampling_rate = audio.sampling_rate as f32;
+        for filter in style.filters {
+            let samples = &mut audio.samples;
+            match filter {
+                AudioFilter::Gain(gain) => {
+                    for sample in samples.iter_mut() {
+                        *sample = sample.scale_amp(gain);
+                    }
+                }
+                AudioFilter::HighPass { frequency, q } => {
+                    Biquad::high_pass(sampling_rate, frequency, q).process(samples)
+                }
+                AudioFilter::LowPass { frequency, q } => {
+                    Biquad::low_pass(sampling_rate, frequency, q).process(samples)
+                }
+                AudioFilter::Equalizer { frequency, gain, q } => {
+                    Biquad::peaking(sampling_rate, frequency, gain, q).process(samples)
+                }
+                AudioFilter::Compressor {
+                    threshold,
+                    ratio,
+                    attack,
+                    release,
+                } => compress(
+                    samples,
+                    threshold,
+                    ratio,
+                    attack * sampling_rate,
+                    release * sampling_rate,
+                ),
+                AudioFilter::Reverb { decay, mix } => reverb(samples, sampling_rate, decay, mix),
+            }
+        }
         audio
     }
 }
+
+/// RBJのAudio EQ Cookbookの係数による双2次フィルター
+/// 係数はa0で正規化している
+struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+}
+
+impl Biquad {
+    /// 周波数をナイキスト周波数より低くした、(cos(w0), alpha)を返す
+    fn omega(sampling_rate: f32, frequency: f32, q: f32) -> (f32, f32) {
+        let frequency = frequency.min(sampling_rate * 0.49);
+        let (sin, cos) = (std::f32::consts::TAU * frequency / sampling_rate).sin_cos();
+        (cos, sin / (2.0 * q))
+    }
+    fn normalized(b: [f32; 3], a: [f32; 3]) -> Self {
+        Biquad {
+            b0: b[0] / a[0],
+            b1: b[1] / a[0],
+            b2: b[2] / a[0],
+            a1: a[1] / a[0],
+            a2: a[2] / a[0],
+        }
+    }
+    fn low_pass(sampling_rate: f32, frequency: f32, q: f32) -> Self {
+        let (cos, alpha) = Self::omega(sampling_rate, frequency, q);
+        Self::normalized(
+            [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+    fn high_pass(sampling_rate: f32, frequency: f32, q: f32) -> Self {
+        let (cos, alpha) = Self::omega(sampling_rate, frequency, q);
+        Self::normalized(
+            [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        )
+    }
+    fn peaking(sampling_rate: f32, frequency: f32, gain: f32, q: f32) -> Self {
+        let (cos, alpha) = Self::omega(sampling_rate, frequency, q);
+        let amplitude = 10f32.powf(gain / 40.0);
+        Self::normalized(
+            [1.0 + alpha * amplitude, -2.0 * cos, 1.0 - alpha * amplitude],
+            [1.0 + alpha / amplitude, -2.0 * cos, 1.0 - alpha / amplitude],
+        )
+    }
+
+    fn process(&self, samples: &mut [[f32; 2]]) {
+        let (mut x1, mut x2, mut y1, mut y2) = ([0.0; 2], [0.0; 2], [0.0; 2], [0.0; 2]);
+        for sample in samples.iter_mut() {
+            let x0 = *sample;
+            let y0 = x0
+                .scale_amp(self.b0)
+                .add_amp(x1.scale_amp(self.b1))
+                .add_amp(x2.scale_amp(self.b2))
+                .add_amp(y1.scale_amp(-self.a1))
+                .add_amp(y2.scale_amp(-self.a2));
+            (x2, x1) = (x1, x0);
+            (y2, y1) = (y1, y0);
+            *sample = y0;
+        }
+    }
+}
+
+/// 左右の大きい方のピークがthreshold(dB)を超えた分を1/ratioに圧縮する
+fn compress(
+    samples: &mut [[f32; 2]],
+    threshold: f32,
+    ratio: f32,
+    attack_frames: f32,
+    release_frames: f32,
+) {
+    let mut detector = Detector::peak(attack_frames, release_frames);
+    for sample in samples.iter_mut() {
+        let [left, right]: [f32; 2] = detector.next(*sample);
+        let level = 20.0 * left.max(right).log10();
+        if level > threshold {
+            let reduction = (threshold - level) * (1.0 - 1.0 / ratio);
+            *sample = sample.scale_amp(10f32.powf(reduction / 20.0));
+        }
+    }
+}
+
+/// Schroederの残響で、並列のコムフィルターと直列のオールパスフィルターで残響音を作る
+fn reverb(samples: &mut [[f32; 2]], sampling_rate: f32, decay: f32, mix: f32) {
+    const COMB_DELAYS: [f32; 4] = [0.0297, 0.0371, 0.0411, 0.0437];
+    const ALLPASS_DELAYS: [f32; 2] = [0.005, 0.0017];
+    const ALLPASS_GAIN: f32 = 0.7;
+
+    let delay_line = |delay: f32| {
+        let length = ((delay * sampling_rate) as usize).max(1);
+        ring_buffer::Fixed::from(vec![[0.0f32; 2]; length])
+    };
+    // decayの時間で60dB減衰するように、1周ごとのフィードバックの倍率を決める
+    let mut combs = COMB_DELAYS.map(|delay| (delay_line(delay), 10f32.powf(-3.0 * delay / decay)));
+    let mut allpasses = ALLPASS_DELAYS.map(delay_line);
+
+    for sample in samples.iter_mut() {
+        let dry = *sample;
+        let mut wet = combs
+            .iter_mut()
+            .fold([0.0; 2], |wet: [f32; 2], (buffer, feedback)| {
+                let delayed = *buffer.get(0);
+                buffer.push(dry.add_amp(delayed.scale_amp(*feedback)));
+                wet.add_amp(delayed)
+            })
+            .scale_amp(1.0 / COMB_DELAYS.len() as f32);
+        for buffer in allpasses.iter_mut() {
+            let delayed = *buffer.get(0);
+            let value = wet.add_amp(delayed.scale_amp(ALLPASS_GAIN));
+            buffer.push(value);
+            wet = delayed.add_amp(value.scale_amp(-ALLPASS_GAIN));
+        }
+        *sample = dry.scale_amp(1.0 - mix).add_amp(wet.scale_amp(mix));
+    }
+}
@@ -0,0 +1,99 @@
+use super::*;
+use vsml_core::MixingContext;
+
+const SAMPLING_RATE: u32 = 48000;
+
+/// 振幅1の正弦波を1秒分作る
+fn sine(frequency: f32) -> VsmlAudio {
+    let samples = (0..SAMPLING_RATE)
+        .map(|i| {
+            let value = (std::f32::consts::TAU * frequency * i as f32 / SAMPLING_RATE as f32).sin();
+            [value, value]
+        })
+        .collect();
+    VsmlAudio {
+        samples,
+        sampling_rate: SAMPLING_RATE,
+    }
+}
+
+/// 最初の0.5秒を除いた、左チャンネルの最大の振幅
+fn peak(audio: &VsmlAudio) -> f32 {
+    audio.samples[SAMPLING_RATE as usize / 2..]
+        .iter()
+        .map(|sample| sample[0].abs())
+        .fold(0.0, f32::max)
+}
+
+fn apply(audio: VsmlAudio, filters: Vec<AudioFilter>) -> VsmlAudio {
+    MixingContextImpl::new().apply_style(audio, AudioEffectStyle { filters })
+}
+
+#[test]
+fn test_apply_style_gain() {
+    let result = apply(sine(440.0), vec![AudioFilter::Gain(0.5)]);
+    assert!((peak(&result) - 0.5).abs() < 1e-3);
+}
+
+#[test]
+fn test_apply_style_pass_filters() {
+    let low_pass = AudioFilter::LowPass {
+        frequency: 500.0,
+        q: std::f32::consts::FRAC_1_SQRT_2,
+    };
+    // 遮断周波数より十分低い音は通し、十分高い音は減衰させる
+    assert!(peak(&apply(sine(50.0), vec![low_pass])) > 0.95);
+    assert!(peak(&apply(sine(5000.0), vec![low_pass])) < 0.05);
+
+    let high_pass = AudioFilter::HighPass {
+        frequency: 500.0,
+        q: std::f32::consts::FRAC_1_SQRT_2,
+    };
+    assert!(peak(&apply(sine(50.0), vec![high_pass])) < 0.05);
+    assert!(peak(&apply(sine(5000.0), vec![high_pass])) > 0.95);
+}
+
+#[test]
+fn test_apply_style_equalizer() {
+    let equalizer = AudioFilter::Equalizer {
+        frequency: 1000.0,
+        gain: 6.0,
+        q: 1.0,
+    };
+    // 中心周波数ではgainだけ増幅し、離れた周波数はほとんど変えない
+    assert!((peak(&apply(sine(1000.0), vec![equalizer])) - 10f32.powf(6.0 / 20.0)).abs() < 0.02);
+    assert!((peak(&apply(sine(50.0), vec![equalizer])) - 1.0).abs() < 0.02);
+}
+
+#[test]
+fn test_apply_style_compressor() {
+    let compressor = AudioFilter::Compressor {
+        threshold: -12.0,
+        ratio: 4.0,
+        attack: 0.001,
+        release: 0.1,
+    };
+    // 0dBの音は閾値を12dB超えているので、9dB小さくなる
+    let result = peak(&apply(sine(440.0), vec![compressor]));
+    assert!((20.0 * result.log10() + 9.0).abs() < 1.0, "{result}");
+    // 閾値より小さい音はそのまま
+    let quiet = apply(sine(440.0), vec![AudioFilter::Gain(0.1), compressor]);
+    assert!((peak(&quiet) - 0.1).abs() < 1e-3);
+}
+
+#[test]
+fn test_apply_style_reverb() {
+    // 短い音の後に残響が続く
+    let mut audio = sine(440.0);
+    audio.samples[SAMPLING_RATE as usize / 10..].fill([0.0, 0.0]);
+    let result = apply(
+        audio,
+        vec![AudioFilter::Reverb {
+            decay: 2.0,
+            mix: 0.5,
+        }],
+    );
+    let tail = &result.samples[SAMPLING_RATE as usize / 5..SAMPLING_RATE as usize / 2];
+    assert!(tail.iter().any(|sample| sample[0].abs() > 0.01));
+    assert!(tail.iter().all(|sample| sample[0].abs() < 1.0));
+}
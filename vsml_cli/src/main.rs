@@ -2,6 +2,7 @@ use clap::Parser;
 use std::collections::HashMap;
 use std::env;
 use std::path::{self, Path, PathBuf};
+use std::process;
 use std::sync::Arc;
 use vsml_audio_mixer::MixingContextImpl;
 use vsml_common_audio::Audio as VsmlAudio;
@@ -81,7 +82,12 @@ fn main() {
     let vsml_string = std::fs::read_to_string(&args.input_path).unwrap();
     env::set_current_dir(args.input_path.parent().unwrap())
         .expect("Failed to set current directory");
-    let vsml = parse(&vsml_string, &VSSFileLoader).unwrap();
+    // VSSファイルのパスに合わせ、VSMLファイルのディレクトリからの相対パスで表示する
+    let vsml_file_name = args.input_path.file_name().unwrap().to_string_lossy();
+    let vsml = parse(&vsml_string, &vsml_file_name, &VSSFileLoader).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
     let (device, queue) = get_gpu_device();
     let provider = HashMap::from([
         (
@@ -106,7 +112,10 @@ fn main() {
             ))) as Arc<dyn ObjectProcessor<VsmlImage, VsmlAudio>>,
         ),
     ]);
-    let iv_data = convert(&vsml, &provider);
+    let iv_data = convert(&vsml, &provider).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
 
     let mut rendering_context = RenderingContextImpl::new(device.clone(), queue.clone());
     let mut mixing_context = MixingContextImpl::new();
@@ -0,0 +1,785 @@
+use crate::ElementRect;
+use crate::schemas::{Color, Duration, DurationParseError};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// アニメーションの進み方 (CSSのanimation-timing-function)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimingFunction {
+    Linear,
+    /// `cubic-bezier(x1, y1, x2, y2)` の制御点
+    CubicBezier(f64, f64, f64, f64),
+    /// `steps(n, jump-end)` のような段階的な変化
+    Steps(u32, StepPosition),
+}
+
+/// steps()で値が切り替わるタイミング
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StepPosition {
+    /// 各区間の始まりで切り替わる
+    Start,
+    /// 各区間の終わりで切り替わる
+    End,
+}
+
+impl TimingFunction {
+    pub const EASE: Self = TimingFunction::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    pub const EASE_IN: Self = TimingFunction::CubicBezier(0.42, 0.0, 1.0, 1.0);
+    pub const EASE_OUT: Self = TimingFunction::CubicBezier(0.0, 0.0, 0.58, 1.0);
+    pub const EASE_IN_OUT: Self = TimingFunction::CubicBezier(0.42, 0.0, 0.58, 1.0);
+
+    /// 0.0〜1.0の進捗を、タイミング関数を適用した進捗に変換する
+    pub fn apply(&self, progress: f64) -> f64 {
+        match *self {
+            TimingFunction::Linear => progress,
+            TimingFunction::CubicBezier(x1, y1, x2, y2) => {
+                let t = solve_cubic_bezier_t(x1, x2, progress);
+                cubic_bezier(y1, y2, t)
+            }
+            TimingFunction::Steps(steps, position) => {
+                let steps = steps as f64;
+                let step = match position {
+                    StepPosition::Start => (progress * steps).ceil(),
+                    StepPosition::End => (progress * steps).floor(),
+                };
+                (step / steps).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+/// 始点(0, 0)と終点(1, 1)を持つ3次ベジェ曲線の1軸分の値
+fn cubic_bezier(p1: f64, p2: f64, t: f64) -> f64 {
+    let u = 1.0 - t;
+    3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+}
+
+/// x座標が `x` になるベジェ曲線の媒介変数tを求める
+/// x1, x2は0〜1に制限されているため、xはtに対して単調増加する
+fn solve_cubic_bezier_t(x1: f64, x2: f64, x: f64) -> f64 {
+    let (mut low, mut high) = (0.0, 1.0);
+    let mut t = x;
+    // ニュートン法で求め、収束しない場合は二分法で求める
+    for _ in 0..8 {
+        let error = cubic_bezier(x1, x2, t) - x;
+        if error.abs() < 1e-7 {
+            return t;
+        }
+        let u = 1.0 - t;
+        let derivative = 3.0 * u * u * x1 + 6.0 * u * t * (x2 - x1) + 3.0 * t * t * (1.0 - x2);
+        if derivative.abs() < 1e-6 {
+            break;
+        }
+        t -= error / derivative;
+    }
+    t = x;
+    for _ in 0..64 {
+        let value = cubic_bezier(x1, x2, t);
+        if (value - x).abs() < 1e-7 {
+            break;
+        }
+        if value < x {
+            low = t;
+        } else {
+            high = t;
+        }
+        t = (low + high) / 2.0;
+    }
+    t
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TimingFunctionParseError {
+    UnknownMode,
+}
+impl FromStr for TimingFunction {
+    type Err = TimingFunctionParseError;
+
+    fn from_str(value: &str) -> Result<TimingFunction, Self::Err> {
+        match value {
+            "linear" => return Ok(TimingFunction::Linear),
+            "ease" => return Ok(TimingFunction::EASE),
+            "ease-in" => return Ok(TimingFunction::EASE_IN),
+            "ease-out" => return Ok(TimingFunction::EASE_OUT),
+            "ease-in-out" => return Ok(TimingFunction::EASE_IN_OUT),
+            "step-start" => return Ok(TimingFunction::Steps(1, StepPosition::Start)),
+            "step-end" => return Ok(TimingFunction::Steps(1, StepPosition::End)),
+            _ => {}
+        }
+        let arguments = |name: &str| {
+            value
+                .strip_prefix(name)
+                .and_then(|value| value.strip_prefix('('))
+                .and_then(|value| value.strip_suffix(')'))
+                .map(|arguments| arguments.split(',').map(str::trim).collect::<Vec<_>>())
+        };
+        if let Some(arguments) = arguments("cubic-bezier") {
+            let points = arguments
+                .iter()
+                .map(|argument| argument.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| TimingFunctionParseError::UnknownMode)?;
+            return match points.as_slice() {
+                // x座標が0〜1の範囲外の場合は時間が逆行するため指定できない
+                &[x1, y1, x2, y2] if (0.0..=1.0).contains(&x1) && (0.0..=1.0).contains(&x2) => {
+                    Ok(TimingFunction::CubicBezier(x1, y1, x2, y2))
+                }
+                _ => Err(TimingFunctionParseError::UnknownMode),
+            };
+        }
+        if let Some(arguments) = arguments("steps") {
+            let (steps, position) = match arguments.as_slice() {
+                [steps] => (steps, StepPosition::End),
+                [steps, "jump-start" | "start"] => (steps, StepPosition::Start),
+                [steps, "jump-end" | "end"] => (steps, StepPosition::End),
+                _ => return Err(TimingFunctionParseError::UnknownMode),
+            };
+            return match steps.parse() {
+                Ok(steps) if steps > 0 => Ok(TimingFunction::Steps(steps, position)),
+                _ => Err(TimingFunctionParseError::UnknownMode),
+            };
+        }
+        Err(TimingFunctionParseError::UnknownMode)
+    }
+}
+
+/// 繰り返しごとの再生方向 (CSSのanimation-direction)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AnimationDirection {
+    #[default]
+    Normal,
+    Reverse,
+    /// 奇数回目は順方向、偶数回目は逆方向に再生する
+    Alternate,
+    /// 奇数回目は逆方向、偶数回目は順方向に再生する
+    AlternateReverse,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationDirectionParseError {
+    UnknownMode,
+}
+impl FromStr for AnimationDirection {
+    type Err = AnimationDirectionParseError;
+
+    fn from_str(value: &str) -> Result<AnimationDirection, Self::Err> {
+        match value {
+            "normal" => Ok(AnimationDirection::Normal),
+            "reverse" => Ok(AnimationDirection::Reverse),
+            "alternate" => Ok(AnimationDirection::Alternate),
+            "alternate-reverse" => Ok(AnimationDirection::AlternateReverse),
+            _ => Err(AnimationDirectionParseError::UnknownMode),
+        }
+    }
+}
+
+/// 再生前後にキーフレームの値を適用するか (CSSのanimation-fill-mode)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AnimationFillMode {
+    /// 再生中のみ適用する
+    #[default]
+    None,
+    /// 再生後も最後の値を適用し続ける
+    Forwards,
+    /// delayの間も最初の値を適用する
+    Backwards,
+    Both,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AnimationFillModeParseError {
+    UnknownMode,
+}
+impl FromStr for AnimationFillMode {
+    type Err = AnimationFillModeParseError;
+
+    fn from_str(value: &str) -> Result<AnimationFillMode, Self::Err> {
+        match value {
+            "none" => Ok(AnimationFillMode::None),
+            "forwards" => Ok(AnimationFillMode::Forwards),
+            "backwards" => Ok(AnimationFillMode::Backwards),
+            "both" => Ok(AnimationFillMode::Both),
+            _ => Err(AnimationFillModeParseError::UnknownMode),
+        }
+    }
+}
+
+/// `animation` プロパティの1つ分の値
+/// `slide 2s ease-in-out 0.5s infinite` のように、CSSと同様に順不同で指定する
+/// 時間は1つ目がduration、2つ目がdelayとなり、%指定はエレメントのdurationを基準にする
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimationShorthand {
+    pub name: String,
+    pub duration: Duration,
+    pub timing_function: TimingFunction,
+    pub delay: Duration,
+    /// 繰り返し回数で、`infinite` の場合は無限大になる
+    pub iteration_count: f64,
+    pub direction: AnimationDirection,
+    pub fill_mode: AnimationFillMode,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum AnimationParseError {
+    #[error("keyframes name is not specified")]
+    MissingName,
+    #[error("duration is not specified")]
+    MissingDuration,
+    #[error("unexpected value {0:?}")]
+    UnexpectedValue(String),
+    #[error(transparent)]
+    DurationParseError(#[from] DurationParseError),
+}
+
+impl FromStr for AnimationShorthand {
+    type Err = AnimationParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut name = None;
+        let mut duration = None;
+        let mut delay = None;
+        let mut timing_function = None;
+        let mut iteration_count = None;
+        let mut direction = None;
+        let mut fill_mode = None;
+        for token in split_top_level(value, ' ') {
+            let unexpected = || AnimationParseError::UnexpectedValue(token.to_owned());
+            if let Ok(count) = token.parse::<f64>() {
+                if iteration_count.is_some() || count < 0.0 {
+                    return Err(unexpected());
+                }
+                iteration_count = Some(count);
+            } else if token == "infinite" {
+                if iteration_count.is_some() {
+                    return Err(unexpected());
+                }
+                iteration_count = Some(f64::INFINITY);
+            } else if token.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '-') {
+                let time = token.parse::<Duration>()?;
+                if duration.is_none() {
+                    duration = Some(time);
+                } else if delay.is_none() {
+                    delay = Some(time);
+                } else {
+                    return Err(unexpected());
+                }
+            } else if timing_function.is_none()
+                && let Ok(parsed) = token.parse()
+            {
+                timing_function = Some(parsed);
+            } else if direction.is_none()
+                && let Ok(parsed) = token.parse()
+            {
+                direction = Some(parsed);
+            } else if fill_mode.is_none()
+                && let Ok(parsed) = token.parse()
+            {
+                fill_mode = Some(parsed);
+            } else if name.is_none() {
+                name = Some(token.to_owned());
+            } else {
+                return Err(unexpected());
+            }
+        }
+        Ok(AnimationShorthand {
+            name: name.ok_or(AnimationParseError::MissingName)?,
+            duration: duration.ok_or(AnimationParseError::MissingDuration)?,
+            timing_function: timing_function.unwrap_or(TimingFunction::EASE),
+            delay: delay.unwrap_or(Duration::Frame(0)),
+            iteration_count: iteration_count.unwrap_or(1.0),
+            direction: direction.unwrap_or_default(),
+            fill_mode: fill_mode.unwrap_or_default(),
+        })
+    }
+}
+
+/// `animation` プロパティの値をパースする
+/// `fade 1s, slide 2s` のようにカンマ区切りで複数指定でき、`none` の場合は空になる
+pub fn parse_animations(value: &str) -> Result<Vec<AnimationShorthand>, AnimationParseError> {
+    if value.trim() == "none" {
+        return Ok(vec![]);
+    }
+    split_top_level(value, ',')
+        .map(AnimationShorthand::from_str)
+        .collect()
+}
+
+/// `cubic-bezier(...)` のような括弧の中を除いて、区切り文字で分割する
+fn split_top_level(value: &str, delimiter: char) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    value
+        .split(move |c: char| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            depth == 0 && c == delimiter
+        })
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// キーフレームの位置と、その位置での値
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keyframe<T> {
+    /// アニメーションの中での位置(0.0〜1.0)
+    pub offset: f64,
+    pub value: T,
+}
+
+/// アニメーションで変化する1つのプロパティのキーフレームの列
+/// キーフレームは位置の昇順に並び、0.0と1.0の位置には必ずキーフレームがある
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnimationTrack {
+    X(Vec<Keyframe<f32>>),
+    Y(Vec<Keyframe<f32>>),
+    Width(Vec<Keyframe<f32>>),
+    Height(Vec<Keyframe<f32>>),
+    BackgroundColor(Vec<Keyframe<Color>>),
+    FontColor(Vec<Keyframe<Color>>),
+    Opacity(Vec<Keyframe<f32>>),
+}
+
+/// エレメントに適用される1つのキーフレームアニメーション
+/// 時間はエレメントの開始時刻からの時間(s)で表す
+#[derive(Clone, Debug, PartialEq)]
+pub struct Animation {
+    pub tracks: Vec<AnimationTrack>,
+    /// 1回の再生にかかる時間(s)
+    pub duration: f64,
+    pub delay: f64,
+    pub iteration_count: f64,
+    pub timing_function: TimingFunction,
+    pub direction: AnimationDirection,
+    pub fill_mode: AnimationFillMode,
+}
+
+impl Animation {
+    /// エレメントの開始時刻から `time` 秒後の、アニメーションの中での位置(0.0〜1.0)を返す
+    /// アニメーションが適用されない時刻の場合はNoneを返す
+    fn progress(&self, time: f64) -> Option<f64> {
+        let elapsed = time - self.delay;
+        let total = self.duration * self.iteration_count;
+        let (iteration, progress) = if elapsed < 0.0 {
+            if !matches!(
+                self.fill_mode,
+                AnimationFillMode::Backwards | AnimationFillMode::Both
+            ) {
+                return None;
+            }
+            (0.0, 0.0)
+        } else if elapsed >= total || self.duration <= 0.0 {
+            if !matches!(
+                self.fill_mode,
+                AnimationFillMode::Forwards | AnimationFillMode::Both
+            ) {
+                return None;
+            }
+            // 繰り返し回数が整数の場合は最後の繰り返しの終わりの値になる
+            let fraction = self.iteration_count.fract();
+            if self.iteration_count.is_infinite() {
+                (0.0, 1.0)
+            } else if fraction == 0.0 && self.iteration_count > 0.0 {
+                (self.iteration_count - 1.0, 1.0)
+            } else {
+                (self.iteration_count.floor(), fraction)
+            }
+        } else {
+            let position = elapsed / self.duration;
+            (position.floor(), position.fract())
+        };
+        let odd_iteration = iteration % 2.0 == 1.0;
+        let reversed = match self.direction {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => odd_iteration,
+            AnimationDirection::AlternateReverse => !odd_iteration,
+        };
+        Some(if reversed { 1.0 - progress } else { progress })
+    }
+
+    /// 時刻 `time` でのキーフレームの値をpropertiesに適用する
+    pub fn apply(&self, time: f64, properties: &mut AnimatedProperties) {
+        let Some(progress) = self.progress(time) else {
+            return;
+        };
+        let timing_function = &self.timing_function;
+        for track in &self.tracks {
+            match track {
+                AnimationTrack::X(keyframes) => {
+                    properties.x = interpolate(keyframes, progress, timing_function);
+                }
+                AnimationTrack::Y(keyframes) => {
+                    properties.y = interpolate(keyframes, progress, timing_function);
+                }
+                AnimationTrack::Width(keyframes) => {
+                    properties.width = interpolate(keyframes, progress, timing_function);
+                }
+                AnimationTrack::Height(keyframes) => {
+                    properties.height = interpolate(keyframes, progress, timing_function);
+                }
+                AnimationTrack::BackgroundColor(keyframes) => {
+                    properties.background_color =
+                        Some(interpolate(keyframes, progress, timing_function));
+                }
+                AnimationTrack::FontColor(keyframes) => {
+                    properties.font_color = Some(interpolate(keyframes, progress, timing_function));
+                }
+                AnimationTrack::Opacity(keyframes) => {
+                    properties.opacity = interpolate(keyframes, progress, timing_function);
+                }
+            }
+        }
+    }
+}
+
+/// アニメーションを適用したエレメントのプロパティ
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimatedProperties {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub background_color: Option<Color>,
+    /// Noneの場合はテキストのスタイルの色のまま
+    pub font_color: Option<Color>,
+    pub opacity: f32,
+}
+
+impl AnimatedProperties {
+    pub fn new(element_rect: &ElementRect, background_color: Option<Color>, opacity: f32) -> Self {
+        AnimatedProperties {
+            x: element_rect.x,
+            y: element_rect.y,
+            width: element_rect.width,
+            height: element_rect.height,
+            background_color,
+            font_color: None,
+            opacity,
+        }
+    }
+
+    /// アニメーションで変化した位置とサイズのElementRectを返す
+    pub fn element_rect(&self, element_rect: &ElementRect) -> ElementRect {
+        ElementRect {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+            ..*element_rect
+        }
+    }
+}
+
+/// キーフレームの間を補間できる値
+pub trait Interpolate: Copy {
+    fn interpolate(self, to: Self, ratio: f64) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(self, to: Self, ratio: f64) -> Self {
+        self + (to - self) * ratio as f32
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(self, to: Self, ratio: f64) -> Self {
+        let channel = |from: u8, to: u8| {
+            (from as f64 + (to as f64 - from as f64) * ratio)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+        Color {
+            r: channel(self.r, to.r),
+            g: channel(self.g, to.g),
+            b: channel(self.b, to.b),
+            a: channel(self.a, to.a),
+        }
+    }
+}
+
+/// `progress` の位置の値を、前後のキーフレームから補間して求める
+/// CSSと同様に、タイミング関数はキーフレームの区間ごとに適用する
+fn interpolate<T: Interpolate>(
+    keyframes: &[Keyframe<T>],
+    progress: f64,
+    timing_function: &TimingFunction,
+) -> T {
+    let (first, last) = (&keyframes[0], &keyframes[keyframes.len() - 1]);
+    if progress <= first.offset {
+        return first.value;
+    }
+    if progress >= last.offset {
+        return last.value;
+    }
+    // 同じ位置のキーフレームが複数ある場合は後のものを使う
+    let (from, to) = keyframes
+        .windows(2)
+        .map(|pair| (&pair[0], &pair[1]))
+        .rfind(|(from, to)| from.offset <= progress && progress < to.offset)
+        .unwrap();
+    let ratio = (progress - from.offset) / (to.offset - from.offset);
+    from.value
+        .interpolate(to.value, timing_function.apply(ratio))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframes<T>(values: &[(f64, T)]) -> Vec<Keyframe<T>>
+    where
+        T: Copy,
+    {
+        values
+            .iter()
+            .map(|&(offset, value)| Keyframe { offset, value })
+            .collect()
+    }
+
+    fn animation(tracks: Vec<AnimationTrack>) -> Animation {
+        Animation {
+            tracks,
+            duration: 2.0,
+            delay: 0.0,
+            iteration_count: 1.0,
+            timing_function: TimingFunction::Linear,
+            direction: AnimationDirection::Normal,
+            fill_mode: AnimationFillMode::None,
+        }
+    }
+
+    fn animated_x(animation: &Animation, time: f64) -> f32 {
+        let mut properties = AnimatedProperties {
+            x: -1.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            background_color: None,
+            font_color: None,
+            opacity: 1.0,
+        };
+        animation.apply(time, &mut properties);
+        properties.x
+    }
+
+    #[test]
+    fn test_parse_timing_function() {
+        assert_eq!("linear".parse(), Ok(TimingFunction::Linear));
+        assert_eq!("ease-in-out".parse(), Ok(TimingFunction::EASE_IN_OUT));
+        assert_eq!(
+            "cubic-bezier(0.1, -0.5, 0.9, 1.5)".parse(),
+            Ok(TimingFunction::CubicBezier(0.1, -0.5, 0.9, 1.5))
+        );
+        assert_eq!(
+            "steps(4, jump-start)".parse(),
+            Ok(TimingFunction::Steps(4, StepPosition::Start))
+        );
+        assert_eq!(
+            "steps(4)".parse(),
+            Ok(TimingFunction::Steps(4, StepPosition::End))
+        );
+        assert_eq!(
+            "cubic-bezier(1.5, 0, 0, 1)".parse::<TimingFunction>(),
+            Err(TimingFunctionParseError::UnknownMode)
+        );
+        assert_eq!(
+            "steps(0)".parse::<TimingFunction>(),
+            Err(TimingFunctionParseError::UnknownMode)
+        );
+    }
+
+    #[test]
+    fn test_timing_function_apply() {
+        assert_eq!(TimingFunction::Linear.apply(0.3), 0.3);
+        for timing_function in [TimingFunction::EASE, TimingFunction::EASE_IN_OUT] {
+            assert!(timing_function.apply(0.0).abs() < 1e-6);
+            assert!((timing_function.apply(1.0) - 1.0).abs() < 1e-6);
+        }
+        // ease-in-outは中央で対称になる
+        assert!((TimingFunction::EASE_IN_OUT.apply(0.5) - 0.5).abs() < 1e-6);
+        assert!(TimingFunction::EASE_IN.apply(0.25) < 0.25);
+        let steps = TimingFunction::Steps(4, StepPosition::End);
+        assert_eq!(steps.apply(0.3), 0.25);
+        let steps = TimingFunction::Steps(4, StepPosition::Start);
+        assert_eq!(steps.apply(0.3), 0.5);
+    }
+
+    #[test]
+    fn test_parse_animations() {
+        assert_eq!(
+            parse_animations("slide 2s 500ms"),
+            Ok(vec![AnimationShorthand {
+                name: "slide".to_string(),
+                duration: Duration::Second(2.0),
+                timing_function: TimingFunction::EASE,
+                delay: Duration::Second(0.5),
+                iteration_count: 1.0,
+                direction: AnimationDirection::Normal,
+                fill_mode: AnimationFillMode::None,
+            }])
+        );
+        assert_eq!(
+            parse_animations("slide 2s 1min"),
+            Err(AnimationParseError::DurationParseError(
+                DurationParseError::UnknownUnit
+            ))
+        );
+        assert_eq!(
+            parse_animations("slide 2s ease-in-out 30f infinite alternate forwards"),
+            Ok(vec![AnimationShorthand {
+                name: "slide".to_string(),
+                duration: Duration::Second(2.0),
+                timing_function: TimingFunction::EASE_IN_OUT,
+                delay: Duration::Frame(30),
+                iteration_count: f64::INFINITY,
+                direction: AnimationDirection::Alternate,
+                fill_mode: AnimationFillMode::Forwards,
+            }])
+        );
+        assert_eq!(
+            parse_animations("fade 50% cubic-bezier(0, 0, 1, 1), slide 1s 3"),
+            Ok(vec![
+                AnimationShorthand {
+                    name: "fade".to_string(),
+                    duration: Duration::Percent(50.0),
+                    timing_function: TimingFunction::CubicBezier(0.0, 0.0, 1.0, 1.0),
+                    delay: Duration::Frame(0),
+                    iteration_count: 1.0,
+                    direction: AnimationDirection::Normal,
+                    fill_mode: AnimationFillMode::None,
+                },
+                AnimationShorthand {
+                    name: "slide".to_string(),
+                    duration: Duration::Second(1.0),
+                    timing_function: TimingFunction::EASE,
+                    delay: Duration::Frame(0),
+                    iteration_count: 3.0,
+                    direction: AnimationDirection::Normal,
+                    fill_mode: AnimationFillMode::None,
+                },
+            ])
+        );
+        assert_eq!(parse_animations("none"), Ok(vec![]));
+        assert_eq!(
+            parse_animations("slide"),
+            Err(AnimationParseError::MissingDuration)
+        );
+        assert_eq!(
+            parse_animations("1s"),
+            Err(AnimationParseError::MissingName)
+        );
+        assert_eq!(
+            parse_animations("slide fade 1s"),
+            Err(AnimationParseError::UnexpectedValue("fade".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_animation_interpolation() {
+        let animation = animation(vec![AnimationTrack::X(keyframes(&[
+            (0.0, 0.0),
+            (0.5, 100.0),
+            (1.0, 0.0),
+        ]))]);
+        assert_eq!(animated_x(&animation, 0.0), 0.0);
+        assert_eq!(animated_x(&animation, 0.5), 50.0);
+        assert_eq!(animated_x(&animation, 1.0), 100.0);
+        assert_eq!(animated_x(&animation, 1.5), 50.0);
+        // fill-modeがnoneの場合は再生後に元の値に戻る
+        assert_eq!(animated_x(&animation, 2.0), -1.0);
+    }
+
+    #[test]
+    fn test_animation_delay_and_fill_mode() {
+        let track = AnimationTrack::X(keyframes(&[(0.0, 0.0), (1.0, 100.0)]));
+        let none = Animation {
+            delay: 1.0,
+            ..animation(vec![track.clone()])
+        };
+        assert_eq!(animated_x(&none, 0.5), -1.0);
+        assert_eq!(animated_x(&none, 2.0), 50.0);
+        assert_eq!(animated_x(&none, 3.5), -1.0);
+
+        let both = Animation {
+            fill_mode: AnimationFillMode::Both,
+            ..none
+        };
+        assert_eq!(animated_x(&both, 0.5), 0.0);
+        assert_eq!(animated_x(&both, 3.5), 100.0);
+
+        // 途中で終わる場合はその位置の値で止まる
+        let half = Animation {
+            iteration_count: 1.5,
+            fill_mode: AnimationFillMode::Forwards,
+            ..animation(vec![track])
+        };
+        assert_eq!(animated_x(&half, 10.0), 50.0);
+    }
+
+    #[test]
+    fn test_animation_iteration_and_direction() {
+        let track = AnimationTrack::X(keyframes(&[(0.0, 0.0), (1.0, 100.0)]));
+        let infinite = Animation {
+            iteration_count: f64::INFINITY,
+            ..animation(vec![track])
+        };
+        assert_eq!(animated_x(&infinite, 4.5), 25.0);
+
+        let alternate = Animation {
+            direction: AnimationDirection::Alternate,
+            ..infinite.clone()
+        };
+        assert_eq!(animated_x(&alternate, 0.5), 25.0);
+        assert_eq!(animated_x(&alternate, 2.5), 75.0);
+
+        let reverse = Animation {
+            direction: AnimationDirection::Reverse,
+            ..infinite
+        };
+        assert_eq!(animated_x(&reverse, 0.5), 75.0);
+    }
+
+    #[test]
+    fn test_color_interpolation() {
+        let animation = animation(vec![AnimationTrack::BackgroundColor(keyframes(&[
+            (0.0, Color::from(0, 0, 0, 0)),
+            (1.0, Color::from(255, 100, 0, 255)),
+        ]))]);
+        let mut properties = AnimatedProperties {
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            background_color: None,
+            font_color: None,
+            opacity: 1.0,
+        };
+        animation.apply(1.0, &mut properties);
+        assert_eq!(
+            properties.background_color,
+            Some(Color::from(128, 50, 0, 128))
+        );
+    }
+
+    #[test]
+    fn test_opacity_interpolation() {
+        let animation = animation(vec![AnimationTrack::Opacity(keyframes(&[
+            (0.0, 1.0),
+            (0.5, 0.0),
+            (1.0, 1.0),
+        ]))]);
+        let mut properties = AnimatedProperties {
+            x: 0.0,
+            y: 0.0,
+            width: 0.0,
+            height: 0.0,
+            background_color: None,
+            font_color: None,
+            opacity: 1.0,
+        };
+        animation.apply(1.5, &mut properties);
+        assert_eq!(properties.opacity, 0.5);
+    }
+}
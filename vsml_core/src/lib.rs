@@ -1,5 +1,9 @@
-use crate::schemas::{ObjectData, ObjectType, ProcessorInput};
+use crate::animation::AnimatedProperties;
+use crate::schemas::{
+    ObjectData, ObjectType, ProcessorInput, Transition, TransitionDirection, TransitionEffect,
+};
 
+pub mod animation;
 pub mod schemas;
 #[cfg(test)]
 mod tests;
@@ -43,7 +47,26 @@ impl Alignment {
     }
 }
 
+/// margin, paddingの上下左右の幅(px)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Edges {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl Edges {
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
 /// Alignment付きのRectの位置とサイズ
+/// width, heightはpaddingを含まない内容のサイズで、x, yとalignmentはmarginを含む外側の矩形に対して適用される
 #[derive(Debug)]
 pub struct ElementRect {
     pub alignment: Alignment,
@@ -52,15 +75,40 @@ pub struct ElementRect {
     pub y: f32,
     pub width: f32,
     pub height: f32,
+    pub margin: Edges,
+    pub padding: Edges,
 }
 
 impl ElementRect {
+    /// margin, paddingを含む外側の幅
+    pub fn outer_width(&self) -> f32 {
+        self.margin.horizontal() + self.padding.horizontal() + self.width
+    }
+    /// margin, paddingを含む外側の高さ
+    pub fn outer_height(&self) -> f32 {
+        self.margin.vertical() + self.padding.vertical() + self.height
+    }
+
+    /// 内容を描画する矩形を計算する
     fn calc_rendering_info(&self, outer_width: f32, outer_height: f32) -> RenderingInfo {
+        let info = self.calc_box_rendering_info(outer_width, outer_height);
+        RenderingInfo {
+            x: info.x + self.padding.left,
+            y: info.y + self.padding.top,
+            width: self.width,
+            height: self.height,
+            opacity: 1.0,
+            transform: Transform::IDENTITY,
+        }
+    }
+
+    /// 背景を描画する、paddingを含む矩形を計算する
+    fn calc_box_rendering_info(&self, outer_width: f32, outer_height: f32) -> RenderingInfo {
         let x = self.x
             - match self.alignment.x_axis() {
                 AlignmentSingle::Start => 0.0,
-                AlignmentSingle::Center => self.width / 2.0,
-                AlignmentSingle::End => self.width,
+                AlignmentSingle::Center => self.outer_width() / 2.0,
+                AlignmentSingle::End => self.outer_width(),
             }
             + match self.parent_alignment.x_axis() {
                 AlignmentSingle::Start => 0.0,
@@ -70,8 +118,8 @@ impl ElementRect {
         let y = self.y
             - match self.alignment.y_axis() {
                 AlignmentSingle::Start => 0.0,
-                AlignmentSingle::Center => self.height / 2.0,
-                AlignmentSingle::End => self.height,
+                AlignmentSingle::Center => self.outer_height() / 2.0,
+                AlignmentSingle::End => self.outer_height(),
             }
             + match self.parent_alignment.y_axis() {
                 AlignmentSingle::Start => 0.0,
@@ -79,20 +127,192 @@ impl ElementRect {
                 AlignmentSingle::End => outer_height,
             };
         RenderingInfo {
-            x,
-            y,
-            width: self.width,
-            height: self.height,
+            x: x + self.margin.left,
+            y: y + self.margin.top,
+            width: self.padding.horizontal() + self.width,
+            height: self.padding.vertical() + self.height,
+            opacity: 1.0,
+            transform: Transform::IDENTITY,
         }
     }
 }
 
+/// object-fit, object-positionで決まる、エレメントの内容の矩形の左上からの画像の位置とサイズ
+/// エレメントの矩形からはみ出した部分は描画されない
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObjectFitRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
 pub struct RenderBoxProperty {
     pub background_color: Option<schemas::Color>,
 }
 
-pub struct ImageEffectStyle {}
-pub struct AudioEffectStyle {}
+/// filterで指定された、描画した画像に順番に適用するフィルター
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImageEffectStyle {
+    pub filters: Vec<ImageFilter>,
+}
+
+/// 長さをピクセルに変換したフィルター関数
+/// 割合は1.0で100%、角度はラジアンになる
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageFilter {
+    /// ガウスぼかしの標準偏差(px)
+    Blur(f32),
+    Brightness(f32),
+    Contrast(f32),
+    Saturate(f32),
+    Grayscale(f32),
+    Sepia(f32),
+    HueRotate(f32),
+    Invert(f32),
+    /// 画像の不透明な部分の形の影を、(x, y)だけずらして画像の後ろに描画する
+    /// blurはぼかしの半径(px)で、標準偏差はその半分になる
+    DropShadow {
+        x: f32,
+        y: f32,
+        blur: f32,
+        color: schemas::Color,
+    },
+}
+
+/// audio-filterで指定された、エレメントの音声に順番に適用するエフェクト
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AudioEffectStyle {
+    pub filters: Vec<AudioFilter>,
+}
+
+/// 音声のエフェクト
+/// 周波数はHz、時間は秒、音量の比はdBで表す
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioFilter {
+    /// 振幅にかける倍率
+    Gain(f32),
+    HighPass {
+        frequency: f32,
+        q: f32,
+    },
+    LowPass {
+        frequency: f32,
+        q: f32,
+    },
+    /// frequencyを中心にgainだけ増幅するピーキングイコライザー
+    Equalizer {
+        frequency: f32,
+        gain: f32,
+        q: f32,
+    },
+    /// thresholdを超えた分の音量を1/ratioに圧縮する
+    Compressor {
+        threshold: f32,
+        ratio: f32,
+        attack: f32,
+        release: f32,
+    },
+    /// decayの時間で60dB減衰する残響を、mixの割合で元の音声に混ぜる
+    Reverb {
+        decay: f32,
+        mix: f32,
+    },
+}
+
+/// 2次元のアフィン変換
+/// 点(x, y)を(a * x + c * y + e, b * x + d * y + f)に移す
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform::scale(1.0, 1.0);
+
+    pub const fn translate(x: f32, y: f32) -> Transform {
+        Transform {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            e: x,
+            f: y,
+        }
+    }
+    pub const fn scale(x: f32, y: f32) -> Transform {
+        Transform {
+            a: x,
+            b: 0.0,
+            c: 0.0,
+            d: y,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+    /// y軸が下向きの座標で、時計回りにangle(rad)回転する
+    pub fn rotate(angle: f32) -> Transform {
+        let (sin, cos) = angle.sin_cos();
+        Transform {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+    /// x軸方向にx(rad)、y軸方向にy(rad)傾ける
+    pub fn skew(x: f32, y: f32) -> Transform {
+        Transform {
+            a: 1.0,
+            b: y.tan(),
+            c: x.tan(),
+            d: 1.0,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    /// (x, y)を原点とした変換にする
+    pub fn around(self, x: f32, y: f32) -> Transform {
+        Transform::translate(x, y) * self * Transform::translate(-x, -y)
+    }
+
+    pub fn transform_point(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
+
+/// rhsを適用してからselfを適用する変換
+impl std::ops::Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Transform {
+        Transform {
+            a: self.a * rhs.a + self.c * rhs.b,
+            b: self.b * rhs.a + self.d * rhs.b,
+            c: self.a * rhs.c + self.c * rhs.d,
+            d: self.b * rhs.c + self.d * rhs.d,
+            e: self.a * rhs.e + self.c * rhs.f + self.e,
+            f: self.b * rhs.e + self.d * rhs.f + self.f,
+        }
+    }
+}
 
 /// rendererから見た左上の座標とサイズ
 #[derive(Debug)]
@@ -101,6 +321,10 @@ pub struct RenderingInfo {
     pub y: f32,
     pub width: f32,
     pub height: f32,
+    /// 描画するときの不透明度(0.0〜1.0)
+    pub opacity: f32,
+    /// 描画する矩形に適用する、rendererの座標での変換
+    pub transform: Transform,
 }
 
 #[cfg_attr(test, mockall::automock(type Image=tests::MockImage;))]
@@ -141,6 +365,26 @@ where
     }
 }
 
+/// transitionで前の子要素から切り替わっている途中であれば、その効果と進み具合(0.0〜1.0)を返す
+fn transition_progress<I, A>(
+    object: &ObjectData<I, A>,
+    target_time: f64,
+) -> Option<(Transition, f64)> {
+    match object {
+        &ObjectData::Element {
+            start_time,
+            transition: Some(transition),
+            ..
+        } => {
+            let elapsed = target_time - start_time;
+            (0.0..transition.duration)
+                .contains(&elapsed)
+                .then(|| (transition, elapsed / transition.duration))
+        }
+        _ => None,
+    }
+}
+
 pub fn render_frame_image<R, A>(
     &schemas::IVData {
         resolution_x,
@@ -174,6 +418,12 @@ where
                 ref attributes,
                 ref children,
                 background_color,
+                opacity,
+                transform,
+                transform_origin,
+                ref image_effect_style,
+                object_fit_rect,
+                ref animations,
                 ..
             } => {
                 let range = start_time..start_time + duration;
@@ -182,46 +432,82 @@ where
                 }
                 let target_time = target_time - start_time;
 
+                // アニメーションで変化するプロパティを適用する
+                let mut animated = AnimatedProperties::new(element_rect, background_color, opacity);
+                for animation in animations {
+                    animation.apply(target_time, &mut animated);
+                }
+                let element_rect = &animated.element_rect(element_rect);
+                let background_color = animated.background_color;
+                let opacity = animated.opacity;
+                if opacity <= 0.0 {
+                    return;
+                }
+
+                // 不透明度が1未満かfilterがある場合は、背景と内容をまとめて1枚の画像に描画してから
+                // filterを適用し、半透明で重ねる
+                let has_filters = !image_effect_style.filters.is_empty();
+                let mut layer_renderer =
+                    (opacity < 1.0 || has_filters).then(|| rendering_context.create_renderer());
+                let element_renderer = match &mut layer_renderer {
+                    Some(layer_renderer) => layer_renderer,
+                    None => &mut *renderer,
+                };
+
+                // transform-originを原点とした変換を、親要素の座標での変換にする
+                let box_rendering_info =
+                    element_rect.calc_box_rendering_info(outer_width, outer_height);
+                let transform = transform.around(
+                    box_rendering_info.x + transform_origin.0,
+                    box_rendering_info.y + transform_origin.1,
+                );
+
                 // 背景色のレンダリング
                 if let Some(background_color) = background_color {
                     let property = RenderBoxProperty {
                         background_color: Some(background_color),
                     };
-                    let rendering_info =
-                        element_rect.calc_rendering_info(outer_width, outer_height);
-                    renderer.render_box(property, rendering_info);
+                    let rendering_info = RenderingInfo {
+                        transform,
+                        ..box_rendering_info
+                    };
+                    element_renderer.render_box(property, rendering_info);
                 }
 
                 match object_type {
+                    ObjectType::Wrap if children.is_empty() => {}
                     ObjectType::Wrap => {
-                        if children.is_empty() {
-                            return;
-                        }
                         let mut inner_renderer = rendering_context.create_renderer();
-                        children.iter().for_each(|object| {
-                            render_inner(
-                                rendering_context,
-                                &mut inner_renderer,
-                                object,
-                                target_time,
-                                element_rect.width,
-                                element_rect.height,
-                            )
-                        });
+                        render_children(
+                            rendering_context,
+                            &mut inner_renderer,
+                            children,
+                            target_time,
+                            element_rect.width,
+                            element_rect.height,
+                        );
                         let child_image = inner_renderer.render(
                             element_rect.width.ceil() as u32,
                             element_rect.height.ceil() as u32,
                         );
-                        let rendering_info =
-                            element_rect.calc_rendering_info(outer_width, outer_height);
-                        renderer.render_image(child_image, rendering_info);
+                        let rendering_info = RenderingInfo {
+                            transform,
+                            ..element_rect.calc_rendering_info(outer_width, outer_height)
+                        };
+                        element_renderer.render_image(child_image, rendering_info);
                     }
                     ObjectType::Other(processor) => {
                         // 子要素からTextDataを収集
                         let mut text_data_list: Vec<schemas::TextData> = Vec::new();
                         for child in children {
                             if let ObjectData::Text(data) = child {
-                                text_data_list.extend(data.iter().cloned());
+                                text_data_list.extend(data.iter().map(|data| {
+                                    let mut data = data.clone();
+                                    if let Some(font_color) = animated.font_color {
+                                        data.style.color = font_color;
+                                    }
+                                    data
+                                }));
                             }
                         }
 
@@ -231,16 +517,14 @@ where
                         } else if !children.is_empty() {
                             // img, vidなどの場合: 子要素をレンダリング
                             let mut inner_renderer = rendering_context.create_renderer();
-                            children.iter().for_each(|object| {
-                                render_inner(
-                                    rendering_context,
-                                    &mut inner_renderer,
-                                    object,
-                                    target_time,
-                                    element_rect.width,
-                                    element_rect.height,
-                                )
-                            });
+                            render_children(
+                                rendering_context,
+                                &mut inner_renderer,
+                                children,
+                                target_time,
+                                element_rect.width,
+                                element_rect.height,
+                            );
                             let image = inner_renderer.render(
                                 element_rect.width.ceil() as u32,
                                 element_rect.height.ceil() as u32,
@@ -252,12 +536,36 @@ where
 
                         println!("[debug] target_time: {}", target_time);
                         let result = processor.process_image(target_time, attributes, input);
+                        // object-fitが指定されている場合は、エレメントの矩形の中に収めた画像にしてから描画する
+                        let result = match (result, object_fit_rect) {
+                            (Some(result), Some(fit_rect)) => {
+                                let mut fit_renderer = rendering_context.create_renderer();
+                                fit_renderer.render_image(
+                                    result,
+                                    RenderingInfo {
+                                        x: fit_rect.x,
+                                        y: fit_rect.y,
+                                        width: fit_rect.width,
+                                        height: fit_rect.height,
+                                        opacity: 1.0,
+                                        transform: Transform::IDENTITY,
+                                    },
+                                );
+                                Some(fit_renderer.render(
+                                    element_rect.width.ceil() as u32,
+                                    element_rect.height.ceil() as u32,
+                                ))
+                            }
+                            (result, _) => result,
+                        };
                         if let Some(result) = result {
+                            let info = RenderingInfo {
+                                transform,
+                                ..element_rect.calc_rendering_info(outer_width, outer_height)
+                            };
                             let rendering_info = if processor.has_default_image_size() {
-                                element_rect.calc_rendering_info(outer_width, outer_height)
+                                info
                             } else {
-                                let info =
-                                    element_rect.calc_rendering_info(outer_width, outer_height);
                                 let result_size = rendering_context.get_size(&result);
                                 RenderingInfo {
                                     width: result_size.width,
@@ -265,10 +573,30 @@ where
                                     ..info
                                 }
                             };
-                            renderer.render_image(result, rendering_info);
+                            element_renderer.render_image(result, rendering_info);
                         }
                     }
                 }
+
+                if let Some(layer_renderer) = layer_renderer {
+                    let mut layer_image = layer_renderer
+                        .render(outer_width.ceil() as u32, outer_height.ceil() as u32);
+                    if has_filters {
+                        layer_image =
+                            rendering_context.apply_style(layer_image, image_effect_style.clone());
+                    }
+                    renderer.render_image(
+                        layer_image,
+                        RenderingInfo {
+                            x: 0.0,
+                            y: 0.0,
+                            width: outer_width,
+                            height: outer_height,
+                            opacity,
+                            transform: Transform::IDENTITY,
+                        },
+                    );
+                }
             }
             // TextDataは親要素のProcessorで処理される
             ObjectData::Text(_) => {
@@ -278,6 +606,195 @@ where
         }
     }
 
+    /// 子要素を順に描画する
+    /// transitionで切り替わっている間は、前の子要素と次の子要素をまとめて描画する
+    fn render_children<R, A>(
+        rendering_context: &mut R,
+        renderer: &mut R::Renderer,
+        children: &[ObjectData<R::Image, A>],
+        target_time: f64,
+        outer_width: f32,
+        outer_height: f32,
+    ) where
+        R: RenderingContext,
+    {
+        for (i, object) in children.iter().enumerate() {
+            // 次の子要素への切り替え中は、次の子要素と一緒に描画する
+            if children
+                .get(i + 1)
+                .and_then(|next| transition_progress(next, target_time))
+                .is_some()
+            {
+                continue;
+            }
+            match (
+                i.checked_sub(1).map(|i| &children[i]),
+                transition_progress(object, target_time),
+            ) {
+                (Some(previous), Some((transition, progress))) => render_transition(
+                    rendering_context,
+                    renderer,
+                    previous,
+                    object,
+                    transition,
+                    progress as f32,
+                    target_time,
+                    outer_width,
+                    outer_height,
+                ),
+                _ => render_inner(
+                    rendering_context,
+                    renderer,
+                    object,
+                    target_time,
+                    outer_width,
+                    outer_height,
+                ),
+            }
+        }
+    }
+
+    /// 親要素と同じサイズの画像に1つの子要素を描画する
+    fn render_layer<R, A>(
+        rendering_context: &mut R,
+        object: &ObjectData<R::Image, A>,
+        target_time: f64,
+        outer_width: f32,
+        outer_height: f32,
+    ) -> R::Image
+    where
+        R: RenderingContext,
+    {
+        let mut layer_renderer = rendering_context.create_renderer();
+        render_inner(
+            rendering_context,
+            &mut layer_renderer,
+            object,
+            target_time,
+            outer_width,
+            outer_height,
+        );
+        layer_renderer.render(outer_width.ceil() as u32, outer_height.ceil() as u32)
+    }
+
+    /// transitionの効果で、前の子要素から次の子要素に切り替わる途中を描画する
+    /// progressは切り替えの進み具合(0.0〜1.0)
+    #[allow(clippy::too_many_arguments)]
+    fn render_transition<R, A>(
+        rendering_context: &mut R,
+        renderer: &mut R::Renderer,
+        previous: &ObjectData<R::Image, A>,
+        next: &ObjectData<R::Image, A>,
+        transition: Transition,
+        progress: f32,
+        target_time: f64,
+        outer_width: f32,
+        outer_height: f32,
+    ) where
+        R: RenderingContext,
+    {
+        let layer_info = |x, y, opacity| RenderingInfo {
+            x,
+            y,
+            width: outer_width,
+            height: outer_height,
+            opacity,
+            transform: Transform::IDENTITY,
+        };
+        let mut render_layer = |object| {
+            render_layer(
+                rendering_context,
+                object,
+                target_time,
+                outer_width,
+                outer_height,
+            )
+        };
+        match transition.effect {
+            TransitionEffect::Crossfade => {
+                let previous_image = render_layer(previous);
+                let next_image = render_layer(next);
+                renderer.render_image(previous_image, layer_info(0.0, 0.0, 1.0 - progress));
+                renderer.render_image(next_image, layer_info(0.0, 0.0, progress));
+            }
+            TransitionEffect::FadeThroughColor(color) => {
+                // 前半は前の子要素から色へ、後半は色から次の子要素へフェードする
+                let (image, opacity) = if progress < 0.5 {
+                    (render_layer(previous), progress * 2.0)
+                } else {
+                    (render_layer(next), (1.0 - progress) * 2.0)
+                };
+                renderer.render_image(image, layer_info(0.0, 0.0, 1.0));
+                renderer.render_box(
+                    RenderBoxProperty {
+                        background_color: Some(color),
+                    },
+                    layer_info(0.0, 0.0, opacity),
+                );
+            }
+            TransitionEffect::Slide(direction) => {
+                // 前の子要素を押し出しながら、反対側から次の子要素が入ってくる
+                let (dx, dy) = match direction {
+                    TransitionDirection::Left => (-outer_width, 0.0),
+                    TransitionDirection::Right => (outer_width, 0.0),
+                    TransitionDirection::Up => (0.0, -outer_height),
+                    TransitionDirection::Down => (0.0, outer_height),
+                };
+                let previous_image = render_layer(previous);
+                let next_image = render_layer(next);
+                renderer.render_image(
+                    previous_image,
+                    layer_info(dx * progress, dy * progress, 1.0),
+                );
+                renderer.render_image(
+                    next_image,
+                    layer_info(dx * (progress - 1.0), dy * (progress - 1.0), 1.0),
+                );
+            }
+            TransitionEffect::Wipe(direction) => {
+                // 境界が動く方向の反対側から、次の子要素を見せる範囲を広げていく
+                let (x, y, width, height) = match direction {
+                    TransitionDirection::Left => (
+                        outer_width * (1.0 - progress),
+                        0.0,
+                        outer_width * progress,
+                        outer_height,
+                    ),
+                    TransitionDirection::Right => (0.0, 0.0, outer_width * progress, outer_height),
+                    TransitionDirection::Up => (
+                        0.0,
+                        outer_height * (1.0 - progress),
+                        outer_width,
+                        outer_height * progress,
+                    ),
+                    TransitionDirection::Down => (0.0, 0.0, outer_width, outer_height * progress),
+                };
+                let previous_image = render_layer(previous);
+                renderer.render_image(previous_image, layer_info(0.0, 0.0, 1.0));
+                let (width, height) = (width.ceil(), height.ceil());
+                if width == 0.0 || height == 0.0 {
+                    return;
+                }
+                // 見せる範囲だけを切り出してから重ねる
+                let next_image = render_layer(next);
+                let mut clip_renderer = rendering_context.create_renderer();
+                clip_renderer.render_image(next_image, layer_info(-x, -y, 1.0));
+                let clipped_image = clip_renderer.render(width as u32, height as u32);
+                renderer.render_image(
+                    clipped_image,
+                    RenderingInfo {
+                        x,
+                        y,
+                        width,
+                        height,
+                        opacity: 1.0,
+                        transform: Transform::IDENTITY,
+                    },
+                );
+            }
+        }
+    }
+
     let mut renderer = rendering_context.create_renderer();
     render_inner(
         &mut rendering_context,
@@ -290,12 +807,27 @@ where
     renderer.render(resolution_x, resolution_y)
 }
 
+/// 音声の始まりと終わりで音量を0から変化させる時間(s)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AudioFade {
+    pub fade_in: f64,
+    pub fade_out: f64,
+}
+
 #[cfg_attr(test, mockall::automock(type Audio=tests::MockAudio;))]
 pub trait Mixer {
     type Audio;
     /// offset_time is the time in seconds from the start of the audio
     /// volume is the volume multiplier (1.0 = 100%)
-    fn mix_audio(&mut self, _audio: Self::Audio, offset_time: f64, duration: f64, volume: f64);
+    /// fade is applied to the start and the end of the mixed duration
+    fn mix_audio(
+        &mut self,
+        _audio: Self::Audio,
+        offset_time: f64,
+        duration: f64,
+        volume: f64,
+        fade: AudioFade,
+    );
     fn mix(self, duration: f64) -> Self::Audio;
 }
 
@@ -322,6 +854,21 @@ where
     }
 }
 
+/// transitionで前後の子要素と重なっている間は、音声をクロスフェードする
+fn transition_audio_fade<I, A>(children: &[ObjectData<I, A>], index: usize) -> AudioFade {
+    let transition_duration = |object: Option<&ObjectData<I, A>>| match object {
+        Some(ObjectData::Element {
+            transition: Some(transition),
+            ..
+        }) => transition.duration,
+        _ => 0.0,
+    };
+    AudioFade {
+        fade_in: transition_duration(children.get(index)),
+        fade_out: transition_duration(children.get(index + 1)),
+    }
+}
+
 pub fn mix_audio<M, I>(
     &schemas::IVData {
         ref object,
@@ -339,6 +886,7 @@ where
         object: &ObjectData<I, M::Audio>,
         sampling_rate: u32,
         ancestor_duration: f64,
+        fade: AudioFade,
     ) where
         M: MixingContext,
     {
@@ -348,6 +896,7 @@ where
                 duration,
                 start_time,
                 audio_volume,
+                ref audio_effect_style,
                 ref children,
                 ..
             } => {
@@ -355,21 +904,24 @@ where
                     return;
                 }
                 let mut inner_mixer = mixing_context.create_mixer(sampling_rate);
-                children.iter().for_each(|object| {
-                    mix_inner(
-                        mixing_context,
-                        &mut inner_mixer,
-                        object,
-                        sampling_rate,
-                        ancestor_duration.min(duration),
-                    )
-                });
-                let child_audio = inner_mixer.mix(ancestor_duration.min(duration));
+                mix_children(
+                    mixing_context,
+                    &mut inner_mixer,
+                    children,
+                    sampling_rate,
+                    ancestor_duration.min(duration),
+                );
+                let child_audio = apply_audio_effect_style(
+                    mixing_context,
+                    inner_mixer.mix(ancestor_duration.min(duration)),
+                    audio_effect_style,
+                );
                 mixer.mix_audio(
                     child_audio,
                     start_time,
                     ancestor_duration.min(duration),
                     audio_volume,
+                    fade,
                 );
             }
             &ObjectData::Element {
@@ -377,30 +929,32 @@ where
                 duration,
                 start_time,
                 audio_volume,
+                ref audio_effect_style,
                 ref attributes,
                 ref children,
                 ..
             } => {
                 let child_audio = (!children.is_empty()).then(|| {
                     let mut inner_mixer = mixing_context.create_mixer(sampling_rate);
-                    children.iter().for_each(|object| {
-                        mix_inner(
-                            mixing_context,
-                            &mut inner_mixer,
-                            object,
-                            sampling_rate,
-                            ancestor_duration.min(duration),
-                        )
-                    });
+                    mix_children(
+                        mixing_context,
+                        &mut inner_mixer,
+                        children,
+                        sampling_rate,
+                        ancestor_duration.min(duration),
+                    );
                     inner_mixer.mix(ancestor_duration.min(duration))
                 });
                 let result = processor.process_audio(attributes, child_audio);
                 if let Some(result) = result {
+                    let result =
+                        apply_audio_effect_style(mixing_context, result, audio_effect_style);
                     mixer.mix_audio(
                         result,
                         start_time,
                         ancestor_duration.min(duration),
                         audio_volume,
+                        fade,
                     );
                 }
             }
@@ -408,6 +962,43 @@ where
         }
     }
 
+    /// audio-filterが指定されている場合のみエフェクトを適用する
+    fn apply_audio_effect_style<M>(
+        mixing_context: &mut M,
+        audio: M::Audio,
+        audio_effect_style: &AudioEffectStyle,
+    ) -> M::Audio
+    where
+        M: MixingContext,
+    {
+        if audio_effect_style.filters.is_empty() {
+            audio
+        } else {
+            mixing_context.apply_style(audio, audio_effect_style.clone())
+        }
+    }
+
+    fn mix_children<M, I>(
+        mixing_context: &mut M,
+        mixer: &mut M::Mixer,
+        children: &[ObjectData<I, M::Audio>],
+        sampling_rate: u32,
+        ancestor_duration: f64,
+    ) where
+        M: MixingContext,
+    {
+        for (i, object) in children.iter().enumerate() {
+            mix_inner(
+                mixing_context,
+                mixer,
+                object,
+                sampling_rate,
+                ancestor_duration,
+                transition_audio_fade(children, i),
+            );
+        }
+    }
+
     let mut mixer = mixing_context.create_mixer(sampling_rate);
     if let &ObjectData::Element { duration, .. } = object {
         mix_inner(
@@ -416,6 +1007,7 @@ where
             object,
             sampling_rate,
             duration,
+            AudioFade::default(),
         );
         mixer.mix(duration)
     } else {
@@ -1,4 +1,8 @@
-use crate::ElementRect;
+use crate::animation::Animation;
+use crate::{
+    Alignment, AudioEffectStyle, AudioFilter, ElementRect, ImageEffectStyle, ObjectFitRect,
+    Transform,
+};
 use phf::phf_map;
 use regex::Regex;
 use std::collections::HashMap;
@@ -283,7 +287,8 @@ pub enum TimeUnit {
 #[derive(Clone, Debug, PartialEq)]
 pub enum Duration {
     Percent(f64),
-    Frame(usize),
+    /// 負の値はtime-marginでのみ使える
+    Frame(isize),
     Second(f64),
     Fit,
 }
@@ -304,6 +309,11 @@ impl FromStr for Duration {
             Ok(Duration::Fit)
         } else if value == "0" {
             Ok(Duration::Frame(0))
+        } else if let Some(value) = value.strip_suffix("ms") {
+            let val: f64 = value
+                .parse()
+                .map_err(|_| DurationParseError::NumberParseError)?;
+            Ok(Duration::Second(val / 1000.0))
         } else if let Some(value) = value.strip_suffix('s') {
             let val = value
                 .parse()
@@ -325,6 +335,44 @@ impl FromStr for Duration {
     }
 }
 
+/// time-margin, time-paddingのように1〜2個のDurationで前後を指定する値
+/// 1個の場合は前後とも同じ値になる
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeEdges {
+    pub start: Duration,
+    pub end: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum TimeEdgesParseError {
+    #[error("expected 1 or 2 values")]
+    InvalidValueCount,
+    #[error(transparent)]
+    DurationParseError(#[from] DurationParseError),
+}
+
+impl FromStr for TimeEdges {
+    type Err = TimeEdgesParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let durations = value
+            .split_whitespace()
+            .map(Duration::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        match durations.as_slice() {
+            [both] => Ok(TimeEdges {
+                start: both.clone(),
+                end: both.clone(),
+            }),
+            [start, end] => Ok(TimeEdges {
+                start: start.clone(),
+                end: end.clone(),
+            }),
+            _ => Err(TimeEdgesParseError::InvalidValueCount),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum AudioVolume {
     Percent(f64),
@@ -353,6 +401,38 @@ impl FromStr for AudioVolume {
     }
 }
 
+/// 0.5のような数値または50%のような百分率で指定する不透明度
+/// 0.0〜1.0の範囲外の値は範囲内に丸められる
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Opacity(pub f32);
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum OpacityParseError {
+    #[error("number parse error")]
+    NumberParseError,
+}
+
+impl FromStr for Opacity {
+    type Err = OpacityParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let opacity = if let Some(value) = value.strip_suffix('%') {
+            value
+                .parse::<f32>()
+                .map_err(|_| OpacityParseError::NumberParseError)?
+                / 100.0
+        } else {
+            value
+                .parse::<f32>()
+                .map_err(|_| OpacityParseError::NumberParseError)?
+        };
+        if opacity.is_nan() {
+            return Err(OpacityParseError::NumberParseError);
+        }
+        Ok(Opacity(opacity.clamp(0.0, 1.0)))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Length {
     /// ピクセルの絶対値
@@ -405,6 +485,538 @@ impl FromStr for Length {
     }
 }
 
+/// margin, paddingのように1〜4個のLengthで上下左右を指定する値
+/// 値の個数による割り当てはCSSのショートハンドと同じ
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgeLengths {
+    pub top: Length,
+    pub right: Length,
+    pub bottom: Length,
+    pub left: Length,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum EdgeLengthsParseError {
+    #[error("expected 1 to 4 values")]
+    InvalidValueCount,
+    #[error(transparent)]
+    LengthParseError(#[from] LengthParseError),
+}
+
+impl FromStr for EdgeLengths {
+    type Err = EdgeLengthsParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let lengths = value
+            .split_whitespace()
+            .map(Length::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        let (top, right, bottom, left) = match lengths.as_slice() {
+            [all] => (all, all, all, all),
+            [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+            [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+            [top, right, bottom, left] => (top, right, bottom, left),
+            _ => return Err(EdgeLengthsParseError::InvalidValueCount),
+        };
+        Ok(EdgeLengths {
+            top: top.clone(),
+            right: right.clone(),
+            bottom: bottom.clone(),
+            left: left.clone(),
+        })
+    }
+}
+
+/// img, vidなどの画像をwidth, heightで決まる矩形にどう収めるか
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ObjectFit {
+    /// 矩形いっぱいに引き伸ばす
+    Fill,
+    /// アスペクト比を維持して矩形に収まるように拡大縮小する
+    Contain,
+    /// アスペクト比を維持して矩形を覆うように拡大縮小する
+    Cover,
+    /// 拡大縮小しない
+    None,
+    /// NoneとContainのうち小さくなる方
+    ScaleDown,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ObjectFitParseError {
+    UnknownMode,
+}
+impl FromStr for ObjectFit {
+    type Err = ObjectFitParseError;
+
+    fn from_str(value: &str) -> Result<ObjectFit, Self::Err> {
+        match value {
+            "fill" => Ok(ObjectFit::Fill),
+            "contain" => Ok(ObjectFit::Contain),
+            "cover" => Ok(ObjectFit::Cover),
+            "none" => Ok(ObjectFit::None),
+            "scale-down" => Ok(ObjectFit::ScaleDown),
+            _ => Err(ObjectFitParseError::UnknownMode),
+        }
+    }
+}
+
+/// object-positionの値
+/// `left`, `center`, `bottom` などのキーワードは0%, 50%, 100%として扱う
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectPosition {
+    pub x: Length,
+    pub y: Length,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum ObjectPositionParseError {
+    #[error("expected 1 or 2 values")]
+    InvalidValueCount,
+    #[error("invalid keyword combination")]
+    InvalidKeyword,
+    #[error(transparent)]
+    LengthParseError(#[from] LengthParseError),
+}
+
+impl FromStr for ObjectPosition {
+    type Err = ObjectPositionParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        /// 値が水平方向と垂直方向のどちらに使えるか
+        enum Component {
+            Horizontal(Length),
+            Vertical(Length),
+            Either(Length),
+        }
+        fn parse_component(value: &str) -> Result<Component, LengthParseError> {
+            Ok(match value {
+                "left" => Component::Horizontal(Length::Percent(0.0)),
+                "right" => Component::Horizontal(Length::Percent(100.0)),
+                "top" => Component::Vertical(Length::Percent(0.0)),
+                "bottom" => Component::Vertical(Length::Percent(100.0)),
+                "center" => Component::Either(Length::Percent(50.0)),
+                value => Component::Either(value.parse()?),
+            })
+        }
+
+        let components = value
+            .split_whitespace()
+            .map(parse_component)
+            .collect::<Result<Vec<_>, _>>()?;
+        let center = || Length::Percent(50.0);
+        let (x, y) = match components.as_slice() {
+            [Component::Vertical(y)] => (center(), y.clone()),
+            [Component::Horizontal(x) | Component::Either(x)] => (x.clone(), center()),
+            // `top left` のように垂直方向が先に来た場合は入れ替える
+            [
+                Component::Vertical(y),
+                Component::Horizontal(x) | Component::Either(x),
+            ] => (x.clone(), y.clone()),
+            [
+                Component::Horizontal(x) | Component::Either(x),
+                Component::Vertical(y) | Component::Either(y),
+            ] => (x.clone(), y.clone()),
+            [_, _] => return Err(ObjectPositionParseError::InvalidKeyword),
+            _ => return Err(ObjectPositionParseError::InvalidValueCount),
+        };
+        Ok(ObjectPosition { x, y })
+    }
+}
+
+/// transformプロパティの1つの変換関数
+/// 角度はラジアンで、時計回りが正になる
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransformFunction {
+    Translate(Length, Length),
+    Scale(f32, f32),
+    Rotate(f32),
+    Skew(f32, f32),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum TransformParseError {
+    #[error("invalid syntax")]
+    InvalidSyntax,
+    #[error("unknown function")]
+    UnknownFunction,
+    #[error("invalid number of arguments")]
+    InvalidArgumentCount,
+    #[error("number parse error")]
+    NumberParseError,
+    #[error(transparent)]
+    AngleParseError(#[from] AngleParseError),
+    #[error(transparent)]
+    LengthParseError(#[from] LengthParseError),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum AngleParseError {
+    #[error("number parse error")]
+    NumberParseError,
+    #[error("unknown angle unit")]
+    UnknownUnit,
+}
+
+/// `deg`, `rad`, `grad`, `turn` の角度をラジアンに変換する
+fn parse_angle(value: &str) -> Result<f32, AngleParseError> {
+    let parse = |value: &str| {
+        value
+            .parse::<f32>()
+            .map_err(|_| AngleParseError::NumberParseError)
+    };
+    if value == "0" {
+        Ok(0.0)
+    } else if let Some(value) = value.strip_suffix("deg") {
+        Ok(parse(value)?.to_radians())
+    } else if let Some(value) = value.strip_suffix("grad") {
+        Ok(parse(value)? * std::f32::consts::PI / 200.0)
+    } else if let Some(value) = value.strip_suffix("rad") {
+        parse(value)
+    } else if let Some(value) = value.strip_suffix("turn") {
+        Ok(parse(value)? * std::f32::consts::TAU)
+    } else {
+        Err(AngleParseError::UnknownUnit)
+    }
+}
+
+/// `name(args) name(args)` のように空白区切りで並んだ関数を、関数名と引数の組に分ける
+/// 引数の中の括弧は対応する閉じ括弧までを引数として扱う
+fn split_function_calls(value: &str) -> Option<Vec<(&str, &str)>> {
+    let mut calls = vec![];
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        let (name, args) = rest.split_once('(')?;
+        let mut depth = 0;
+        let end = args.char_indices().find_map(|(i, c)| match c {
+            '(' => {
+                depth += 1;
+                None
+            }
+            ')' if depth == 0 => Some(i),
+            ')' => {
+                depth -= 1;
+                None
+            }
+            _ => None,
+        })?;
+        calls.push((name.trim(), &args[..end]));
+        rest = args[end + 1..].trim_start();
+    }
+    Some(calls)
+}
+
+/// transformの値を変換関数の列にする
+/// `translate(10px, 20%) rotate(15deg)` のように空白区切りで並べ、`none` は空の列になる
+pub fn parse_transform(value: &str) -> Result<Vec<TransformFunction>, TransformParseError> {
+    if value.trim() == "none" {
+        return Ok(vec![]);
+    }
+    let calls = split_function_calls(value).ok_or(TransformParseError::InvalidSyntax)?;
+    let mut functions = vec![];
+    for (name, args) in calls {
+        let args = args.split(',').map(str::trim).collect::<Vec<_>>();
+        let number = |value: &str| {
+            value
+                .parse::<f32>()
+                .map_err(|_| TransformParseError::NumberParseError)
+        };
+        let zero = || Length::Pixel(0.0);
+        let function = match (name, args.as_slice()) {
+            ("translate", [x]) => TransformFunction::Translate(x.parse()?, zero()),
+            ("translate", [x, y]) => TransformFunction::Translate(x.parse()?, y.parse()?),
+            ("translateX", [x]) => TransformFunction::Translate(x.parse()?, zero()),
+            ("translateY", [y]) => TransformFunction::Translate(zero(), y.parse()?),
+            ("scale", [s]) => TransformFunction::Scale(number(s)?, number(s)?),
+            ("scale", [x, y]) => TransformFunction::Scale(number(x)?, number(y)?),
+            ("scaleX", [x]) => TransformFunction::Scale(number(x)?, 1.0),
+            ("scaleY", [y]) => TransformFunction::Scale(1.0, number(y)?),
+            ("rotate", [angle]) => TransformFunction::Rotate(parse_angle(angle)?),
+            ("skew", [x]) => TransformFunction::Skew(parse_angle(x)?, 0.0),
+            ("skew", [x, y]) => TransformFunction::Skew(parse_angle(x)?, parse_angle(y)?),
+            ("skewX", [x]) => TransformFunction::Skew(parse_angle(x)?, 0.0),
+            ("skewY", [y]) => TransformFunction::Skew(0.0, parse_angle(y)?),
+            (
+                "translate" | "translateX" | "translateY" | "scale" | "scaleX" | "scaleY"
+                | "rotate" | "skew" | "skewX" | "skewY",
+                _,
+            ) => return Err(TransformParseError::InvalidArgumentCount),
+            _ => return Err(TransformParseError::UnknownFunction),
+        };
+        functions.push(function);
+    }
+    Ok(functions)
+}
+
+/// filterプロパティの1つのフィルター関数
+/// 割合は1.0で100%、角度はラジアンになる
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterFunction {
+    Blur(Length),
+    Brightness(f32),
+    Contrast(f32),
+    Saturate(f32),
+    Grayscale(f32),
+    Sepia(f32),
+    HueRotate(f32),
+    Invert(f32),
+    DropShadow {
+        x: Length,
+        y: Length,
+        blur: Length,
+        color: Color,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum FilterParseError {
+    #[error("invalid syntax")]
+    InvalidSyntax,
+    #[error("unknown function")]
+    UnknownFunction,
+    #[error("invalid number of arguments")]
+    InvalidArgumentCount,
+    #[error("number parse error")]
+    NumberParseError,
+    #[error("negative value")]
+    NegativeValue,
+    #[error(transparent)]
+    AngleParseError(#[from] AngleParseError),
+    #[error("invalid color")]
+    InvalidColor,
+    #[error(transparent)]
+    LengthParseError(#[from] LengthParseError),
+}
+
+/// filterの値をフィルター関数の列にする
+/// `blur(4px) drop-shadow(2px 2px 4px rgb(0, 0, 0))` のように空白区切りで並べ、`none` は空の列になる
+pub fn parse_filter(value: &str) -> Result<Vec<FilterFunction>, FilterParseError> {
+    /// 数値または%の割合を、省略された場合は1.0として変換する
+    fn parse_amount(value: &str) -> Result<f32, FilterParseError> {
+        let amount = match value {
+            "" => 1.0,
+            value => match value.strip_suffix('%') {
+                Some(percent) => percent.parse::<f32>().map(|percent| percent / 100.0),
+                None => value.parse::<f32>(),
+            }
+            .map_err(|_| FilterParseError::NumberParseError)?,
+        };
+        if amount.is_nan() {
+            return Err(FilterParseError::NumberParseError);
+        }
+        if amount < 0.0 {
+            return Err(FilterParseError::NegativeValue);
+        }
+        Ok(amount)
+    }
+    /// drop-shadowの引数を、括弧の外の空白で分ける
+    fn split_arguments(args: &str) -> Vec<&str> {
+        let mut arguments = vec![];
+        let (mut depth, mut start) = (0, 0);
+        for (i, c) in args.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                c if c.is_whitespace() && depth == 0 => {
+                    arguments.push(&args[start..i]);
+                    start = i + c.len_utf8();
+                }
+                _ => {}
+            }
+        }
+        arguments.push(&args[start..]);
+        arguments.retain(|argument| !argument.is_empty());
+        arguments
+    }
+
+    if value.trim() == "none" {
+        return Ok(vec![]);
+    }
+    let calls = split_function_calls(value).ok_or(FilterParseError::InvalidSyntax)?;
+    let mut functions = vec![];
+    for (name, args) in calls {
+        let args = args.trim();
+        let function = match name {
+            "blur" if args.is_empty() => FilterFunction::Blur(Length::Pixel(0.0)),
+            "blur" => FilterFunction::Blur(args.parse()?),
+            // grayscale, sepia, invertは100%より大きくしても変わらない
+            "brightness" => FilterFunction::Brightness(parse_amount(args)?),
+            "contrast" => FilterFunction::Contrast(parse_amount(args)?),
+            "saturate" => FilterFunction::Saturate(parse_amount(args)?),
+            "grayscale" => FilterFunction::Grayscale(parse_amount(args)?.min(1.0)),
+            "sepia" => FilterFunction::Sepia(parse_amount(args)?.min(1.0)),
+            "invert" => FilterFunction::Invert(parse_amount(args)?.min(1.0)),
+            "hue-rotate" if args.is_empty() => FilterFunction::HueRotate(0.0),
+            "hue-rotate" => FilterFunction::HueRotate(parse_angle(args)?),
+            "drop-shadow" => {
+                // 長さではない引数を色として扱い、色が省略された場合は黒にする
+                let (lengths, colors): (Vec<_>, Vec<_>) = split_arguments(args)
+                    .into_iter()
+                    .partition(|argument| argument.parse::<Length>().is_ok());
+                let color = match colors.as_slice() {
+                    [] => Color::from_rgb(0, 0, 0),
+                    [color] => color.parse().map_err(|_| FilterParseError::InvalidColor)?,
+                    _ => return Err(FilterParseError::InvalidArgumentCount),
+                };
+                let lengths = lengths
+                    .iter()
+                    .map(|length| length.parse())
+                    .collect::<Result<Vec<Length>, _>>()?;
+                let (x, y, blur) = match lengths.as_slice() {
+                    [x, y] => (x.clone(), y.clone(), Length::Pixel(0.0)),
+                    [x, y, blur] => (x.clone(), y.clone(), blur.clone()),
+                    _ => return Err(FilterParseError::InvalidArgumentCount),
+                };
+                FilterFunction::DropShadow { x, y, blur, color }
+            }
+            _ => return Err(FilterParseError::UnknownFunction),
+        };
+        functions.push(function);
+    }
+    Ok(functions)
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum AudioFilterParseError {
+    #[error("invalid syntax")]
+    InvalidSyntax,
+    #[error("unknown function")]
+    UnknownFunction,
+    #[error("invalid number of arguments")]
+    InvalidArgumentCount,
+    #[error("number parse error")]
+    NumberParseError,
+    #[error("unknown unit")]
+    UnknownUnit,
+    #[error("value out of range")]
+    OutOfRange,
+}
+
+/// audio-filterの値をエフェクトの列にする
+/// `high-pass(80Hz) eq(2kHz, 3dB, 1.5) compressor(-18dB, 4)` のように空白区切りで並べ、
+/// `none` は空の列になる
+pub fn parse_audio_filter(value: &str) -> Result<Vec<AudioFilter>, AudioFilterParseError> {
+    fn number(value: &str) -> Result<f32, AudioFilterParseError> {
+        match value.parse::<f32>() {
+            Ok(number) if number.is_finite() => Ok(number),
+            _ => Err(AudioFilterParseError::NumberParseError),
+        }
+    }
+    fn positive(value: f32) -> Result<f32, AudioFilterParseError> {
+        if value > 0.0 {
+            Ok(value)
+        } else {
+            Err(AudioFilterParseError::OutOfRange)
+        }
+    }
+    fn decibel(value: &str) -> Result<f32, AudioFilterParseError> {
+        let value = value
+            .strip_suffix("dB")
+            .ok_or(AudioFilterParseError::UnknownUnit)?;
+        number(value)
+    }
+    fn frequency(value: &str) -> Result<f32, AudioFilterParseError> {
+        let frequency = if let Some(value) = value.strip_suffix("kHz") {
+            number(value)? * 1000.0
+        } else if let Some(value) = value.strip_suffix("Hz") {
+            number(value)?
+        } else {
+            return Err(AudioFilterParseError::UnknownUnit);
+        };
+        positive(frequency)
+    }
+    fn seconds(value: &str) -> Result<f32, AudioFilterParseError> {
+        let seconds = if let Some(value) = value.strip_suffix("ms") {
+            number(value)? / 1000.0
+        } else if let Some(value) = value.strip_suffix('s') {
+            number(value)?
+        } else {
+            return Err(AudioFilterParseError::UnknownUnit);
+        };
+        positive(seconds)
+    }
+    /// 数値または%の割合
+    fn ratio(value: &str) -> Result<f32, AudioFilterParseError> {
+        match value.strip_suffix('%') {
+            Some(percent) => Ok(number(percent)? / 100.0),
+            None => number(value),
+        }
+    }
+
+    /// 省略できるQを変換し、省略された場合はdefaultにする
+    fn q(value: Option<&&str>, default: f32) -> Result<f32, AudioFilterParseError> {
+        value.map_or(Ok(default), |value| positive(number(value)?))
+    }
+
+    if value.trim() == "none" {
+        return Ok(vec![]);
+    }
+    let calls = split_function_calls(value).ok_or(AudioFilterParseError::InvalidSyntax)?;
+    let mut filters = vec![];
+    for (name, args) in calls {
+        let args = args
+            .split(',')
+            .map(str::trim)
+            .filter(|arg| !arg.is_empty())
+            .collect::<Vec<_>>();
+        let filter = match (name, args.as_slice()) {
+            // 倍率はdBでも指定できる
+            ("gain", [gain]) => {
+                let gain = match gain.strip_suffix("dB") {
+                    Some(_) => 10f32.powf(decibel(gain)? / 20.0),
+                    None => ratio(gain)?,
+                };
+                if gain < 0.0 {
+                    return Err(AudioFilterParseError::OutOfRange);
+                }
+                AudioFilter::Gain(gain)
+            }
+            // Qを省略した場合はバターワース特性になる値にする
+            ("high-pass", [f] | [f, _]) => AudioFilter::HighPass {
+                frequency: frequency(f)?,
+                q: q(args.get(1), std::f32::consts::FRAC_1_SQRT_2)?,
+            },
+            ("low-pass", [f] | [f, _]) => AudioFilter::LowPass {
+                frequency: frequency(f)?,
+                q: q(args.get(1), std::f32::consts::FRAC_1_SQRT_2)?,
+            },
+            ("eq", [f, gain] | [f, gain, _]) => AudioFilter::Equalizer {
+                frequency: frequency(f)?,
+                gain: decibel(gain)?,
+                q: q(args.get(2), 1.0)?,
+            },
+            ("compressor", [threshold, rest @ ..]) if rest.len() <= 3 => {
+                let ratio = number(rest.first().unwrap_or(&"4"))?;
+                if ratio < 1.0 {
+                    return Err(AudioFilterParseError::OutOfRange);
+                }
+                AudioFilter::Compressor {
+                    threshold: decibel(threshold)?,
+                    ratio,
+                    attack: seconds(rest.get(1).unwrap_or(&"10ms"))?,
+                    release: seconds(rest.get(2).unwrap_or(&"100ms"))?,
+                }
+            }
+            ("reverb", args) if args.len() <= 2 => {
+                let mix = ratio(args.get(1).unwrap_or(&"30%"))?;
+                if !(0.0..=1.0).contains(&mix) {
+                    return Err(AudioFilterParseError::OutOfRange);
+                }
+                AudioFilter::Reverb {
+                    decay: seconds(args.first().unwrap_or(&"1s"))?,
+                    mix,
+                }
+            }
+            ("gain" | "high-pass" | "low-pass" | "eq" | "compressor" | "reverb", _) => {
+                return Err(AudioFilterParseError::InvalidArgumentCount);
+            }
+            _ => return Err(AudioFilterParseError::UnknownFunction),
+        };
+        filters.push(filter);
+    }
+    Ok(filters)
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Order {
     Sequence,
@@ -427,6 +1039,170 @@ impl FromStr for Order {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AlignmentParseError {
+    UnknownMode,
+}
+impl FromStr for Alignment {
+    type Err = AlignmentParseError;
+
+    fn from_str(value: &str) -> Result<Alignment, Self::Err> {
+        match value {
+            "center" => Ok(Alignment::Center),
+            "top" => Ok(Alignment::Top),
+            "left" => Ok(Alignment::Left),
+            "right" => Ok(Alignment::Right),
+            "bottom" => Ok(Alignment::Bottom),
+            "top-left" => Ok(Alignment::TopLeft),
+            "top-right" => Ok(Alignment::TopRight),
+            "bottom-left" => Ok(Alignment::BottomLeft),
+            "bottom-right" => Ok(Alignment::BottomRight),
+            _ => Err(AlignmentParseError::UnknownMode),
+        }
+    }
+}
+
+/// layer-modeがsingleの場合に子要素を並べる方向
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Row,
+    Column,
+    RowReverse,
+    ColumnReverse,
+}
+
+impl Direction {
+    pub fn is_reverse(&self) -> bool {
+        matches!(self, Direction::RowReverse | Direction::ColumnReverse)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DirectionParseError {
+    UnknownMode,
+}
+impl FromStr for Direction {
+    type Err = DirectionParseError;
+
+    fn from_str(value: &str) -> Result<Direction, Self::Err> {
+        match value {
+            "row" => Ok(Direction::Row),
+            "column" => Ok(Direction::Column),
+            "row-reverse" => Ok(Direction::RowReverse),
+            "column-reverse" => Ok(Direction::ColumnReverse),
+            _ => Err(DirectionParseError::UnknownMode),
+        }
+    }
+}
+
+/// slide, wipeで画面が動く方向
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransitionDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// seqの中で前の子要素から切り替わるときの効果
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransitionEffect {
+    /// 前の要素をフェードアウトしながら次の要素をフェードインする
+    Crossfade,
+    /// 指定した色にフェードアウトしてから次の要素をフェードインする
+    FadeThroughColor(Color),
+    /// 前の要素を押し出しながら次の要素が指定した方向に入ってくる
+    Slide(TransitionDirection),
+    /// 指定した方向に動く境界で、前の要素を次の要素で覆っていく
+    Wipe(TransitionDirection),
+}
+
+/// transitionプロパティの値
+/// `<effect> <duration> [<color> | <direction>]`の形式で、
+/// fade-through-colorの色の省略時はblack、slide, wipeの方向の省略時はleftになる
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransitionShorthand {
+    pub effect: TransitionEffect,
+    pub duration: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Error)]
+pub enum TransitionParseError {
+    #[error("unknown effect")]
+    UnknownEffect,
+    #[error("unknown direction")]
+    UnknownDirection,
+    #[error("invalid color")]
+    InvalidColor,
+    #[error("missing duration")]
+    MissingDuration,
+    #[error("unexpected parameter")]
+    UnexpectedParameter,
+    #[error(transparent)]
+    DurationParseError(#[from] DurationParseError),
+}
+
+impl FromStr for TransitionDirection {
+    type Err = TransitionParseError;
+
+    fn from_str(value: &str) -> Result<TransitionDirection, Self::Err> {
+        match value {
+            "left" => Ok(TransitionDirection::Left),
+            "right" => Ok(TransitionDirection::Right),
+            "up" => Ok(TransitionDirection::Up),
+            "down" => Ok(TransitionDirection::Down),
+            _ => Err(TransitionParseError::UnknownDirection),
+        }
+    }
+}
+
+impl FromStr for TransitionShorthand {
+    type Err = TransitionParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        // 色はrgb(0, 0, 0)のように空白を含むことがあるので、3つ目以降はまとめて扱う
+        let mut tokens = value.trim().splitn(3, char::is_whitespace);
+        let effect = tokens.next().unwrap_or_default();
+        let duration = tokens
+            .next()
+            .ok_or(TransitionParseError::MissingDuration)?
+            .parse()?;
+        let parameter = tokens
+            .next()
+            .map(str::trim)
+            .filter(|value| !value.is_empty());
+        let direction = || {
+            parameter
+                .map(TransitionDirection::from_str)
+                .unwrap_or(Ok(TransitionDirection::Left))
+        };
+        let effect = match effect {
+            "crossfade" if parameter.is_some() => {
+                return Err(TransitionParseError::UnexpectedParameter);
+            }
+            "crossfade" => TransitionEffect::Crossfade,
+            "fade-through-color" => TransitionEffect::FadeThroughColor(
+                parameter
+                    .map(Color::from_str)
+                    .unwrap_or(Ok(Color::from_rgb(0, 0, 0)))
+                    .map_err(|_| TransitionParseError::InvalidColor)?,
+            ),
+            "slide" => TransitionEffect::Slide(direction()?),
+            "wipe" => TransitionEffect::Wipe(direction()?),
+            _ => return Err(TransitionParseError::UnknownEffect),
+        };
+        Ok(TransitionShorthand { effect, duration })
+    }
+}
+
+/// 前の子要素からの切り替えの効果と時間
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transition {
+    pub effect: TransitionEffect,
+    /// 前の子要素と重なる時間(s)
+    pub duration: f64,
+}
+
 /// font-familyのパース用のutil関数
 pub fn parse_font_family(value: &str) -> Vec<String> {
     let mut result = Vec::new();
@@ -511,11 +1287,77 @@ impl<I, A> Debug for dyn ObjectProcessor<I, A> {
     }
 }
 
+/// 単語の途中で改行するかどうか (CSSのword-break)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WordBreak {
+    /// 単語の区切りで改行し、1単語が幅に収まらない場合のみ途中で改行する
+    #[default]
+    Normal,
+    /// どの文字の間でも改行する
+    BreakAll,
+    /// 単語の途中では改行しない
+    KeepAll,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WordBreakParseError {
+    UnknownMode,
+}
+impl FromStr for WordBreak {
+    type Err = WordBreakParseError;
+
+    fn from_str(value: &str) -> Result<WordBreak, Self::Err> {
+        match value {
+            "normal" => Ok(WordBreak::Normal),
+            "break-all" => Ok(WordBreak::BreakAll),
+            "keep-all" => Ok(WordBreak::KeepAll),
+            _ => Err(WordBreakParseError::UnknownMode),
+        }
+    }
+}
+
+/// 禁則処理の強さ (CSSのline-break)
+/// anywhere以外はUnicodeの改行規則(UAX #14)に従い、句読点や閉じ括弧の前では改行しない
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineBreak {
+    #[default]
+    Auto,
+    Loose,
+    Normal,
+    Strict,
+    /// 禁則処理をせず、どの文字の間でも改行する
+    Anywhere,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LineBreakParseError {
+    UnknownMode,
+}
+impl FromStr for LineBreak {
+    type Err = LineBreakParseError;
+
+    fn from_str(value: &str) -> Result<LineBreak, Self::Err> {
+        match value {
+            "auto" => Ok(LineBreak::Auto),
+            "loose" => Ok(LineBreak::Loose),
+            "normal" => Ok(LineBreak::Normal),
+            "strict" => Ok(LineBreak::Strict),
+            "anywhere" => Ok(LineBreak::Anywhere),
+            _ => Err(LineBreakParseError::UnknownMode),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TextStyleData {
     pub color: Color,
     pub font_family: Vec<String>,
     pub font_size: f32,
+    pub word_break: WordBreak,
+    pub line_break: LineBreak,
+    /// テキストを折り返す幅(px)
+    /// Noneの場合は改行文字以外では折り返さない
+    pub wrap_width: Option<f32>,
 }
 impl Default for TextStyleData {
     fn default() -> Self {
@@ -524,6 +1366,9 @@ impl Default for TextStyleData {
             font_size: 32.0,
             // 環境によってプリインストールのフォントが変わるのでvsml_coreでは定義しない
             font_family: vec![],
+            word_break: WordBreak::default(),
+            line_break: LineBreak::default(),
+            wrap_width: None,
         }
     }
 }
@@ -547,6 +1392,8 @@ pub enum ProcessorInput<I> {
 }
 
 /// Elementまたはテキスト1つに相当するデータ
+// ほとんどのObjectDataはElementなので、Elementのサイズが大きくてもBoxにはしない
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum ObjectData<I, A> {
     Element {
@@ -557,11 +1404,29 @@ pub enum ObjectData<I, A> {
         duration: f64,
         /// 音量（1.0 = 100%）
         audio_volume: f64,
+        /// audio-filterで指定された、子要素をまとめた音声に適用するエフェクト
+        audio_effect_style: AudioEffectStyle,
+        /// 不透明度(0.0〜1.0)で、背景と子要素をまとめて1枚の画像にしてから適用される
+        opacity: f32,
+        /// transformで決まる、transform-originを原点とした変換
+        transform: Transform,
+        /// 背景を描画する矩形の左上からのtransform-originの位置(px)
+        transform_origin: (f32, f32),
+        /// filterで指定された、背景と子要素をまとめた画像に適用するフィルター
+        image_effect_style: ImageEffectStyle,
         background_color: Option<Color>,
         attributes: HashMap<String, String>,
         /// エレメントの表示位置とサイズ
         /// x, yは親エレメントからの相対位置
         element_rect: ElementRect,
+        /// object-fitで決まる画像の位置とサイズ
+        /// Noneの場合は画像をエレメントの矩形いっぱいに描画する
+        object_fit_rect: Option<ObjectFitRect>,
+        /// エレメントに適用されるアニメーションで、後のものほど優先される
+        animations: Vec<Animation>,
+        /// 順番に並べる親要素の中で、前の子要素から切り替わるときの効果
+        /// start_timeは前の子要素と重なる分だけ早められている
+        transition: Option<Transition>,
         children: Vec<ObjectData<I, A>>,
     },
     Text(Vec<TextData>),
@@ -595,8 +1460,14 @@ mod tests {
         assert_eq!("fit".parse::<Duration>(), Ok(Duration::Fit));
         assert_eq!("0".parse::<Duration>(), Ok(Duration::Frame(0)));
         assert_eq!("1f".parse::<Duration>(), Ok(Duration::Frame(1)));
+        assert_eq!("-1f".parse::<Duration>(), Ok(Duration::Frame(-1)));
         assert_eq!("1s".parse::<Duration>(), Ok(Duration::Second(1.0)));
         assert_eq!("1.0s".parse::<Duration>(), Ok(Duration::Second(1.0)));
+        assert_eq!("500ms".parse::<Duration>(), Ok(Duration::Second(0.5)));
+        assert_eq!(
+            "ms".parse::<Duration>(),
+            Err(DurationParseError::NumberParseError)
+        );
         assert_eq!("1%".parse::<Duration>(), Ok(Duration::Percent(1.0)));
         assert_eq!("1.0%".parse::<Duration>(), Ok(Duration::Percent(1.0)));
         assert_eq!(
@@ -607,6 +1478,120 @@ mod tests {
             "1".parse::<Duration>(),
             Err(DurationParseError::UnknownUnit)
         );
+        assert_eq!(
+            "1min".parse::<Duration>(),
+            Err(DurationParseError::UnknownUnit)
+        );
+    }
+
+    #[test]
+    fn test_parse_time_edges() {
+        assert_eq!(
+            "1s".parse::<TimeEdges>(),
+            Ok(TimeEdges {
+                start: Duration::Second(1.0),
+                end: Duration::Second(1.0),
+            })
+        );
+        assert_eq!(
+            "-0.5s 30f".parse::<TimeEdges>(),
+            Ok(TimeEdges {
+                start: Duration::Second(-0.5),
+                end: Duration::Frame(30),
+            })
+        );
+        assert_eq!(
+            "".parse::<TimeEdges>(),
+            Err(TimeEdgesParseError::InvalidValueCount)
+        );
+        assert_eq!(
+            "1s 2s 3s".parse::<TimeEdges>(),
+            Err(TimeEdgesParseError::InvalidValueCount)
+        );
+        assert_eq!(
+            "1s 2".parse::<TimeEdges>(),
+            Err(TimeEdgesParseError::DurationParseError(
+                DurationParseError::UnknownUnit
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_object_fit() {
+        assert_eq!("fill".parse::<ObjectFit>(), Ok(ObjectFit::Fill));
+        assert_eq!("contain".parse::<ObjectFit>(), Ok(ObjectFit::Contain));
+        assert_eq!("cover".parse::<ObjectFit>(), Ok(ObjectFit::Cover));
+        assert_eq!("none".parse::<ObjectFit>(), Ok(ObjectFit::None));
+        assert_eq!("scale-down".parse::<ObjectFit>(), Ok(ObjectFit::ScaleDown));
+        assert_eq!(
+            "stretch".parse::<ObjectFit>(),
+            Err(ObjectFitParseError::UnknownMode)
+        );
+    }
+
+    #[test]
+    fn test_parse_object_position() {
+        let position = |x, y| Ok(ObjectPosition { x, y });
+        assert_eq!(
+            "center".parse::<ObjectPosition>(),
+            position(Length::Percent(50.0), Length::Percent(50.0))
+        );
+        assert_eq!(
+            "top".parse::<ObjectPosition>(),
+            position(Length::Percent(50.0), Length::Percent(0.0))
+        );
+        assert_eq!(
+            "right bottom".parse::<ObjectPosition>(),
+            position(Length::Percent(100.0), Length::Percent(100.0))
+        );
+        assert_eq!(
+            "top left".parse::<ObjectPosition>(),
+            position(Length::Percent(0.0), Length::Percent(0.0))
+        );
+        assert_eq!(
+            "10px 25%".parse::<ObjectPosition>(),
+            position(Length::Pixel(10.0), Length::Percent(25.0))
+        );
+        assert_eq!(
+            "left right".parse::<ObjectPosition>(),
+            Err(ObjectPositionParseError::InvalidKeyword)
+        );
+        assert_eq!(
+            "top 10px".parse::<ObjectPosition>(),
+            position(Length::Pixel(10.0), Length::Percent(0.0))
+        );
+        assert_eq!(
+            "".parse::<ObjectPosition>(),
+            Err(ObjectPositionParseError::InvalidValueCount)
+        );
+        assert_eq!(
+            "1px 2px 3px".parse::<ObjectPosition>(),
+            Err(ObjectPositionParseError::InvalidValueCount)
+        );
+    }
+
+    #[test]
+    fn test_parse_word_break() {
+        assert_eq!("normal".parse::<WordBreak>(), Ok(WordBreak::Normal));
+        assert_eq!("break-all".parse::<WordBreak>(), Ok(WordBreak::BreakAll));
+        assert_eq!("keep-all".parse::<WordBreak>(), Ok(WordBreak::KeepAll));
+        assert_eq!(
+            "break-word".parse::<WordBreak>(),
+            Err(WordBreakParseError::UnknownMode)
+        );
+    }
+
+    #[test]
+    fn test_parse_line_break() {
+        assert_eq!("auto".parse::<LineBreak>(), Ok(LineBreak::Auto));
+        assert_eq!("loose".parse::<LineBreak>(), Ok(LineBreak::Loose));
+        assert_eq!("normal".parse::<LineBreak>(), Ok(LineBreak::Normal));
+        assert_eq!("strict".parse::<LineBreak>(), Ok(LineBreak::Strict));
+        assert_eq!("anywhere".parse::<LineBreak>(), Ok(LineBreak::Anywhere));
+        assert_eq!(
+            "none".parse::<LineBreak>(),
+            Err(LineBreakParseError::UnknownMode)
+        );
     }
 
     #[test]
@@ -619,6 +1604,316 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_alignment() {
+        assert_eq!("center".parse::<Alignment>(), Ok(Alignment::Center));
+        assert_eq!("top".parse::<Alignment>(), Ok(Alignment::Top));
+        assert_eq!("left".parse::<Alignment>(), Ok(Alignment::Left));
+        assert_eq!("right".parse::<Alignment>(), Ok(Alignment::Right));
+        assert_eq!("bottom".parse::<Alignment>(), Ok(Alignment::Bottom));
+        assert_eq!("top-left".parse::<Alignment>(), Ok(Alignment::TopLeft));
+        assert_eq!("top-right".parse::<Alignment>(), Ok(Alignment::TopRight));
+        assert_eq!(
+            "bottom-left".parse::<Alignment>(),
+            Ok(Alignment::BottomLeft)
+        );
+        assert_eq!(
+            "bottom-right".parse::<Alignment>(),
+            Ok(Alignment::BottomRight)
+        );
+        assert_eq!(
+            "left-top".parse::<Alignment>(),
+            Err(AlignmentParseError::UnknownMode)
+        );
+    }
+
+    #[test]
+    fn test_parse_direction() {
+        assert_eq!("row".parse::<Direction>(), Ok(Direction::Row));
+        assert_eq!("column".parse::<Direction>(), Ok(Direction::Column));
+        assert_eq!(
+            "row-reverse".parse::<Direction>(),
+            Ok(Direction::RowReverse)
+        );
+        assert_eq!(
+            "column-reverse".parse::<Direction>(),
+            Ok(Direction::ColumnReverse)
+        );
+        assert_eq!(
+            "horizontal".parse::<Direction>(),
+            Err(DirectionParseError::UnknownMode)
+        );
+    }
+
+    #[test]
+    fn test_parse_audio_filter() {
+        assert_eq!(parse_audio_filter("none"), Ok(vec![]));
+        assert_eq!(
+            parse_audio_filter("gain(50%) gain(-20dB) high-pass(80Hz) low-pass(1.5kHz, 2)"),
+            Ok(vec![
+                AudioFilter::Gain(0.5),
+                AudioFilter::Gain(0.1),
+                AudioFilter::HighPass {
+                    frequency: 80.0,
+                    q: std::f32::consts::FRAC_1_SQRT_2
+                },
+                AudioFilter::LowPass {
+                    frequency: 1500.0,
+                    q: 2.0
+                },
+            ])
+        );
+        assert_eq!(
+            parse_audio_filter("eq(2kHz, -3dB) compressor(-18dB) reverb() reverb(2s, 0.5)"),
+            Ok(vec![
+                AudioFilter::Equalizer {
+                    frequency: 2000.0,
+                    gain: -3.0,
+                    q: 1.0
+                },
+                AudioFilter::Compressor {
+                    threshold: -18.0,
+                    ratio: 4.0,
+                    attack: 0.01,
+                    release: 0.1
+                },
+                AudioFilter::Reverb {
+                    decay: 1.0,
+                    mix: 0.3
+                },
+                AudioFilter::Reverb {
+                    decay: 2.0,
+                    mix: 0.5
+                },
+            ])
+        );
+        assert_eq!(
+            parse_audio_filter("compressor(-10dB, 8, 5ms, 0.2s)"),
+            Ok(vec![AudioFilter::Compressor {
+                threshold: -10.0,
+                ratio: 8.0,
+                attack: 0.005,
+                release: 0.2
+            }])
+        );
+        assert_eq!(
+            parse_audio_filter("high-pass(80)"),
+            Err(AudioFilterParseError::UnknownUnit)
+        );
+        assert_eq!(
+            parse_audio_filter("eq(1kHz)"),
+            Err(AudioFilterParseError::InvalidArgumentCount)
+        );
+        assert_eq!(
+            parse_audio_filter("compressor(-10dB, 0.5)"),
+            Err(AudioFilterParseError::OutOfRange)
+        );
+        assert_eq!(
+            parse_audio_filter("reverb(1s, 150%)"),
+            Err(AudioFilterParseError::OutOfRange)
+        );
+        assert_eq!(
+            parse_audio_filter("gain(-1)"),
+            Err(AudioFilterParseError::OutOfRange)
+        );
+        assert_eq!(
+            parse_audio_filter("distortion(1)"),
+            Err(AudioFilterParseError::UnknownFunction)
+        );
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(parse_filter("none"), Ok(vec![]));
+        assert_eq!(
+            parse_filter("blur(4px) brightness(150%) contrast(0.5) saturate()"),
+            Ok(vec![
+                FilterFunction::Blur(Length::Pixel(4.0)),
+                FilterFunction::Brightness(1.5),
+                FilterFunction::Contrast(0.5),
+                FilterFunction::Saturate(1.0),
+            ])
+        );
+        assert_eq!(
+            parse_filter("grayscale(200%) sepia(0.25) invert(1) hue-rotate(0.5turn)"),
+            Ok(vec![
+                FilterFunction::Grayscale(1.0),
+                FilterFunction::Sepia(0.25),
+                FilterFunction::Invert(1.0),
+                FilterFunction::HueRotate(std::f32::consts::PI),
+            ])
+        );
+        assert_eq!(
+            parse_filter("drop-shadow(2px 1rh 4px rgb(255, 0, 0)) drop-shadow(white 0 -2px)"),
+            Ok(vec![
+                FilterFunction::DropShadow {
+                    x: Length::Pixel(2.0),
+                    y: Length::ResolutionHeight(1.0),
+                    blur: Length::Pixel(4.0),
+                    color: Color::from_rgb(255, 0, 0),
+                },
+                FilterFunction::DropShadow {
+                    x: Length::Pixel(0.0),
+                    y: Length::Pixel(-2.0),
+                    blur: Length::Pixel(0.0),
+                    color: Color::WHITE,
+                },
+            ])
+        );
+        assert_eq!(
+            parse_filter("brightness(-1)"),
+            Err(FilterParseError::NegativeValue)
+        );
+        assert_eq!(
+            parse_filter("hue-rotate(90)"),
+            Err(FilterParseError::AngleParseError(
+                AngleParseError::UnknownUnit
+            ))
+        );
+        assert_eq!(
+            parse_filter("drop-shadow(2px)"),
+            Err(FilterParseError::InvalidArgumentCount)
+        );
+        assert_eq!(
+            parse_filter("drop-shadow(2px 2px nocolor)"),
+            Err(FilterParseError::InvalidColor)
+        );
+        assert_eq!(
+            parse_filter("sharpen(1)"),
+            Err(FilterParseError::UnknownFunction)
+        );
+        assert_eq!(
+            parse_filter("blur(4px"),
+            Err(FilterParseError::InvalidSyntax)
+        );
+    }
+
+    #[test]
+    fn test_parse_transform() {
+        assert_eq!(parse_transform("none"), Ok(vec![]));
+        assert_eq!(
+            parse_transform("translate(10px, 50%) scale(2) rotate(90deg)"),
+            Ok(vec![
+                TransformFunction::Translate(Length::Pixel(10.0), Length::Percent(50.0)),
+                TransformFunction::Scale(2.0, 2.0),
+                TransformFunction::Rotate(std::f32::consts::FRAC_PI_2),
+            ])
+        );
+        assert_eq!(
+            parse_transform("translateY(5rh) scaleX(0.5) skew(0.5turn,0)"),
+            Ok(vec![
+                TransformFunction::Translate(Length::Pixel(0.0), Length::ResolutionHeight(5.0)),
+                TransformFunction::Scale(0.5, 1.0),
+                TransformFunction::Skew(std::f32::consts::PI, 0.0),
+            ])
+        );
+        assert_eq!(
+            parse_transform("rotate(1rad)skewY(200grad)"),
+            Ok(vec![
+                TransformFunction::Rotate(1.0),
+                TransformFunction::Skew(0.0, std::f32::consts::PI),
+            ])
+        );
+        assert_eq!(
+            parse_transform("rotate(90)"),
+            Err(TransformParseError::AngleParseError(
+                AngleParseError::UnknownUnit
+            ))
+        );
+        assert_eq!(
+            parse_transform("scale(1, 2, 3)"),
+            Err(TransformParseError::InvalidArgumentCount)
+        );
+        assert_eq!(
+            parse_transform("matrix3d(1)"),
+            Err(TransformParseError::UnknownFunction)
+        );
+        assert_eq!(
+            parse_transform("rotate(90deg"),
+            Err(TransformParseError::InvalidSyntax)
+        );
+        assert_eq!(
+            parse_transform("translate(10)"),
+            Err(TransformParseError::LengthParseError(
+                LengthParseError::UnknownUnit
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_opacity() {
+        assert_eq!("0.5".parse::<Opacity>(), Ok(Opacity(0.5)));
+        assert_eq!("1".parse::<Opacity>(), Ok(Opacity(1.0)));
+        assert_eq!("25%".parse::<Opacity>(), Ok(Opacity(0.25)));
+        assert_eq!("1.5".parse::<Opacity>(), Ok(Opacity(1.0)));
+        assert_eq!("-10%".parse::<Opacity>(), Ok(Opacity(0.0)));
+        assert_eq!(
+            "half".parse::<Opacity>(),
+            Err(OpacityParseError::NumberParseError)
+        );
+        assert_eq!(
+            "NaN".parse::<Opacity>(),
+            Err(OpacityParseError::NumberParseError)
+        );
+    }
+
+    #[test]
+    fn test_parse_transition() {
+        let transition = |effect, duration| Ok(TransitionShorthand { effect, duration });
+        assert_eq!(
+            "crossfade 1s".parse::<TransitionShorthand>(),
+            transition(TransitionEffect::Crossfade, Duration::Second(1.0))
+        );
+        assert_eq!(
+            "fade-through-color 30f".parse::<TransitionShorthand>(),
+            transition(
+                TransitionEffect::FadeThroughColor(Color::from_rgb(0, 0, 0)),
+                Duration::Frame(30)
+            )
+        );
+        assert_eq!(
+            "fade-through-color 1s rgb(255, 255, 255)".parse::<TransitionShorthand>(),
+            transition(
+                TransitionEffect::FadeThroughColor(Color::WHITE),
+                Duration::Second(1.0)
+            )
+        );
+        assert_eq!(
+            "slide 10% up".parse::<TransitionShorthand>(),
+            transition(
+                TransitionEffect::Slide(TransitionDirection::Up),
+                Duration::Percent(10.0)
+            )
+        );
+        assert_eq!(
+            "wipe 1s".parse::<TransitionShorthand>(),
+            transition(
+                TransitionEffect::Wipe(TransitionDirection::Left),
+                Duration::Second(1.0)
+            )
+        );
+        assert_eq!(
+            "crossfade".parse::<TransitionShorthand>(),
+            Err(TransitionParseError::MissingDuration)
+        );
+        assert_eq!(
+            "crossfade 1s left".parse::<TransitionShorthand>(),
+            Err(TransitionParseError::UnexpectedParameter)
+        );
+        assert_eq!(
+            "slide 1s sideways".parse::<TransitionShorthand>(),
+            Err(TransitionParseError::UnknownDirection)
+        );
+        assert_eq!(
+            "fade-through-color 1s nocolor".parse::<TransitionShorthand>(),
+            Err(TransitionParseError::InvalidColor)
+        );
+        assert_eq!(
+            "dissolve 1s".parse::<TransitionShorthand>(),
+            Err(TransitionParseError::UnknownEffect)
+        );
+    }
+
     #[test]
     fn test_parse_length() {
         assert_eq!("100px".parse::<Length>(), Ok(Length::Pixel(100.0)));
@@ -644,4 +1939,59 @@ mod tests {
         assert_eq!("0".parse::<Length>(), Ok(Length::Pixel(0.0)));
         assert_eq!("-100px".parse::<Length>(), Ok(Length::Pixel(-100.0)));
     }
+
+    #[test]
+    fn test_parse_edge_lengths() {
+        let px = Length::Pixel;
+        assert_eq!(
+            "10px".parse::<EdgeLengths>(),
+            Ok(EdgeLengths {
+                top: px(10.0),
+                right: px(10.0),
+                bottom: px(10.0),
+                left: px(10.0),
+            })
+        );
+        assert_eq!(
+            "10px 20px".parse::<EdgeLengths>(),
+            Ok(EdgeLengths {
+                top: px(10.0),
+                right: px(20.0),
+                bottom: px(10.0),
+                left: px(20.0),
+            })
+        );
+        assert_eq!(
+            "10px 5rw 30px".parse::<EdgeLengths>(),
+            Ok(EdgeLengths {
+                top: px(10.0),
+                right: Length::ResolutionWidth(5.0),
+                bottom: px(30.0),
+                left: Length::ResolutionWidth(5.0),
+            })
+        );
+        assert_eq!(
+            "1px 2px 3px 4%".parse::<EdgeLengths>(),
+            Ok(EdgeLengths {
+                top: px(1.0),
+                right: px(2.0),
+                bottom: px(3.0),
+                left: Length::Percent(4.0),
+            })
+        );
+        assert_eq!(
+            "".parse::<EdgeLengths>(),
+            Err(EdgeLengthsParseError::InvalidValueCount)
+        );
+        assert_eq!(
+            "1px 2px 3px 4px 5px".parse::<EdgeLengths>(),
+            Err(EdgeLengthsParseError::InvalidValueCount)
+        );
+        assert_eq!(
+            "1px 2".parse::<EdgeLengths>(),
+            Err(EdgeLengthsParseError::LengthParseError(
+                LengthParseError::UnknownUnit
+            ))
+        );
+    }
 }
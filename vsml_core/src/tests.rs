@@ -1,5 +1,6 @@
 use super::*;
 use mockall::predicate;
+use std::sync::{Arc, Mutex};
 
 #[test]
 fn test_calc_rendering_info() {
@@ -10,6 +11,8 @@ fn test_calc_rendering_info() {
         y: 2.0,
         width: 1.0,
         height: 2.0,
+        margin: Default::default(),
+        padding: Default::default(),
     };
 
     // x軸方向のテスト
@@ -125,6 +128,50 @@ fn test_calc_rendering_info() {
     assert_eq!(element_rect.calc_rendering_info(4.0, 8.0).y, 8.0);
 }
 
+#[test]
+fn test_calc_rendering_info_with_margin_and_padding() {
+    let element_rect = ElementRect {
+        alignment: Alignment::TopLeft,
+        parent_alignment: Alignment::TopLeft,
+        x: 1.0,
+        y: 2.0,
+        width: 10.0,
+        height: 20.0,
+        margin: Edges {
+            top: 1.0,
+            right: 2.0,
+            bottom: 3.0,
+            left: 4.0,
+        },
+        padding: Edges {
+            top: 5.0,
+            right: 6.0,
+            bottom: 7.0,
+            left: 8.0,
+        },
+    };
+    assert_eq!(element_rect.outer_width(), 30.0);
+    assert_eq!(element_rect.outer_height(), 36.0);
+
+    let info = element_rect.calc_box_rendering_info(100.0, 100.0);
+    assert_eq!((info.x, info.y), (5.0, 3.0));
+    assert_eq!((info.width, info.height), (24.0, 32.0));
+    let info = element_rect.calc_rendering_info(100.0, 100.0);
+    assert_eq!((info.x, info.y), (13.0, 8.0));
+    assert_eq!((info.width, info.height), (10.0, 20.0));
+
+    // 右下揃えの場合はmarginを含む外側の矩形が揃えられる
+    let element_rect = ElementRect {
+        alignment: Alignment::BottomRight,
+        parent_alignment: Alignment::BottomRight,
+        ..element_rect
+    };
+    let info = element_rect.calc_box_rendering_info(100.0, 100.0);
+    assert_eq!((info.x, info.y), (75.0, 67.0));
+    let info = element_rect.calc_rendering_info(100.0, 100.0);
+    assert_eq!((info.x, info.y), (83.0, 72.0));
+}
+
 pub struct MockImage {}
 pub struct MockAudio {}
 
@@ -140,6 +187,11 @@ fn test_render_frame_image() {
             start_time: 0.0,
             duration: 1.0,
             audio_volume: 1.0,
+            audio_effect_style: Default::default(),
+            opacity: 1.0,
+            transform: Transform::IDENTITY,
+            transform_origin: (0.0, 0.0),
+            image_effect_style: Default::default(),
             background_color: None,
             element_rect: ElementRect {
                 alignment: Alignment::Center,
@@ -148,7 +200,12 @@ fn test_render_frame_image() {
                 y: 0.0,
                 width: 1920.0,
                 height: 1080.0,
+                margin: Default::default(),
+                padding: Default::default(),
             },
+            object_fit_rect: None,
+            animations: vec![],
+            transition: None,
             attributes: Default::default(),
             children: vec![],
         },
@@ -178,6 +235,11 @@ fn test_mix_audio() {
             start_time: 0.0,
             duration: 1.0,
             audio_volume: 1.0,
+            audio_effect_style: Default::default(),
+            opacity: 1.0,
+            transform: Transform::IDENTITY,
+            transform_origin: (0.0, 0.0),
+            image_effect_style: Default::default(),
             background_color: None,
             element_rect: ElementRect {
                 alignment: Alignment::Center,
@@ -186,7 +248,12 @@ fn test_mix_audio() {
                 y: 0.0,
                 width: 1920.0,
                 height: 1080.0,
+                margin: Default::default(),
+                padding: Default::default(),
             },
+            object_fit_rect: None,
+            animations: vec![],
+            transition: None,
             attributes: Default::default(),
             children: vec![],
         },
@@ -236,3 +303,316 @@ fn test_font_family_escaped_quotes() {
     let result = schemas::parse_font_family("\"\\\"hoge\\\"\"");
     assert_eq!(result, vec!["\"hoge\"".to_string()]);
 }
+
+/// テストごとに変える値だけを指定して、左上揃えで100x100のwrapエレメントを作る
+struct WrapElement {
+    start_time: f64,
+    duration: f64,
+    audio_effect_style: AudioEffectStyle,
+    opacity: f32,
+    transform: Transform,
+    transform_origin: (f32, f32),
+    image_effect_style: ImageEffectStyle,
+    background_color: Option<schemas::Color>,
+    position: (f32, f32),
+    transition: Option<schemas::Transition>,
+    children: Vec<ObjectData<MockImage, MockAudio>>,
+}
+
+impl Default for WrapElement {
+    fn default() -> Self {
+        WrapElement {
+            start_time: 0.0,
+            duration: 1.0,
+            audio_effect_style: Default::default(),
+            opacity: 1.0,
+            transform: Transform::IDENTITY,
+            transform_origin: (0.0, 0.0),
+            image_effect_style: Default::default(),
+            background_color: None,
+            position: (0.0, 0.0),
+            transition: None,
+            children: vec![],
+        }
+    }
+}
+
+impl WrapElement {
+    fn build(self) -> ObjectData<MockImage, MockAudio> {
+        ObjectData::Element {
+            object_type: ObjectType::Wrap,
+            start_time: self.start_time,
+            duration: self.duration,
+            audio_volume: 1.0,
+            audio_effect_style: self.audio_effect_style,
+            opacity: self.opacity,
+            transform: self.transform,
+            transform_origin: self.transform_origin,
+            image_effect_style: self.image_effect_style,
+            background_color: self.background_color,
+            element_rect: ElementRect {
+                alignment: Alignment::TopLeft,
+                parent_alignment: Alignment::TopLeft,
+                x: self.position.0,
+                y: self.position.1,
+                width: 100.0,
+                height: 100.0,
+                margin: Default::default(),
+                padding: Default::default(),
+            },
+            object_fit_rect: None,
+            animations: vec![],
+            transition: self.transition,
+            attributes: Default::default(),
+            children: self.children,
+        }
+    }
+}
+
+/// 100x100の動画としてobjectを変換したIVDataを作る
+fn create_iv_data(
+    fps: u32,
+    object: ObjectData<MockImage, MockAudio>,
+) -> schemas::IVData<MockImage, MockAudio> {
+    schemas::IVData {
+        resolution_x: 100,
+        resolution_y: 100,
+        fps,
+        sampling_rate: 44100,
+        object,
+    }
+}
+
+/// 描画された背景と画像のRenderingInfoを、描画した順に記録する
+#[derive(Default)]
+struct RenderingRecord {
+    boxes: Vec<RenderingInfo>,
+    images: Vec<RenderingInfo>,
+}
+
+/// 作成したrendererの描画をすべてRenderingRecordに記録するMockRenderingContextを作る
+fn create_recording_rendering_context() -> (MockRenderingContext, Arc<Mutex<RenderingRecord>>) {
+    let record = Arc::new(Mutex::new(RenderingRecord::default()));
+    let mut mock_rc = MockRenderingContext::new();
+    let recorded = record.clone();
+    mock_rc.expect_create_renderer().returning(move || {
+        let mut mock_renderer = MockRenderer::new();
+        let boxes = recorded.clone();
+        mock_renderer
+            .expect_render_box()
+            .returning(move |_, info| boxes.lock().unwrap().boxes.push(info));
+        let images = recorded.clone();
+        mock_renderer
+            .expect_render_image()
+            .returning(move |_, info| images.lock().unwrap().images.push(info));
+        mock_renderer.expect_render().returning(|_, _| MockImage {});
+        mock_renderer
+    });
+    (mock_rc, record)
+}
+
+/// 記録した画像の不透明度を描画した順に返す
+fn image_opacities(record: &Mutex<RenderingRecord>) -> Vec<f32> {
+    let record = record.lock().unwrap();
+    record.images.iter().map(|info| info.opacity).collect()
+}
+
+#[test]
+fn test_render_frame_image_crossfade() {
+    let element = |start_time, transition| {
+        WrapElement {
+            start_time,
+            background_color: Some(schemas::Color::WHITE),
+            transition,
+            ..Default::default()
+        }
+        .build()
+    };
+    let iv_data = create_iv_data(
+        4,
+        WrapElement {
+            duration: 1.5,
+            children: vec![
+                element(0.0, None),
+                element(
+                    0.5,
+                    Some(schemas::Transition {
+                        effect: schemas::TransitionEffect::Crossfade,
+                        duration: 0.5,
+                    }),
+                ),
+            ],
+            ..Default::default()
+        }
+        .build(),
+    );
+
+    let (mock_rc, record) = create_recording_rendering_context();
+    // 0.75秒は切り替えの中間なので、前後の子要素が半分ずつの不透明度で重なる
+    render_frame_image(&iv_data, 3, mock_rc);
+    assert_eq!(image_opacities(&record), [0.5, 0.5, 1.0]);
+}
+
+#[test]
+fn test_render_frame_image_opacity() {
+    let iv_data = create_iv_data(
+        60,
+        WrapElement {
+            children: vec![
+                WrapElement {
+                    opacity: 0.5,
+                    background_color: Some(schemas::Color::WHITE),
+                    ..Default::default()
+                }
+                .build(),
+            ],
+            ..Default::default()
+        }
+        .build(),
+    );
+
+    let (mock_rc, record) = create_recording_rendering_context();
+    render_frame_image(&iv_data, 0, mock_rc);
+    assert_eq!(image_opacities(&record), [0.5, 1.0]);
+    // 背景は半透明の画像の中に不透明で描画される
+    let record = record.lock().unwrap();
+    assert_eq!(record.boxes.len(), 1);
+    assert_eq!(record.boxes[0].opacity, 1.0);
+}
+
+#[test]
+fn test_transform() {
+    let assert_point = |transform: Transform, point: (f32, f32), expected: (f32, f32)| {
+        let (x, y) = transform.transform_point(point.0, point.1);
+        assert!(
+            (x - expected.0).abs() < 1e-4 && (y - expected.1).abs() < 1e-4,
+            "{transform:?}: {point:?} -> {:?}, expected {expected:?}",
+            (x, y)
+        );
+    };
+    assert_point(Transform::IDENTITY, (3.0, 4.0), (3.0, 4.0));
+    assert_point(Transform::translate(10.0, 20.0), (3.0, 4.0), (13.0, 24.0));
+    assert_point(Transform::scale(2.0, 3.0), (3.0, 4.0), (6.0, 12.0));
+    // y軸が下向きなので、x軸上の点は時計回りに回転するとy軸の正の方向に移る
+    assert_point(
+        Transform::rotate(std::f32::consts::FRAC_PI_2),
+        (1.0, 0.0),
+        (0.0, 1.0),
+    );
+    assert_point(
+        Transform::skew(std::f32::consts::FRAC_PI_4, 0.0),
+        (0.0, 2.0),
+        (2.0, 2.0),
+    );
+    // 右側の変換が先に適用される
+    assert_point(
+        Transform::translate(10.0, 0.0) * Transform::scale(2.0, 2.0),
+        (1.0, 1.0),
+        (12.0, 2.0),
+    );
+    assert_point(
+        Transform::scale(2.0, 2.0).around(50.0, 50.0),
+        (0.0, 0.0),
+        (-50.0, -50.0),
+    );
+    assert_point(
+        Transform::scale(2.0, 2.0).around(50.0, 50.0),
+        (50.0, 50.0),
+        (50.0, 50.0),
+    );
+}
+
+#[test]
+fn test_render_frame_image_transform() {
+    let iv_data = create_iv_data(
+        60,
+        WrapElement {
+            transform: Transform::scale(2.0, 2.0),
+            transform_origin: (10.0, 20.0),
+            background_color: Some(schemas::Color::WHITE),
+            position: (30.0, 40.0),
+            ..Default::default()
+        }
+        .build(),
+    );
+
+    let (mock_rc, record) = create_recording_rendering_context();
+    render_frame_image(&iv_data, 0, mock_rc);
+    // transform-originは背景を描画する矩形の左上(30, 40)からの位置になる
+    let record = record.lock().unwrap();
+    let transforms: Vec<_> = record.boxes.iter().map(|info| info.transform).collect();
+    assert_eq!(transforms, [Transform::scale(2.0, 2.0).around(40.0, 60.0)]);
+}
+
+#[test]
+fn test_render_frame_image_filter() {
+    let image_effect_style = ImageEffectStyle {
+        filters: vec![ImageFilter::Blur(4.0), ImageFilter::Grayscale(1.0)],
+    };
+    let iv_data = create_iv_data(
+        60,
+        WrapElement {
+            image_effect_style: image_effect_style.clone(),
+            background_color: Some(schemas::Color::WHITE),
+            ..Default::default()
+        }
+        .build(),
+    );
+
+    let (mut mock_rc, record) = create_recording_rendering_context();
+    // 背景と内容をまとめた画像に1度だけfilterを適用する
+    mock_rc
+        .expect_apply_style()
+        .withf(move |_, style| *style == image_effect_style)
+        .times(1)
+        .returning(|_, _| MockImage {});
+    render_frame_image(&iv_data, 0, mock_rc);
+    // filterを適用した画像は不透明度1で重ねる
+    assert_eq!(image_opacities(&record), [1.0]);
+}
+
+#[test]
+fn test_mix_audio_filter() {
+    let audio_effect_style = AudioEffectStyle {
+        filters: vec![
+            AudioFilter::Gain(0.5),
+            AudioFilter::Reverb {
+                decay: 1.0,
+                mix: 0.3,
+            },
+        ],
+    };
+    let wrap = |audio_effect_style, children| {
+        WrapElement {
+            audio_effect_style,
+            children,
+            ..Default::default()
+        }
+        .build()
+    };
+    let iv_data = create_iv_data(
+        60,
+        wrap(
+            Default::default(),
+            vec![wrap(
+                audio_effect_style.clone(),
+                vec![wrap(Default::default(), vec![])],
+            )],
+        ),
+    );
+
+    let mut mock_mc = MockMixingContext::new();
+    mock_mc.expect_create_mixer().returning(|_| {
+        let mut mock_mixer = MockMixer::new();
+        mock_mixer.expect_mix_audio().returning(|_, _, _, _, _| ());
+        mock_mixer.expect_mix().returning(|_| MockAudio {});
+        mock_mixer
+    });
+    // audio-filterが指定されたエレメントの音声にだけエフェクトを適用する
+    mock_mc
+        .expect_apply_style()
+        .withf(move |_, style| *style == audio_effect_style)
+        .times(1)
+        .returning(|_, _| MockAudio {});
+    mix_audio(&iv_data, mock_mc);
+}
@@ -0,0 +1,45 @@
+struct BlurUniform {
+    // ぼかす方向で、(1, 0)か(0, 1)になる
+    direction: vec2<f32>,
+    sigma: f32,
+}
+
+@group(0) @binding(0)
+var t_source: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> blur: BlurUniform;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // 出力画像全体を覆う三角形を描画する
+    let x = f32(vertex_index & 1) * 4.0 - 1.0;
+    let y = f32(vertex_index & 2) * 2.0 - 1.0;
+    return vec4<f32>(x, y, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = vec2<i32>(textureDimensions(t_source));
+    let center = vec2<i32>(position.xy);
+    let direction = vec2<i32>(blur.direction);
+    let radius = i32(ceil(blur.sigma * 3.0));
+
+    var color = vec4<f32>(0.0);
+    var total_weight = 0.0;
+    for (var i = -radius; i <= radius; i++) {
+        let weight = exp(-f32(i * i) / (2.0 * blur.sigma * blur.sigma));
+        total_weight += weight;
+        let coords = center + direction * i;
+        // 画像の外は透明として扱う
+        if all(coords >= vec2<i32>(0)) && all(coords < size) {
+            let sample = textureLoad(t_source, coords, 0);
+            // 透明な部分の色が混ざらないように、不透明度をかけた色で平均する
+            color += vec4<f32>(sample.rgb * sample.a, sample.a) * weight;
+        }
+    }
+    color /= total_weight;
+    if color.a <= 0.0 {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
@@ -0,0 +1,45 @@
+struct ColorMatrixUniform {
+    // Rust側では行ごとに並べているので、色を行ベクトルとしてかける
+    matrix: mat4x4<f32>,
+    offset: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var t_source: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> color_matrix: ColorMatrixUniform;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // 出力画像全体を覆う三角形を描画する
+    let x = f32(vertex_index & 1) * 4.0 - 1.0;
+    let y = f32(vertex_index & 2) * 2.0 - 1.0;
+    return vec4<f32>(x, y, 0.0, 1.0);
+}
+
+// sRGBのテクスチャから読み出した線形な値を、sRGBでエンコードされた値に戻す
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low = color * 12.92;
+    let high = 1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3<f32>(0.0031308));
+}
+
+// sRGBでエンコードされた値を、sRGBのテクスチャに書き込む線形な値にする
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, color <= vec3<f32>(0.04045));
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let color = textureLoad(t_source, vec2<i32>(position.xy), 0);
+    // CSSのフィルターの行列はsRGBでエンコードされた値に対して定義されている
+    let encoded = vec4<f32>(linear_to_srgb(color.rgb), color.a);
+    let result = clamp(
+        encoded * color_matrix.matrix + color_matrix.offset,
+        vec4<f32>(0.0),
+        vec4<f32>(1.0),
+    );
+    return vec4<f32>(srgb_to_linear(result.rgb), result.a);
+}
@@ -2,8 +2,10 @@
 mod tests;
 
 use vsml_common_image::Image as VsmlImage;
+use vsml_core::schemas::Color;
 use vsml_core::{
-    ImageEffectStyle, ImageSize, RenderBoxProperty, Renderer, RenderingContext, RenderingInfo,
+    ImageEffectStyle, ImageFilter, ImageSize, RenderBoxProperty, Renderer, RenderingContext,
+    RenderingInfo, Transform,
 };
 use wgpu::util::DeviceExt;
 
@@ -29,6 +31,9 @@ pub struct RenderingContextImpl {
     box_render_pipeline: wgpu::RenderPipeline,
     texture_bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
+    filter_bind_group_layout: wgpu::BindGroupLayout,
+    blur_pipeline: wgpu::RenderPipeline,
+    color_matrix_pipeline: wgpu::RenderPipeline,
 }
 
 #[repr(C)]
@@ -40,16 +45,22 @@ struct Vertex {
     y: f32,
     width: f32,
     height: f32,
+    opacity: f32,
+    transform_x: [f32; 3],
+    transform_y: [f32; 3],
 }
 
 impl Vertex {
-    const ATTRIBUTES: [wgpu::VertexAttribute; 6] = wgpu::vertex_attr_array![
+    const ATTRIBUTES: [wgpu::VertexAttribute; 9] = wgpu::vertex_attr_array![
         0 => Uint32,
         1 => Uint32,
         2 => Float32,
         3 => Float32,
         4 => Float32,
         5 => Float32,
+        6 => Float32,
+        7 => Float32x3,
+        8 => Float32x3,
     ];
     fn desc() -> wgpu::VertexBufferLayout<'static> {
         wgpu::VertexBufferLayout {
@@ -70,10 +81,12 @@ struct BoxVertex {
     width: f32,
     height: f32,
     color: [f32; 4],
+    transform_x: [f32; 3],
+    transform_y: [f32; 3],
 }
 
 impl BoxVertex {
-    const ATTRIBUTES: [wgpu::VertexAttribute; 7] = wgpu::vertex_attr_array![
+    const ATTRIBUTES: [wgpu::VertexAttribute; 9] = wgpu::vertex_attr_array![
         0 => Uint32,
         1 => Uint32,
         2 => Float32,
@@ -81,6 +94,8 @@ impl BoxVertex {
         4 => Float32,
         5 => Float32,
         6 => Float32x4,
+        7 => Float32x3,
+        8 => Float32x3,
     ];
     fn desc() -> wgpu::VertexBufferLayout<'static> {
         wgpu::VertexBufferLayout {
@@ -91,6 +106,179 @@ impl BoxVertex {
     }
 }
 
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct BlurUniform {
+    direction: [f32; 2],
+    sigma: f32,
+    _padding: f32,
+}
+
+/// 不透明度をかけていないrgbaに行列をかけてoffsetを足す色の変換
+/// 各フィルターの行列はFilter Effectsの仕様に合わせ、sRGBでエンコードされた値にかける
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ColorMatrixUniform {
+    rows: [[f32; 4]; 4],
+    offset: [f32; 4],
+}
+
+impl ColorMatrixUniform {
+    /// rgbだけを変換し、不透明度はそのままにする
+    fn rgb(rows: [[f32; 3]; 3], offset: f32) -> Self {
+        let row = |[r, g, b]: [f32; 3]| [r, g, b, 0.0];
+        ColorMatrixUniform {
+            rows: [
+                row(rows[0]),
+                row(rows[1]),
+                row(rows[2]),
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+            offset: [offset, offset, offset, 0.0],
+        }
+    }
+    fn brightness(amount: f32) -> Self {
+        Self::rgb(
+            [[amount, 0.0, 0.0], [0.0, amount, 0.0], [0.0, 0.0, amount]],
+            0.0,
+        )
+    }
+    fn contrast(amount: f32) -> Self {
+        Self::rgb(
+            [[amount, 0.0, 0.0], [0.0, amount, 0.0], [0.0, 0.0, amount]],
+            0.5 - 0.5 * amount,
+        )
+    }
+    fn saturate(amount: f32) -> Self {
+        let s = amount;
+        Self::rgb(
+            [
+                [0.213 + 0.787 * s, 0.715 - 0.715 * s, 0.072 - 0.072 * s],
+                [0.213 - 0.213 * s, 0.715 + 0.285 * s, 0.072 - 0.072 * s],
+                [0.213 - 0.213 * s, 0.715 - 0.715 * s, 0.072 + 0.928 * s],
+            ],
+            0.0,
+        )
+    }
+    fn grayscale(amount: f32) -> Self {
+        let g = 1.0 - amount;
+        Self::rgb(
+            [
+                [
+                    0.2126 + 0.7874 * g,
+                    0.7152 - 0.7152 * g,
+                    0.0722 - 0.0722 * g,
+                ],
+                [
+                    0.2126 - 0.2126 * g,
+                    0.7152 + 0.2848 * g,
+                    0.0722 - 0.0722 * g,
+                ],
+                [
+                    0.2126 - 0.2126 * g,
+                    0.7152 - 0.7152 * g,
+                    0.0722 + 0.9278 * g,
+                ],
+            ],
+            0.0,
+        )
+    }
+    fn sepia(amount: f32) -> Self {
+        let g = 1.0 - amount;
+        Self::rgb(
+            [
+                [0.393 + 0.607 * g, 0.769 - 0.769 * g, 0.189 - 0.189 * g],
+                [0.349 - 0.349 * g, 0.686 + 0.314 * g, 0.168 - 0.168 * g],
+                [0.272 - 0.272 * g, 0.534 - 0.534 * g, 0.131 + 0.869 * g],
+            ],
+            0.0,
+        )
+    }
+    fn hue_rotate(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::rgb(
+            [
+                [
+                    0.213 + cos * 0.787 - sin * 0.213,
+                    0.715 - cos * 0.715 - sin * 0.715,
+                    0.072 - cos * 0.072 + sin * 0.928,
+                ],
+                [
+                    0.213 - cos * 0.213 + sin * 0.143,
+                    0.715 + cos * 0.285 + sin * 0.140,
+                    0.072 - cos * 0.072 - sin * 0.283,
+                ],
+                [
+                    0.213 - cos * 0.213 - sin * 0.787,
+                    0.715 - cos * 0.715 + sin * 0.715,
+                    0.072 + cos * 0.928 + sin * 0.072,
+                ],
+            ],
+            0.0,
+        )
+    }
+    fn invert(amount: f32) -> Self {
+        let scale = 1.0 - 2.0 * amount;
+        Self::rgb(
+            [[scale, 0.0, 0.0], [0.0, scale, 0.0], [0.0, 0.0, scale]],
+            amount,
+        )
+    }
+    /// 画像の不透明度だけを残し、色をcolorで塗りつぶす
+    fn shadow(color: Color) -> Self {
+        ColorMatrixUniform {
+            rows: [
+                [0.0; 4],
+                [0.0; 4],
+                [0.0; 4],
+                [0.0, 0.0, 0.0, color.a as f32 / 255.0],
+            ],
+            offset: [
+                color.r as f32 / 255.0,
+                color.g as f32 / 255.0,
+                color.b as f32 / 255.0,
+                0.0,
+            ],
+        }
+    }
+}
+
+/// シェーダーに渡す変換行列の行((a, c, e), (b, d, f))を返す
+fn transform_rows(transform: &Transform) -> ([f32; 3], [f32; 3]) {
+    (
+        [transform.a, transform.c, transform.e],
+        [transform.b, transform.d, transform.f],
+    )
+}
+
+/// 変換後の描画範囲を囲む矩形を出力画像の内側に収めたシザー矩形(x, y, width, height)を返す
+/// 出力画像と重ならない場合はNoneを返す
+fn scissor_rect(info: &RenderingInfo, width: u32, height: u32) -> Option<(u32, u32, u32, u32)> {
+    let corners = [
+        (info.x, info.y),
+        (info.x + info.width, info.y),
+        (info.x, info.y + info.height),
+        (info.x + info.width, info.y + info.height),
+    ]
+    .map(|(x, y)| info.transform.transform_point(x, y));
+    let (min_x, min_y, max_x, max_y) = corners.iter().fold(
+        (
+            f32::INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NEG_INFINITY,
+        ),
+        |(min_x, min_y, max_x, max_y), &(x, y)| {
+            (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+        },
+    );
+    let left = min_x.max(0.0) as u32;
+    let top = min_y.max(0.0) as u32;
+    let right = (max_x.ceil().max(0.0) as u32).min(width);
+    let bottom = (max_y.ceil().max(0.0) as u32).min(height);
+    (left < right && top < bottom).then(|| (left, top, right - left, bottom - top))
+}
+
 impl Renderer for RendererImpl {
     type Image = VsmlImage;
 
@@ -139,12 +327,19 @@ impl Renderer for RendererImpl {
                         return;
                     }
 
+                    let Some((scissor_x, scissor_y, scissor_width, scissor_height)) =
+                        scissor_rect(info, width, height)
+                    else {
+                        return;
+                    };
+
                     let normalized_color = [
                         color.r as f32 / 255.0,
                         color.g as f32 / 255.0,
                         color.b as f32 / 255.0,
-                        color.a as f32 / 255.0,
+                        color.a as f32 / 255.0 * info.opacity,
                     ];
+                    let (transform_x, transform_y) = transform_rows(&info.transform);
 
                     let box_vertex: &[BoxVertex] = &[BoxVertex {
                         base_width: width,
@@ -154,6 +349,8 @@ impl Renderer for RendererImpl {
                         width: info.width,
                         height: info.height,
                         color: normalized_color,
+                        transform_x,
+                        transform_y,
                     }];
                     let vertex_buffer =
                         self.device
@@ -181,25 +378,21 @@ impl Renderer for RendererImpl {
                     });
                     render_pass.set_pipeline(&self.box_render_pipeline);
                     render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-                    let scissor_rect_width = if width < info.x as u32 + info.width as u32 {
-                        width - info.x as u32
-                    } else {
-                        info.width as u32
-                    };
-                    let scissor_rect_height = if height < info.y as u32 + info.height as u32 {
-                        height - info.y as u32
-                    } else {
-                        info.height as u32
-                    };
                     render_pass.set_scissor_rect(
-                        info.x as u32,
-                        info.y as u32,
-                        scissor_rect_width,
-                        scissor_rect_height,
+                        scissor_x,
+                        scissor_y,
+                        scissor_width,
+                        scissor_height,
                     );
-                    render_pass.draw(0..3, 0..1);
+                    render_pass.draw(0..6, 0..1);
                 }
                 RenderItem::Image(image, info) => {
+                    let Some((scissor_x, scissor_y, scissor_width, scissor_height)) =
+                        scissor_rect(info, width, height)
+                    else {
+                        return;
+                    };
+
                     let child_view = image.create_view(&wgpu::TextureViewDescriptor::default());
 
                     let diffuse_bind_group =
@@ -218,6 +411,7 @@ impl Renderer for RendererImpl {
                             label: None,
                         });
 
+                    let (transform_x, transform_y) = transform_rows(&info.transform);
                     let vertex: &[Vertex] = &[Vertex {
                         base_width: width,
                         base_height: height,
@@ -225,6 +419,9 @@ impl Renderer for RendererImpl {
                         y: info.y,
                         width: info.width,
                         height: info.height,
+                        opacity: info.opacity,
+                        transform_x,
+                        transform_y,
                     }];
                     let vertex_buffer =
                         self.device
@@ -253,23 +450,13 @@ impl Renderer for RendererImpl {
                     render_pass.set_pipeline(&self.render_pipeline);
                     render_pass.set_bind_group(0, &diffuse_bind_group, &[]);
                     render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-                    let scissor_rect_width = if width < info.x as u32 + info.width as u32 {
-                        width - info.x as u32
-                    } else {
-                        info.width as u32
-                    };
-                    let scissor_rect_height = if height < info.y as u32 + info.height as u32 {
-                        height - info.y as u32
-                    } else {
-                        info.height as u32
-                    };
                     render_pass.set_scissor_rect(
-                        info.x as u32,
-                        info.y as u32,
-                        scissor_rect_width,
-                        scissor_rect_height,
+                        scissor_x,
+                        scissor_y,
+                        scissor_width,
+                        scissor_height,
                     );
-                    render_pass.draw(0..3, 0..1);
+                    render_pass.draw(0..6, 0..1);
                 }
             }
         });
@@ -302,12 +489,195 @@ impl RenderingContext for RenderingContextImpl {
         }
     }
 
-    fn apply_style(&mut self, _image: Self::Image, _style: ImageEffectStyle) -> Self::Image {
-        todo!()
+    fn apply_style(&mut self, image: Self::Image, style: ImageEffectStyle) -> Self::Image {
+        style
+            .filters
+            .iter()
+            .fold(image, |image, filter| match *filter {
+                ImageFilter::Blur(sigma) => self.blur(image, sigma),
+                ImageFilter::Brightness(amount) => {
+                    self.apply_color_matrix(&image, ColorMatrixUniform::brightness(amount))
+                }
+                ImageFilter::Contrast(amount) => {
+                    self.apply_color_matrix(&image, ColorMatrixUniform::contrast(amount))
+                }
+                ImageFilter::Saturate(amount) => {
+                    self.apply_color_matrix(&image, ColorMatrixUniform::saturate(amount))
+                }
+                ImageFilter::Grayscale(amount) => {
+                    self.apply_color_matrix(&image, ColorMatrixUniform::grayscale(amount))
+                }
+                ImageFilter::Sepia(amount) => {
+                    self.apply_color_matrix(&image, ColorMatrixUniform::sepia(amount))
+                }
+                ImageFilter::HueRotate(angle) => {
+                    self.apply_color_matrix(&image, ColorMatrixUniform::hue_rotate(angle))
+                }
+                ImageFilter::Invert(amount) => {
+                    self.apply_color_matrix(&image, ColorMatrixUniform::invert(amount))
+                }
+                ImageFilter::DropShadow { x, y, blur, color } => {
+                    let shadow = self.apply_color_matrix(&image, ColorMatrixUniform::shadow(color));
+                    let shadow = self.blur(shadow, blur / 2.0);
+                    let size = image.size();
+                    let rendering_info = |x, y| RenderingInfo {
+                        x,
+                        y,
+                        width: size.width as f32,
+                        height: size.height as f32,
+                        opacity: 1.0,
+                        transform: Transform::IDENTITY,
+                    };
+                    let mut renderer = self.create_renderer();
+                    renderer.render_image(shadow, rendering_info(x, y));
+                    renderer.render_image(image, rendering_info(0.0, 0.0));
+                    renderer.render(size.width, size.height)
+                }
+            })
     }
 }
 
 impl RenderingContextImpl {
+    /// 縦横の2回に分けてガウスぼかしをかける
+    fn blur(&self, image: VsmlImage, sigma: f32) -> VsmlImage {
+        if sigma <= 0.0 {
+            return image;
+        }
+        let pass = |image: &VsmlImage, direction| {
+            let uniform = BlurUniform {
+                direction,
+                sigma,
+                _padding: 0.0,
+            };
+            self.apply_filter_pass(image, &self.blur_pipeline, bytemuck::bytes_of(&uniform))
+        };
+        let image = pass(&image, [1.0, 0.0]);
+        pass(&image, [0.0, 1.0])
+    }
+
+    fn apply_color_matrix(&self, image: &VsmlImage, color_matrix: ColorMatrixUniform) -> VsmlImage {
+        self.apply_filter_pass(
+            image,
+            &self.color_matrix_pipeline,
+            bytemuck::bytes_of(&color_matrix),
+        )
+    }
+
+    /// 画像と同じサイズの新しい画像に、フィルターのシェーダーで1回描画する
+    fn apply_filter_pass(
+        &self,
+        image: &VsmlImage,
+        pipeline: &wgpu::RenderPipeline,
+        uniform: &[u8],
+    ) -> VsmlImage {
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: image.size(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let source_view = image.create_view(&wgpu::TextureViewDescriptor::default());
+        let uniform_buffer = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: uniform,
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.filter_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&source_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+            ],
+            label: None,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Filter Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+                multiview_mask: None,
+            });
+            render_pass.set_pipeline(pipeline);
+            render_pass.set_bind_group(0, &bind_group, &[]);
+            render_pass.draw(0..3, 0..1);
+        }
+        self.queue.submit(std::iter::once(encoder.finish()));
+        texture
+    }
+
+    /// 出力画像全体に描画するフィルター用のパイプラインを作る
+    fn create_filter_pipeline(
+        device: &wgpu::Device,
+        bind_group_layout: &wgpu::BindGroupLayout,
+        label: &str,
+        source: &str,
+    ) -> wgpu::RenderPipeline {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(label),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(label),
+            bind_group_layouts: &[bind_group_layout],
+            immediate_size: 0,
+        });
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview_mask: None,
+            cache: None,
+        })
+    }
+
     pub fn new(device: wgpu::Device, queue: wgpu::Queue) -> Self {
         let texture_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -438,6 +808,46 @@ impl RenderingContextImpl {
             cache: None,
         });
 
+        // filter用のシェーダーとパイプライン
+        let filter_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            multisampled: false,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ],
+                label: Some("filter_bind_group_layout"),
+            });
+        let blur_pipeline = Self::create_filter_pipeline(
+            &device,
+            &filter_bind_group_layout,
+            "Blur Filter",
+            include_str!("filter_blur.wgsl"),
+        );
+        let color_matrix_pipeline = Self::create_filter_pipeline(
+            &device,
+            &filter_bind_group_layout,
+            "Color Matrix Filter",
+            include_str!("filter_color_matrix.wgsl"),
+        );
+
         Self {
             device,
             queue,
@@ -445,6 +855,9 @@ impl RenderingContextImpl {
             box_render_pipeline,
             texture_bind_group_layout,
             sampler,
+            filter_bind_group_layout,
+            blur_pipeline,
+            color_matrix_pipeline,
         }
     }
 }
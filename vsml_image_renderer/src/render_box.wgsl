@@ -7,6 +7,9 @@ struct VertexInput {
     @location(4) width: f32,
     @location(5) height: f32,
     @location(6) color: vec4<f32>,
+    // 描画する矩形に適用する変換行列の行
+    @location(7) transform_x: vec3<f32>,
+    @location(8) transform_y: vec3<f32>,
 }
 
 struct VertexOutput {
@@ -18,8 +21,19 @@ struct VertexOutput {
 fn vs_main(in: VertexInput) -> VertexOutput {
     var output: VertexOutput;
 
-    let x = 2.0 * ((in.x + (2.0 * in.width * f32(in.vertex_index & 1))) / f32(in.base_width)) - 1.0;
-    let y = 2.0 * (1.0 - (in.y + in.height - (in.height * f32(in.vertex_index & 2))) / f32(in.base_height)) - 1.0;
+    // 矩形を2つの三角形で描画する
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[in.vertex_index];
+    let position = vec3<f32>(in.x + in.width * corner.x, in.y + in.height * corner.y, 1.0);
+    let x = 2.0 * dot(in.transform_x, position) / f32(in.base_width) - 1.0;
+    let y = 1.0 - 2.0 * dot(in.transform_y, position) / f32(in.base_height);
 
     output.clip_position = vec4<f32>(x, y, 0.0, 1.0);
     output.color = in.color;
@@ -6,24 +6,39 @@ struct VertexInput {
     @location(3) y: f32,
     @location(4) width: f32,
     @location(5) height: f32,
+    @location(6) opacity: f32,
+    // 描画する矩形に適用する変換行列の行
+    @location(7) transform_x: vec3<f32>,
+    @location(8) transform_y: vec3<f32>,
 }
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) tex_coords: vec2<f32>,
+    @location(1) opacity: f32,
 };
 
 @vertex
 fn vs_main(in: VertexInput) -> VertexOutput {
     var output: VertexOutput;
 
-    let x = 2 * ((in.x + (2 * in.width * f32(in.vertex_index & 1))) / f32(in.base_width)) - 1;
-    let y = 2 * (1 - (in.y + in.height - (in.height * f32(in.vertex_index & 2))) / f32(in.base_height)) - 1;
-    let u = f32(in.vertex_index & 1) * 2;
-    let v = 1 - f32(in.vertex_index & 2);
+    // 矩形を2つの三角形で描画する
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0, 0),
+        vec2<f32>(1, 0),
+        vec2<f32>(0, 1),
+        vec2<f32>(0, 1),
+        vec2<f32>(1, 0),
+        vec2<f32>(1, 1),
+    );
+    let corner = corners[in.vertex_index];
+    let position = vec3<f32>(in.x + in.width * corner.x, in.y + in.height * corner.y, 1);
+    let x = 2 * dot(in.transform_x, position) / f32(in.base_width) - 1;
+    let y = 1 - 2 * dot(in.transform_y, position) / f32(in.base_height);
 
     output.clip_position = vec4<f32>(x, y, 0.0, 1.0);
-    output.tex_coords = vec2<f32>(u, v);
+    output.tex_coords = corner;
+    output.opacity = in.opacity;
 
     return output;
 }
@@ -35,5 +50,6 @@ var s_diffuse: sampler;
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(t_diffuse, s_diffuse, in.tex_coords);
+    let color = textureSample(t_diffuse, s_diffuse, in.tex_coords);
+    return vec4<f32>(color.rgb, color.a * in.opacity);
 }
@@ -48,13 +48,14 @@ fn create_image_data(
         y: 0.0,
         width: dimensions.0 as f32,
         height: dimensions.1 as f32,
+        opacity: 1.0,
+        transform: Transform::IDENTITY,
     };
     (texture, info)
 }
 
-#[test]
-fn test_render_vrt() {
-    // GPUのdeviceとqueueを作成
+/// GPUのdeviceとqueueを作成する
+fn create_device() -> (wgpu::Device, wgpu::Queue) {
     let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
         backends: wgpu::Backends::PRIMARY,
         ..Default::default()
@@ -65,7 +66,7 @@ fn test_render_vrt() {
         force_fallback_adapter: false,
     }))
     .unwrap();
-    let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+    pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
         required_features: wgpu::Features::empty(),
         required_limits: wgpu::Limits::default(),
         label: None,
@@ -73,7 +74,72 @@ fn test_render_vrt() {
         experimental_features: Default::default(),
         trace: Default::default(),
     }))
+    .unwrap()
+}
+
+/// テクスチャのピクセルをrgbaの順に読み出す
+fn read_pixels(device: &wgpu::Device, queue: &wgpu::Queue, texture: &VsmlImage) -> Vec<u8> {
+    let size = texture.size();
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: &vec![0u8; size.width as usize * size.height as usize * 4],
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * size.width),
+                rows_per_image: Some(size.height),
+            },
+        },
+        size,
+    );
+    queue.submit(std::iter::once(encoder.finish()));
+
+    let slice = &buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| {});
+
+    device
+        .poll(wgpu::PollType::Wait {
+            submission_index: None,
+            timeout: None,
+        })
+        .unwrap();
+
+    slice.get_mapped_range().to_vec()
+}
+
+/// テクスチャをPNGとして保存する
+fn save_image(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    texture: &VsmlImage,
+    path: std::path::PathBuf,
+) {
+    let size = texture.size();
+    image::save_buffer(
+        path,
+        &read_pixels(device, queue, texture),
+        size.width,
+        size.height,
+        image::ColorType::Rgba8,
+    )
     .unwrap();
+}
+
+#[test]
+fn test_render_vrt() {
+    let (device, queue) = create_device();
 
     // rendering_contextとrendererを作成
     let mut context = RenderingContextImpl::new(device.clone(), queue.clone());
@@ -89,6 +155,8 @@ fn test_render_vrt() {
             y: 0.0,
             width: 1920.0,
             height: 1080.0,
+            opacity: 1.0,
+            transform: Transform::IDENTITY,
         },
     );
     let (texture, info) = create_image_data(
@@ -125,6 +193,8 @@ fn test_render_vrt() {
             y: 100.0,
             width: 200.0,
             height: 300.0,
+            opacity: 1.0,
+            transform: Transform::IDENTITY,
         },
     );
 
@@ -137,52 +207,84 @@ fn test_render_vrt() {
     assert_eq!(result.height(), 1080);
 
     // 試しに画像を保存
-    let mut encoder =
-        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
-    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-        label: None,
-        contents: &vec![0u8; output_dimensions.0 as usize * output_dimensions.1 as usize * 4],
-        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-    });
-    encoder.copy_texture_to_buffer(
-        wgpu::TexelCopyTextureInfo {
-            texture: &result,
-            mip_level: 0,
-            origin: wgpu::Origin3d::ZERO,
-            aspect: wgpu::TextureAspect::All,
+    save_image(&device, &queue, &result, vrt_out_path!("output.png"));
+}
+
+#[test]
+fn test_apply_style_vrt() {
+    let (device, queue) = create_device();
+    let mut context = RenderingContextImpl::new(device.clone(), queue.clone());
+
+    // 影がはみ出さないように、余白を空けて画像を描画する
+    let (texture, info) = create_image_data(
+        device.clone(),
+        queue.clone(),
+        include_bytes!("../test_assets/icon.png"),
+    );
+    let (width, height) = (info.width as u32 + 80, info.height as u32 + 80);
+    let mut renderer = context.create_renderer();
+    renderer.render_image(
+        texture,
+        RenderingInfo {
+            x: 40.0,
+            y: 40.0,
+            ..info
         },
-        wgpu::TexelCopyBufferInfo {
-            buffer: &buffer,
-            layout: wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(4 * output_dimensions.0),
-                rows_per_image: Some(output_dimensions.1),
+    );
+    let image = renderer.render(width, height);
+
+    let style = ImageEffectStyle {
+        filters: vec![
+            ImageFilter::Sepia(1.0),
+            ImageFilter::HueRotate(std::f32::consts::FRAC_PI_2),
+            ImageFilter::Blur(2.0),
+            ImageFilter::DropShadow {
+                x: 10.0,
+                y: 10.0,
+                blur: 8.0,
+                color: Color::from(0, 0, 0, 128),
             },
+        ],
+    };
+    let result = context.apply_style(image, style);
+
+    // assert
+    assert_eq!(result.width(), width);
+    assert_eq!(result.height(), height);
+
+    save_image(&device, &queue, &result, vrt_out_path!("output.png"));
+}
+
+#[test]
+fn test_color_matrix_filter_on_srgb_values() {
+    let (device, queue) = create_device();
+    let mut context = RenderingContextImpl::new(device.clone(), queue.clone());
+
+    let mut renderer = context.create_renderer();
+    renderer.render_box(
+        RenderBoxProperty {
+            background_color: Some(Color::from_rgb(0, 0, 0)),
         },
-        wgpu::Extent3d {
-            width: output_dimensions.0,
-            height: output_dimensions.1,
-            depth_or_array_layers: 1,
+        RenderingInfo {
+            x: 0.0,
+            y: 0.0,
+            width: 4.0,
+            height: 4.0,
+            opacity: 1.0,
+            transform: Transform::IDENTITY,
         },
     );
-    queue.submit(std::iter::once(encoder.finish()));
-
-    let slice = &buffer.slice(..);
-    slice.map_async(wgpu::MapMode::Read, |_| {});
+    let image = renderer.render(4, 4);
 
-    device
-        .poll(wgpu::PollType::Wait {
-            submission_index: None,
-            timeout: None,
-        })
-        .unwrap();
+    let style = ImageEffectStyle {
+        filters: vec![ImageFilter::Contrast(0.5)],
+    };
+    let result = context.apply_style(image, style);
 
-    image::save_buffer(
-        vrt_out_path!("output.png"),
-        &slice.get_mapped_range(),
-        output_dimensions.0,
-        output_dimensions.1,
-        image::ColorType::Rgba8,
-    )
-    .unwrap();
+    // contrast(0.5)はsRGBの値を0.5に近づけるので、黒は0.25(64)になる
+    let pixels = read_pixels(&device, &queue, &result);
+    for &channel in &pixels[..3] {
+        assert!(channel.abs_diff(64) <= 1, "{channel}");
+    }
+    assert_eq!(pixels[3], 255);
 }
@@ -1,22 +1,96 @@
+// ConvertErrorはエラーの位置の表示のためにElementPathを持ち大きいが、変換を中断するときに一度だけ返されるので許容する
+#![allow(clippy::result_large_err)]
+
 #[cfg(test)]
 mod tests;
 
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
+use thiserror::Error;
+use vsml_ast::position::Span;
 use vsml_ast::vsml::{Content, Element, Meta, VSML};
-use vsml_ast::vss::{Rule, VSSItem, VSSSelector, VSSSelectorTree};
-use vsml_core::ElementRect;
+use vsml_ast::vss::{
+    Rule, VSSItem, VSSKeyframes, VSSSelector, VSSSelectorAttributeValue, VSSSelectorTree,
+};
+use vsml_core::animation::{
+    AnimatedProperties, Animation, AnimationShorthand, AnimationTrack, Keyframe, parse_animations,
+};
 use vsml_core::schemas::{
-    AudioVolume, Duration, IVData, LayerMode, Length, ObjectData, ObjectProcessor, ObjectType,
-    Order, RectSize, TextData, TextStyleData, parse_font_family,
+    AudioVolume, Color, Direction, Duration, EdgeLengths, FilterFunction, IVData, LayerMode,
+    Length, ObjectData, ObjectFit, ObjectPosition, ObjectProcessor, ObjectType, Opacity, Order,
+    RectSize, TextData, TextStyleData, TimeEdges, TransformFunction, Transition,
+    TransitionShorthand, parse_audio_filter, parse_filter, parse_font_family, parse_transform,
 };
+use vsml_core::{
+    Alignment, AudioEffectStyle, Edges, ElementRect, ImageEffectStyle, ImageFilter, ObjectFitRect,
+    Transform,
+};
+
+/// VSMLからIVDataへの変換のエラー
+#[derive(Debug, Error, PartialEq)]
+pub enum ConvertError {
+    #[error("{0}: processor not found")]
+    ProcessorNotFound(ElementPath),
+    #[error("{0}: invalid value {2:?} for \"{1}\"")]
+    InvalidValue(ElementPath, String, String),
+    #[error(
+        "{0}: percentage value {2:?} for \"{1}\" cannot be resolved without a definite parent {1}"
+    )]
+    IndefinitePercentageBase(ElementPath, String, String),
+    #[error("{0}: custom property {1:?} is not defined")]
+    UndefinedCustomProperty(ElementPath, String),
+    #[error("{0}: custom property {1:?} references itself")]
+    CyclicCustomProperty(ElementPath, String),
+    #[error("{0}: keyframes {1:?} is not defined")]
+    UndefinedKeyframes(ElementPath, String),
+    #[error("{0}: property \"{1}\" cannot be animated")]
+    NotAnimatable(ElementPath, String),
+}
+
+/// エラーが発生したエレメントの、ルートからのタグ名の列とエラーの原因の範囲
+/// 範囲はVSSのルールが原因の場合はそのルール、それ以外はエレメントを指す
+/// `video.vsml:14:5 <txt>` のように表示される
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementPath {
+    pub tags: Vec<String>,
+    pub span: Span,
+    /// `span.source` が指すソースファイルのパスの一覧(`Meta::sources`)
+    pub sources: Arc<[String]>,
+}
+
+impl ElementPath {
+    /// 同じエレメントの、`span` の範囲を指すElementPathを返す
+    fn at(&self, span: Span) -> ElementPath {
+        ElementPath {
+            span,
+            ..self.clone()
+        }
+    }
+}
+
+impl fmt::Display for ElementPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(source) = self.sources.get(self.span.source.0) {
+            write!(f, "{source}:")?;
+        }
+        let tag = self.tags.last().map_or("", String::as_str);
+        write!(f, "{} <{tag}>", self.span.start)
+    }
+}
 
 pub fn convert<I, A>(
     vsml: &VSML,
     object_processor_provider: &impl ObjectProcessorProvider<I, A>,
-) -> IVData<I, A> {
+) -> Result<IVData<I, A>, ConvertError> {
     let &VSML {
-        meta: Meta { ref vss_items },
+        meta:
+            Meta {
+                ref vss_items,
+                ref keyframes,
+                ref sources,
+            },
         content:
             Content {
                 width,
@@ -24,148 +98,335 @@ pub fn convert<I, A>(
                 fps,
                 sampling_rate,
                 ref elements,
+                span,
             },
     } = vsml;
     let fps = fps.unwrap_or(60);
 
-    let mut vss_scanner = VssScanner::new(vss_items);
+    let mut vss_scanner = VssScanner::new(vss_items, keyframes, sources.as_slice().into());
     let cont_element = Element::Tag {
         name: "cont".to_string(),
         attributes: HashMap::new(),
         children: elements.clone(),
+        style: vec![],
+        span,
     };
     let cont_element_list = vec![cont_element];
-    let object = vss_scanner.traverse(&cont_element_list, |scanner| {
-        let Element::Tag {
-            name,
-            attributes,
-            children,
-        } = &cont_element_list[0]
-        else {
-            unreachable!()
-        };
-        convert_tag_element(
-            scanner,
-            0.0,
-            (0.0, 0.0),
-            name,
-            attributes,
-            children,
-            object_processor_provider,
-            fps,
-            RectSize {
-                width: width as f32,
-                height: height as f32,
-            },
-            None,
-            None,
-            None,
-        )
-    });
+    let object = vss_scanner
+        .traverse(&cont_element_list, 0, |scanner| {
+            let Element::Tag {
+                name,
+                attributes,
+                children,
+                span,
+                ..
+            } = &cont_element_list[0]
+            else {
+                unreachable!()
+            };
+            convert_tag_element(
+                scanner,
+                0.0,
+                (0.0, 0.0),
+                name,
+                attributes,
+                children,
+                *span,
+                object_processor_provider,
+                fps,
+                RectSize {
+                    width: width as f32,
+                    height: height as f32,
+                },
+                None,
+                None,
+                None,
+                None,
+                &HashMap::new(),
+            )
+        })?
+        .0;
 
-    IVData {
+    Ok(IVData {
         resolution_x: width,
         resolution_y: height,
         fps,
         sampling_rate: sampling_rate.unwrap_or(48_000),
         object,
-    }
+    })
 }
 
 struct VssScanner<'a> {
     vss_items: &'a [VSSItem],
+    /// 名前ごとの `@keyframes` の定義で、同じ名前の場合は後の定義が優先される
+    keyframes: HashMap<&'a str, &'a VSSKeyframes>,
+    /// エラーの表示に使うソースファイルのパスの一覧
+    sources: Arc<[String]>,
     /// ルート要素からscan対象の要素までの要素のリスト
-    traverse_stack: Vec<&'a [Element]>,
+    traverse_stack: Vec<TraverseEntry<'a>>,
+}
+
+/// 兄弟エレメントのリストと、その中での対象エレメントの位置
+#[derive(Clone, Copy)]
+struct TraverseEntry<'a> {
+    siblings: &'a [Element],
+    index: usize,
+}
+
+impl<'a> TraverseEntry<'a> {
+    fn new(siblings: &'a [Element], index: usize) -> TraverseEntry<'a> {
+        TraverseEntry { siblings, index }
+    }
+
+    fn element(&self) -> &'a Element {
+        &self.siblings[self.index]
+    }
+
+    /// 兄エレメントを近い順に返す
+    /// テキストはCSSと同様に兄弟関係の判定では無視する
+    fn elder_siblings(self) -> impl Iterator<Item = TraverseEntry<'a>> {
+        (0..self.index)
+            .rev()
+            .map(move |index| TraverseEntry::new(self.siblings, index))
+            .filter(|entry| matches!(entry.element(), Element::Tag { .. }))
+    }
+
+    /// 弟エレメントを近い順に返す
+    fn younger_siblings(self) -> impl Iterator<Item = TraverseEntry<'a>> {
+        (self.index + 1..self.siblings.len())
+            .map(move |index| TraverseEntry::new(self.siblings, index))
+            .filter(|entry| matches!(entry.element(), Element::Tag { .. }))
+    }
 }
 
 impl<'a> VssScanner<'a> {
-    fn new(vss_items: &'a [VSSItem]) -> VssScanner<'a> {
+    fn new(
+        vss_items: &'a [VSSItem],
+        keyframes: &'a [VSSKeyframes],
+        sources: Arc<[String]>,
+    ) -> VssScanner<'a> {
         VssScanner {
             vss_items,
+            keyframes: keyframes
+                .iter()
+                .map(|keyframes| (keyframes.name.as_str(), keyframes))
+                .collect(),
+            sources,
             traverse_stack: Vec::new(),
         }
     }
 
     /// traverse_stackに対して、selectorと一致するスタイルがないか絞り込み、一致するスタイルを取得している
+    /// スタイルは優先度の低い順に返すため、後に返されたものほど優先される
+    /// 優先度は `!important` の有無、style属性での指定か、詳細度、記述順の順で比較する
     fn scan(&mut self) -> impl Iterator<Item = &Rule> + '_ {
-        return self
+        let mut rules = self
             .vss_items
             .iter()
-            .filter(|vss_item| {
-                vss_item.selectors.iter().any(|selector| {
-                    SelectorTreeMatchChecker::new(&self.traverse_stack).is_match(selector)
-                })
+            .filter_map(|vss_item| {
+                // 複数のセレクタにマッチした場合は最も詳細度の高いものを採用する
+                let specificity = vss_item
+                    .selectors
+                    .iter()
+                    .filter(|selector| {
+                        SelectorTreeMatchChecker::new(&self.traverse_stack).is_match(selector)
+                    })
+                    .map(Specificity::of_tree)
+                    .max()?;
+                Some((specificity, vss_item))
+            })
+            .flat_map(|(specificity, vss_item)| {
+                vss_item
+                    .rules
+                    .iter()
+                    .map(move |rule| (false, specificity, rule))
             })
-            .flat_map(|vss_item| &vss_item.rules);
+            .collect::<Vec<_>>();
+        // style属性のルールはどのセレクタよりも優先される
+        if let Some(Element::Tag { style, .. }) =
+            self.traverse_stack.last().map(TraverseEntry::element)
+        {
+            rules.extend(
+                style
+                    .iter()
+                    .map(|rule| (true, Specificity::default(), rule)),
+            );
+        }
+        // 安定ソートのため、同じ優先度のスタイルは記述順のまま残る
+        rules.sort_by_key(|&(inline, specificity, rule)| (rule.important, inline, specificity));
+        return rules.into_iter().map(|(_, _, rule)| rule);
         struct SelectorTreeMatchChecker<'a> {
-            target_stack: &'a [&'a [Element]],
+            target_stack: &'a [TraverseEntry<'a>],
         }
-        impl SelectorTreeMatchChecker<'_> {
-            fn new<'a>(target_stack: &'a [&'a [Element]]) -> SelectorTreeMatchChecker<'a> {
+        impl<'a> SelectorTreeMatchChecker<'a> {
+            fn new(target_stack: &'a [TraverseEntry<'a>]) -> SelectorTreeMatchChecker<'a> {
                 SelectorTreeMatchChecker { target_stack }
             }
 
-            fn is_match(&mut self, selector_tree: &VSSSelectorTree) -> bool {
+            fn is_match(&self, selector_tree: &VSSSelectorTree) -> bool {
+                !self.matched_positions(selector_tree).is_empty()
+            }
+
+            /// selector_treeの一番左のセレクタにマッチしうるエレメントの位置を全て返す
+            /// 位置は(target_stackの中での深さ, エレメント)で、エレメントはtarget_stack[深さ - 1]かその兄弟になる
+            /// 右側のセレクタにマッチするエレメントが複数ある場合も、全ての候補から左側をたどる
+            fn matched_positions(
+                &self,
+                selector_tree: &VSSSelectorTree,
+            ) -> Vec<(usize, TraverseEntry<'a>)> {
+                let mut positions: Vec<(usize, TraverseEntry<'a>)> = vec![];
+                let mut push = |depth: usize, entry: TraverseEntry<'a>| {
+                    // 同じ深さのエレメントは同じ兄弟のリストに含まれる
+                    if !positions
+                        .iter()
+                        .any(|&(d, e)| d == depth && e.index == entry.index)
+                    {
+                        positions.push((depth, entry));
+                    }
+                };
                 match selector_tree {
                     VSSSelectorTree::Selectors(selectors) => {
-                        let Some((tail, head)) = self.target_stack.split_last() else {
-                            return false;
-                        };
-                        self.target_stack = head;
-                        selector_is_match(selectors, tail)
+                        if let Some(&tail) = self.target_stack.last()
+                            && selector_is_match(selectors, tail)
+                        {
+                            push(self.target_stack.len(), tail);
+                        }
                     }
                     VSSSelectorTree::Descendant(parent_selectors, child_tree) => {
-                        if !self.is_match(child_tree) {
-                            return false;
-                        }
-                        // この時点で、child_treeにマッチする部分はself.target_stackから消されているはず
-                        while let Some((tail, head)) = self.target_stack.split_last() {
-                            self.target_stack = head;
-                            if selector_is_match(parent_selectors, tail) {
-                                return true;
+                        for (depth, _) in self.matched_positions(child_tree) {
+                            for ancestor_depth in (1..depth).rev() {
+                                let ancestor = self.target_stack[ancestor_depth - 1];
+                                if selector_is_match(parent_selectors, ancestor) {
+                                    push(ancestor_depth, ancestor);
+                                }
                             }
                         }
-                        false
                     }
                     VSSSelectorTree::Child(parent_selectors, child_tree) => {
-                        if !self.is_match(child_tree) {
-                            return false;
+                        for (depth, _) in self.matched_positions(child_tree) {
+                            if depth > 1 {
+                                let parent = self.target_stack[depth - 2];
+                                if selector_is_match(parent_selectors, parent) {
+                                    push(depth - 1, parent);
+                                }
+                            }
+                        }
+                    }
+                    VSSSelectorTree::Sibling(sibling_selectors, younger_tree) => {
+                        // 兄弟は同じ親を持つため、深さはそのままになる
+                        for (depth, entry) in self.matched_positions(younger_tree) {
+                            for sibling in entry.elder_siblings() {
+                                if selector_is_match(sibling_selectors, sibling) {
+                                    push(depth, sibling);
+                                }
+                            }
+                        }
+                    }
+                    VSSSelectorTree::AdjSibling(sibling_selectors, younger_tree) => {
+                        for (depth, entry) in self.matched_positions(younger_tree) {
+                            if let Some(sibling) = entry.elder_siblings().next()
+                                && selector_is_match(sibling_selectors, sibling)
+                            {
+                                push(depth, sibling);
+                            }
                         }
-                        let Some((tail, head)) = self.target_stack.split_last() else {
-                            return false;
-                        };
-                        self.target_stack = head;
-                        selector_is_match(parent_selectors, tail)
                     }
-                    // TODO: 他のセレクタも実装
-                    _ => todo!(),
                 }
+                positions
             }
         }
     }
 
-    fn traverse<R>(&mut self, element: &'a [Element], f: impl FnOnce(&mut Self) -> R) -> R {
-        self.traverse_stack.push(element);
+    /// traverse_stackの末尾のエレメントの位置を返す
+    fn element_path(&self, span: Span) -> ElementPath {
+        ElementPath {
+            tags: self
+                .traverse_stack
+                .iter()
+                .filter_map(|entry| match entry.element() {
+                    Element::Tag { name, .. } => Some(name.clone()),
+                    Element::Text(..) => None,
+                })
+                .collect(),
+            span,
+            sources: self.sources.clone(),
+        }
+    }
+
+    fn traverse<R>(
+        &mut self,
+        siblings: &'a [Element],
+        index: usize,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        self.traverse_stack
+            .push(TraverseEntry::new(siblings, index));
         let result = f(self);
         self.traverse_stack.pop();
         result
     }
 }
-fn selector_is_match(selectors: &[VSSSelector], element: &[Element]) -> bool {
+/// セレクタの詳細度
+/// (IDの数, クラス・アトリビュート・擬似クラスの数, タグ名の数) の順で比較する
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+struct Specificity(u32, u32, u32);
+
+impl Specificity {
+    fn of_tree(selector_tree: &VSSSelectorTree) -> Specificity {
+        match selector_tree {
+            VSSSelectorTree::Selectors(selectors) => Specificity::of_selectors(selectors),
+            VSSSelectorTree::Descendant(selectors, tree)
+            | VSSSelectorTree::Child(selectors, tree)
+            | VSSSelectorTree::Sibling(selectors, tree)
+            | VSSSelectorTree::AdjSibling(selectors, tree) => {
+                Specificity::of_selectors(selectors) + Specificity::of_tree(tree)
+            }
+        }
+    }
+
+    fn of_selectors(selectors: &[VSSSelector]) -> Specificity {
+        selectors
+            .iter()
+            .map(|selector| match selector {
+                VSSSelector::All => Specificity(0, 0, 0),
+                VSSSelector::Tag(_) => Specificity(0, 0, 1),
+                VSSSelector::Class(_)
+                | VSSSelector::Attribute(_, _)
+                | VSSSelector::PseudoClass(_)
+                | VSSSelector::NthChild(_, _) => Specificity(0, 1, 0),
+                VSSSelector::Id(_) => Specificity(1, 0, 0),
+                // CSSと同様に `:not` 自体は数えず、引数のセレクタの詳細度を用いる
+                VSSSelector::Not(selectors) => Specificity::of_selectors(selectors),
+            })
+            .fold(Specificity::default(), |acc, specificity| acc + specificity)
+    }
+}
+
+impl std::ops::Add for Specificity {
+    type Output = Specificity;
+
+    fn add(self, rhs: Specificity) -> Specificity {
+        Specificity(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+    }
+}
+
+fn selector_is_match(selectors: &[VSSSelector], entry: TraverseEntry) -> bool {
     let element_tag;
     let element_id;
     let element_classes;
-    match element.last().unwrap() {
+    let element_attributes;
+    match entry.element() {
         Element::Tag {
             name, attributes, ..
         } => {
             element_tag = name.as_str();
+            element_attributes = attributes;
             element_id = attributes.get("id").map(String::as_str);
             element_classes = attributes
                 .get("class")
                 .map_or_else(HashSet::new, |classes| classes.split_whitespace().collect());
         }
-        Element::Text(_) => return false,
+        Element::Text(..) => return false,
     };
     selectors
         .iter()
@@ -174,15 +435,585 @@ fn selector_is_match(selectors: &[VSSSelector], element: &[Element]) -> bool {
             VSSSelector::Tag(tag) => element_tag == tag.as_str(),
             VSSSelector::Class(class_name) => element_classes.contains(class_name.as_str()),
             VSSSelector::Id(id_name) => element_id == Some(id_name),
-            VSSSelector::PseudoClass(_) => {
-                todo!()
-            }
-            VSSSelector::Attribute(_, _) => {
-                todo!()
+            VSSSelector::PseudoClass(pseudo_class) => match pseudo_class.as_str() {
+                "first-child" => entry.elder_siblings().next().is_none(),
+                "last-child" => entry.younger_siblings().next().is_none(),
+                // 未対応の擬似クラスにはマッチしない
+                _ => false,
+            },
+            VSSSelector::NthChild(a, b) => {
+                let n = entry.elder_siblings().count() as i32 + 1;
+                // n = a * k + b を満たす0以上の整数kが存在するか
+                match a {
+                    0 => n == *b,
+                    a => (n - b) % a == 0 && (n - b) / a >= 0,
+                }
             }
+            VSSSelector::Not(selectors) => !selector_is_match(selectors, entry),
+            VSSSelector::Attribute(name, value) => element_attributes
+                .get(name)
+                .is_some_and(|attribute| attribute_is_match(value, attribute)),
+        })
+}
+
+fn attribute_is_match(value: &VSSSelectorAttributeValue, attribute: &str) -> bool {
+    match value {
+        VSSSelectorAttributeValue::None => true,
+        VSSSelectorAttributeValue::Equal(value) => attribute == value,
+        VSSSelectorAttributeValue::Contain(value) => {
+            attribute.split_whitespace().any(|item| item == value)
+        }
+        // CSSと同様に空文字列を指定した場合はどのエレメントにもマッチしない
+        VSSSelectorAttributeValue::StartWith(value) => {
+            !value.is_empty() && attribute.starts_with(value.as_str())
+        }
+        VSSSelectorAttributeValue::EndWith(value) => {
+            !value.is_empty() && attribute.ends_with(value.as_str())
+        }
+        VSSSelectorAttributeValue::Include(value) => {
+            !value.is_empty() && attribute.contains(value.as_str())
+        }
+    }
+}
+
+/// ルールの値をパースする
+fn parse_rule_value<T: FromStr>(path: &ElementPath, rule: &Rule) -> Result<T, ConvertError> {
+    rule.value.parse().map_err(|_| invalid_value(path, rule))
+}
+
+fn invalid_value(path: &ElementPath, rule: &Rule) -> ConvertError {
+    ConvertError::InvalidValue(
+        path.at(rule.span),
+        rule.property.clone(),
+        rule.value.clone(),
+    )
+}
+
+fn indefinite_percentage_base(path: &ElementPath, rule: &Rule) -> ConvertError {
+    ConvertError::IndefinitePercentageBase(
+        path.at(rule.span),
+        rule.property.clone(),
+        rule.value.clone(),
+    )
+}
+
+/// time-margin, time-paddingの前後の時間(s)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct TimeSpacing {
+    start: f64,
+    end: f64,
+}
+
+/// time-margin, delayのような時刻のずれを表すDurationを秒に変換する
+/// %指定は親要素のdurationを基準にし、fitは指定できない
+fn resolve_time_offset(
+    path: &ElementPath,
+    rule: &Rule,
+    duration: &Duration,
+    fps: u32,
+    parent_duration: Option<f64>,
+) -> Result<f64, ConvertError> {
+    match *duration {
+        Duration::Percent(percent) => {
+            let parent_duration = parent_duration
+                .filter(|duration| duration.is_finite())
+                .ok_or_else(|| indefinite_percentage_base(path, rule))?;
+            Ok(parent_duration * (percent / 100.0))
+        }
+        Duration::Frame(frames) => Ok(frames as f64 / fps as f64),
+        Duration::Second(seconds) => Ok(seconds),
+        Duration::Fit => Err(invalid_value(path, rule)),
+    }
+}
+
+/// time-margin, time-paddingの値を秒に変換する
+fn resolve_time_spacing(
+    path: &ElementPath,
+    rule: &Rule,
+    fps: u32,
+    parent_duration: Option<f64>,
+    allows_negative: bool,
+) -> Result<TimeSpacing, ConvertError> {
+    let edges: TimeEdges = parse_rule_value(path, rule)?;
+    let resolve =
+        |duration: &Duration| resolve_time_offset(path, rule, duration, fps, parent_duration);
+    let spacing = TimeSpacing {
+        start: resolve(&edges.start)?,
+        end: resolve(&edges.end)?,
+    };
+    if !allows_negative && (spacing.start < 0.0 || spacing.end < 0.0) {
+        return Err(invalid_value(path, rule));
+    }
+    Ok(spacing)
+}
+
+/// object-fit, object-positionから、エレメントの矩形の中での画像の位置とサイズを計算する
+fn calc_object_fit_rect(
+    object_fit: ObjectFit,
+    object_position: &ObjectPosition,
+    image_size: RectSize,
+    box_size: RectSize,
+    resolution: RectSize,
+) -> ObjectFitRect {
+    let contain_scale =
+        (box_size.width / image_size.width).min(box_size.height / image_size.height);
+    let (width, height) = match object_fit {
+        ObjectFit::Fill => (box_size.width, box_size.height),
+        ObjectFit::Contain => (
+            image_size.width * contain_scale,
+            image_size.height * contain_scale,
+        ),
+        ObjectFit::Cover => {
+            let scale =
+                (box_size.width / image_size.width).max(box_size.height / image_size.height);
+            (image_size.width * scale, image_size.height * scale)
+        }
+        ObjectFit::None => (image_size.width, image_size.height),
+        ObjectFit::ScaleDown => {
+            let scale = contain_scale.min(1.0);
+            (image_size.width * scale, image_size.height * scale)
+        }
+    };
+    // %指定は矩形と画像のサイズの差に対する割合になる
+    let resolve = |length: &Length, free_space: f32| match *length {
+        Length::Pixel(px) => px,
+        Length::ResolutionWidth(rw) => resolution.width * (rw / 100.0),
+        Length::ResolutionHeight(rh) => resolution.height * (rh / 100.0),
+        Length::Percent(percent) => free_space * (percent / 100.0) as f32,
+    };
+    ObjectFitRect {
+        x: resolve(&object_position.x, box_size.width - width),
+        y: resolve(&object_position.y, box_size.height - height),
+        width,
+        height,
+    }
+}
+
+/// transformの変換関数を左から順に合成する
+/// translateの%指定は背景を描画する矩形のサイズを基準にする
+fn resolve_transform(
+    path: &ElementPath,
+    rule: &Rule,
+    functions: &[TransformFunction],
+    resolution: RectSize,
+    box_size: RectSize,
+) -> Result<Transform, ConvertError> {
+    functions
+        .iter()
+        .try_fold(Transform::IDENTITY, |transform, function| {
+            let function = match *function {
+                TransformFunction::Translate(ref x, ref y) => Transform::translate(
+                    resolve_length(path, rule, x, resolution, Some(box_size.width))?,
+                    resolve_length(path, rule, y, resolution, Some(box_size.height))?,
+                ),
+                TransformFunction::Scale(x, y) => Transform::scale(x, y),
+                TransformFunction::Rotate(angle) => Transform::rotate(angle),
+                TransformFunction::Skew(x, y) => Transform::skew(x, y),
+            };
+            Ok(transform * function)
         })
 }
 
+/// filterのフィルター関数の長さをピクセルに変換する
+/// ぼかしや影の長さは%指定できない
+fn resolve_filter(
+    path: &ElementPath,
+    rule: &Rule,
+    functions: Vec<FilterFunction>,
+    resolution: RectSize,
+) -> Result<ImageEffectStyle, ConvertError> {
+    let resolve = |length: &Length| match length {
+        Length::Percent(_) => Err(invalid_value(path, rule)),
+        length => resolve_length(path, rule, length, resolution, None),
+    };
+    let resolve_blur = |length: &Length| match resolve(length)? {
+        blur if blur < 0.0 => Err(invalid_value(path, rule)),
+        blur => Ok(blur),
+    };
+    let filters = functions
+        .into_iter()
+        .map(|function| {
+            Ok(match function {
+                FilterFunction::Blur(blur) => ImageFilter::Blur(resolve_blur(&blur)?),
+                FilterFunction::Brightness(amount) => ImageFilter::Brightness(amount),
+                FilterFunction::Contrast(amount) => ImageFilter::Contrast(amount),
+                FilterFunction::Saturate(amount) => ImageFilter::Saturate(amount),
+                FilterFunction::Grayscale(amount) => ImageFilter::Grayscale(amount),
+                FilterFunction::Sepia(amount) => ImageFilter::Sepia(amount),
+                FilterFunction::HueRotate(angle) => ImageFilter::HueRotate(angle),
+                FilterFunction::Invert(amount) => ImageFilter::Invert(amount),
+                FilterFunction::DropShadow { x, y, blur, color } => ImageFilter::DropShadow {
+                    x: resolve(&x)?,
+                    y: resolve(&y)?,
+                    blur: resolve_blur(&blur)?,
+                    color,
+                },
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ImageEffectStyle { filters })
+}
+
+/// Lengthをピクセルに変換する
+/// `percent_base` は%指定の基準となる長さで、Noneの場合は%指定を解決できない
+fn resolve_length(
+    path: &ElementPath,
+    rule: &Rule,
+    length: &Length,
+    resolution: RectSize,
+    percent_base: Option<f32>,
+) -> Result<f32, ConvertError> {
+    Ok(match *length {
+        Length::Pixel(px) => px,
+        Length::ResolutionWidth(rw) => resolution.width * (rw / 100.0),
+        Length::ResolutionHeight(rh) => resolution.height * (rh / 100.0),
+        Length::Percent(percent) => {
+            percent_base.ok_or_else(|| indefinite_percentage_base(path, rule))?
+                * (percent / 100.0) as f32
+        }
+    })
+}
+
+/// margin, paddingの値をピクセルに変換する
+/// CSSと同様に、%指定は上下左右とも親要素の幅を基準にする
+fn resolve_edges(
+    path: &ElementPath,
+    rule: &Rule,
+    resolution: RectSize,
+    parent_width: Option<f32>,
+    allows_negative: bool,
+) -> Result<Edges, ConvertError> {
+    let lengths: EdgeLengths = parse_rule_value(path, rule)?;
+    let resolve = |length| resolve_length(path, rule, length, resolution, parent_width);
+    let edges = Edges {
+        top: resolve(&lengths.top)?,
+        right: resolve(&lengths.right)?,
+        bottom: resolve(&lengths.bottom)?,
+        left: resolve(&lengths.left)?,
+    };
+    let sides = [edges.top, edges.right, edges.bottom, edges.left];
+    if !allows_negative && sides.iter().any(|&side| side < 0.0) {
+        return Err(invalid_value(path, rule));
+    }
+    Ok(edges)
+}
+
+/// margin-top, padding-leftのような一辺だけの値をピクセルに変換する
+fn resolve_edge(
+    path: &ElementPath,
+    rule: &Rule,
+    resolution: RectSize,
+    parent_width: Option<f32>,
+    allows_negative: bool,
+) -> Result<f32, ConvertError> {
+    let length = parse_rule_value(path, rule)?;
+    let side = resolve_length(path, rule, &length, resolution, parent_width)?;
+    if !allows_negative && side < 0.0 {
+        return Err(invalid_value(path, rule));
+    }
+    Ok(side)
+}
+
+/// animationの値を解決するときに参照する、対象エレメントの情報
+struct AnimationContext<'a> {
+    fps: u32,
+    /// エレメントのduration(s)
+    duration: f64,
+    resolution: RectSize,
+    parent_size: Option<RectSize>,
+    custom_properties: &'a HashMap<String, String>,
+}
+
+/// animationプロパティの1つ分の値から、エレメントに適用するAnimationを作る
+/// 時間の%指定はエレメントのdurationを基準にする
+fn resolve_animation(
+    path: &ElementPath,
+    rule: &Rule,
+    shorthand: &AnimationShorthand,
+    vss_scanner: &VssScanner,
+    base: &AnimatedProperties,
+    context: &AnimationContext,
+) -> Result<Animation, ConvertError> {
+    let keyframes = vss_scanner
+        .keyframes
+        .get(shorthand.name.as_str())
+        .ok_or_else(|| {
+            ConvertError::UndefinedKeyframes(path.at(rule.span), shorthand.name.clone())
+        })?;
+    let resolve_time =
+        |time| resolve_time_offset(path, rule, time, context.fps, Some(context.duration));
+    let animation_duration = resolve_time(&shorthand.duration)?;
+    if animation_duration < 0.0 {
+        return Err(invalid_value(path, rule));
+    }
+    Ok(Animation {
+        tracks: resolve_keyframes(path, keyframes, base, context)?,
+        duration: animation_duration,
+        delay: resolve_time(&shorthand.delay)?,
+        iteration_count: shorthand.iteration_count,
+        timing_function: shorthand.timing_function,
+        direction: shorthand.direction,
+        fill_mode: shorthand.fill_mode,
+    })
+}
+
+/// `@keyframes` のルールをプロパティごとのキーフレームの列に変換する
+/// 長さの%指定はwidth, xなどのプロパティと同様に親要素のサイズを基準にする
+fn resolve_keyframes(
+    path: &ElementPath,
+    keyframes: &VSSKeyframes,
+    base: &AnimatedProperties,
+    &AnimationContext {
+        resolution,
+        parent_size,
+        custom_properties,
+        ..
+    }: &AnimationContext,
+) -> Result<Vec<AnimationTrack>, ConvertError> {
+    let (mut x, mut y, mut width, mut height) = (vec![], vec![], vec![], vec![]);
+    let (mut background_color, mut font_color, mut opacity) = (vec![], vec![], vec![]);
+    let parent_width = parent_size.map(|size| size.width);
+    let parent_height = parent_size.map(|size| size.height);
+    for keyframe in &keyframes.keyframes {
+        for rule in &keyframe.rules {
+            let path = &path.at(rule.span);
+            let rule = &Rule {
+                value: substitute_var(path, &rule.property, &rule.value, &mut |name| {
+                    Ok(custom_properties.get(name).cloned())
+                })?,
+                ..rule.clone()
+            };
+            let length = |percent_base| -> Result<f32, ConvertError> {
+                let length = parse_rule_value(path, rule)?;
+                resolve_length(path, rule, &length, resolution, percent_base)
+            };
+            let size = |percent_base| match length(percent_base)? {
+                size if size < 0.0 => Err(invalid_value(path, rule)),
+                size => Ok(size),
+            };
+            let offsets = &keyframe.offsets;
+            match rule.property.as_str() {
+                "x" | "left" => push_keyframes(&mut x, offsets, length(parent_width)?),
+                "y" | "top" => push_keyframes(&mut y, offsets, length(parent_height)?),
+                "width" => push_keyframes(&mut width, offsets, size(parent_width)?),
+                "height" => push_keyframes(&mut height, offsets, size(parent_height)?),
+                "background-color" => push_keyframes(
+                    &mut background_color,
+                    offsets,
+                    parse_rule_value(path, rule)?,
+                ),
+                "font-color" => {
+                    push_keyframes(&mut font_color, offsets, parse_rule_value(path, rule)?)
+                }
+                "opacity" => {
+                    let Opacity(value) = parse_rule_value(path, rule)?;
+                    push_keyframes(&mut opacity, offsets, value)
+                }
+                _ => {
+                    return Err(ConvertError::NotAnimatable(
+                        path.clone(),
+                        rule.property.clone(),
+                    ));
+                }
+            }
+        }
+    }
+
+    let font_color_base = base.font_color.unwrap_or_default();
+    // 背景色がない場合は、隣のキーフレームの色を透明にした色から変化する
+    let transparent = |color| base.background_color.unwrap_or(Color { a: 0, ..color });
+    Ok([
+        complete_keyframes(x, |_| base.x, |_| base.x).map(AnimationTrack::X),
+        complete_keyframes(y, |_| base.y, |_| base.y).map(AnimationTrack::Y),
+        complete_keyframes(width, |_| base.width, |_| base.width).map(AnimationTrack::Width),
+        complete_keyframes(height, |_| base.height, |_| base.height).map(AnimationTrack::Height),
+        complete_keyframes(background_color, transparent, transparent)
+            .map(AnimationTrack::BackgroundColor),
+        complete_keyframes(font_color, |_| font_color_base, |_| font_color_base)
+            .map(AnimationTrack::FontColor),
+        complete_keyframes(opacity, |_| base.opacity, |_| base.opacity)
+            .map(AnimationTrack::Opacity),
+    ]
+    .into_iter()
+    .flatten()
+    .collect())
+}
+
+/// `offsets` (%)の位置に同じ値のキーフレームを追加する
+fn push_keyframes<T: Copy>(track: &mut Vec<Keyframe<T>>, offsets: &[f64], value: T) {
+    track.extend(offsets.iter().map(|offset| Keyframe {
+        offset: offset / 100.0,
+        value,
+    }));
+}
+
+/// キーフレームを位置の順に並べ、0%, 100%のキーフレームがない場合は補う
+/// 補う値は `from`, `to` に最初, 最後のキーフレームの値を渡して求める
+/// キーフレームが1つもない場合はNoneを返す
+fn complete_keyframes<T: Copy>(
+    mut keyframes: Vec<Keyframe<T>>,
+    from: impl Fn(T) -> T,
+    to: impl Fn(T) -> T,
+) -> Option<Vec<Keyframe<T>>> {
+    if keyframes.is_empty() {
+        return None;
+    }
+    // 同じ位置のキーフレームは記述順のまま残る
+    keyframes.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+    let first = &keyframes[0];
+    if first.offset > 0.0 {
+        let value = from(first.value);
+        keyframes.insert(0, Keyframe { offset: 0.0, value });
+    }
+    let last = &keyframes[keyframes.len() - 1];
+    if last.offset < 1.0 {
+        let value = to(last.value);
+        keyframes.push(Keyframe { offset: 1.0, value });
+    }
+    Some(keyframes)
+}
+
+/// エレメントで宣言されたカスタムプロパティ(`--name`)を解決し、親から継承したものと合わせて返す
+fn resolve_custom_properties(
+    path: &ElementPath,
+    rules: &[&Rule],
+    parent_custom_properties: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, ConvertError> {
+    // 後に来るルールほど優先されるため、上書きしながら集める
+    let declared = rules
+        .iter()
+        .filter(|rule| rule.property.starts_with("--"))
+        .map(|&rule| (rule.property.as_str(), rule))
+        .collect::<HashMap<_, _>>();
+    let mut custom_properties = parent_custom_properties.clone();
+    for &name in declared.keys() {
+        let value =
+            resolve_custom_property(path, name, &declared, parent_custom_properties, &mut vec![])?;
+        custom_properties.insert(name.to_owned(), value.unwrap());
+    }
+    Ok(custom_properties)
+}
+
+fn resolve_custom_property(
+    path: &ElementPath,
+    name: &str,
+    declared: &HashMap<&str, &Rule>,
+    parent_custom_properties: &HashMap<String, String>,
+    resolving: &mut Vec<String>,
+) -> Result<Option<String>, ConvertError> {
+    let Some(rule) = declared.get(name) else {
+        return Ok(parent_custom_properties.get(name).cloned());
+    };
+    if resolving
+        .iter()
+        .any(|resolving_name| resolving_name == name)
+    {
+        return Err(ConvertError::CyclicCustomProperty(
+            path.at(rule.span),
+            name.to_owned(),
+        ));
+    }
+    resolving.push(name.to_owned());
+    let value = substitute_var(&path.at(rule.span), name, &rule.value, &mut |name| {
+        resolve_custom_property(path, name, declared, parent_custom_properties, resolving)
+    })?;
+    resolving.pop();
+    Ok(Some(value))
+}
+
+/// 値の中の `var(--name)` や `var(--name, fallback)` をカスタムプロパティの値で置き換える
+/// `property` は値が指定されたプロパティの名前で、エラーの報告に使う
+fn substitute_var(
+    path: &ElementPath,
+    property: &str,
+    value: &str,
+    lookup: &mut dyn FnMut(&str) -> Result<Option<String>, ConvertError>,
+) -> Result<String, ConvertError> {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = find_var_function(rest) {
+        result.push_str(&rest[..start]);
+        let arguments_start = start + "var(".len();
+        // fallbackに括弧が含まれる場合があるため、文字列の外の対応する閉じ括弧を探す
+        let mut depth = 1;
+        let mut quote = QuoteState::default();
+        let arguments_end = rest[arguments_start..]
+            .char_indices()
+            .find_map(|(i, c)| {
+                if quote.update(c) {
+                    return None;
+                }
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                (depth == 0).then_some(arguments_start + i)
+            })
+            .ok_or_else(|| {
+                ConvertError::InvalidValue(path.clone(), property.to_owned(), value.to_owned())
+            })?;
+        let arguments = &rest[arguments_start..arguments_end];
+        let (name, fallback) = match arguments.split_once(',') {
+            Some((name, fallback)) => (name.trim(), Some(fallback.trim())),
+            None => (arguments.trim(), None),
+        };
+        let replaced = match (lookup(name)?, fallback) {
+            (Some(replaced), _) => replaced,
+            (None, Some(fallback)) => substitute_var(path, property, fallback, lookup)?,
+            (None, None) => {
+                return Err(ConvertError::UndefinedCustomProperty(
+                    path.clone(),
+                    name.to_owned(),
+                ));
+            }
+        };
+        result.push_str(&replaced);
+        rest = &rest[arguments_end + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// 引用符で囲まれた文字列の外にある、トークンの先頭の `var(` の位置を探す
+/// `Envar(x)` のような識別子の一部や、`"var(x)"` のような文字列の中の `var(` は置き換えない
+fn find_var_function(value: &str) -> Option<usize> {
+    let mut quote = QuoteState::default();
+    let mut previous = None;
+    for (i, c) in value.char_indices() {
+        if !quote.update(c)
+            && value[i..].starts_with("var(")
+            && previous.is_none_or(|p: char| p.is_whitespace() || p == ',' || p == '(')
+        {
+            return Some(i);
+        }
+        previous = Some(c);
+    }
+    None
+}
+
+/// 値を先頭から1文字ずつ読むときの、引用符で囲まれた文字列の中かどうかの状態
+#[derive(Default)]
+struct QuoteState {
+    quote: Option<char>,
+    escaped: bool,
+}
+
+impl QuoteState {
+    /// `c` を読んで状態を進め、`c` が文字列の一部(引用符を含む)ならtrueを返す
+    fn update(&mut self, c: char) -> bool {
+        match self.quote {
+            Some(_) if self.escaped => self.escaped = false,
+            Some(_) if c == '\\' => self.escaped = true,
+            Some(quote) if c == quote => self.quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => self.quote = Some(c),
+            None => return false,
+        }
+        true
+    }
+}
+
 pub trait ObjectProcessorProvider<I, A> {
     fn get_processor(&self, name: &str) -> Option<Arc<dyn ObjectProcessor<I, A>>>;
 }
@@ -193,6 +1024,7 @@ impl<I, A> ObjectProcessorProvider<I, A> for HashMap<String, Arc<dyn ObjectProce
     }
 }
 
+/// エレメントを変換し、親要素が並べるときに使う前後の時間(time-margin, delay)と合わせて返す
 // TODO: 引数多すぎ警告を修正する
 #[allow(clippy::too_many_arguments)]
 fn convert_tag_element<'a, I, A>(
@@ -202,20 +1034,24 @@ fn convert_tag_element<'a, I, A>(
     name: &str,
     attributes: &HashMap<String, String>,
     children: &'a [Element],
+    span: Span,
     object_processor_provider: &impl ObjectProcessorProvider<I, A>,
     fps: u32,
     resolution: RectSize,
     parent_text_style: Option<TextStyleData>,
+    parent_order: Option<Order>,
     parent_duration: Option<f64>,
     parent_size: Option<RectSize>,
-) -> ObjectData<I, A> {
+    parent_custom_properties: &HashMap<String, String>,
+) -> Result<(ObjectData<I, A>, TimeSpacing), ConvertError> {
+    let path = vss_scanner.element_path(span);
     // スタイル情報
     let object_type = match name {
         "cont" | "seq" | "prl" | "layer" => ObjectType::Wrap,
         name => ObjectType::Other(
             object_processor_provider
                 .get_processor(name)
-                .expect("Processor not found"),
+                .ok_or_else(|| ConvertError::ProcessorNotFound(path.clone()))?,
         ),
     };
     let mut target_duration = match &object_type {
@@ -227,6 +1063,8 @@ fn convert_tag_element<'a, I, A>(
         ObjectType::Wrap => RectSize::ZERO,
         ObjectType::Other(processor) => processor.default_image_size(attributes),
     };
+    // object-fitの計算に使う画像本来のサイズ
+    let image_size = target_size;
     let mut order: Order = match name {
         "seq" | "cont" => Order::Sequence,
         "prl" | "layer" => Order::Parallel,
@@ -239,36 +1077,99 @@ fn convert_tag_element<'a, I, A>(
     };
     let mut text_style = parent_text_style.clone().unwrap_or_default();
     let mut audio_volume = 1.0;
+    let mut audio_effect_style = AudioEffectStyle::default();
+    let mut opacity = 1.0;
+    let mut image_effect_style = ImageEffectStyle::default();
     let mut background_color = None;
     let mut rule_target_width = None;
     let mut rule_target_height = None;
+    // x, yが指定された場合は自動で計算された位置ではなく、親のalignmentの基準点からの位置になる
+    let mut rule_target_x = None;
+    let mut rule_target_y = None;
+    // delayは並列に並べる親要素の中での開始時刻を遅らせる
+    // 順番に並べる親要素の中では無視され、前の要素との間隔はtime-marginで指定する
+    let mut delay = 0.0;
+    let mut time_margin = TimeSpacing::default();
+    let mut time_padding = TimeSpacing::default();
+    // transitionは順番に並べる親要素の中でのみ有効で、前の子要素との重なりは親要素が決める
+    let mut transition = None;
+    let mut margin = Edges::default();
+    let mut padding = Edges::default();
+    let mut gap = 0.0;
+    let mut object_fit = ObjectFit::Fill;
+    let mut object_position = None;
+    let mut direction = Direction::Row;
+    let mut alignment = Alignment::default();
+    let mut parent_alignment = Alignment::default();
+    // animationのキーフレームはエレメントのサイズなどが決まった後に解決するため、ルールごと保持する
+    let mut animation = None;
+    // transform, transform-originの%指定もエレメントのサイズが決まった後に解決する
+    let mut transform = None;
+    let mut transform_origin = None;
+
+    let parent_width = parent_size.map(|size| size.width);
+    let parent_height = parent_size.map(|size| size.height);
 
-    for rule in vss_scanner.scan() {
+    let rules = vss_scanner.scan().collect::<Vec<_>>();
+    let custom_properties = resolve_custom_properties(&path, &rules, parent_custom_properties)?;
+
+    for rule in rules {
+        if rule.property.starts_with("--") {
+            continue;
+        }
+        let rule = &Rule {
+            value: substitute_var(
+                &path.at(rule.span),
+                &rule.property,
+                &rule.value,
+                &mut |name| Ok(custom_properties.get(name).cloned()),
+            )?,
+            ..rule.clone()
+        };
         match rule.property.as_str() {
             "order" => {
-                order = match rule.value.as_str() {
-                    "sequence" => Order::Sequence,
-                    "parallel" => Order::Parallel,
-                    _ => todo!("エラーを実装"),
-                };
+                order = parse_rule_value(&path, rule)?;
             }
             "layer-mode" => {
-                layer_mode = match rule.value.as_str() {
-                    "single" => LayerMode::Single,
-                    "multi" => LayerMode::Multi,
-                    _ => todo!(),
-                };
+                layer_mode = parse_rule_value(&path, rule)?;
+            }
+            "object-fit" => {
+                object_fit = parse_rule_value(&path, rule)?;
+            }
+            "object-position" => {
+                object_position = Some(parse_rule_value(&path, rule)?);
+            }
+            "transform" => {
+                let functions =
+                    parse_transform(rule.value.as_str()).map_err(|_| invalid_value(&path, rule))?;
+                transform = Some((rule.clone(), functions));
+            }
+            "transform-origin" => {
+                let position: ObjectPosition = parse_rule_value(&path, rule)?;
+                transform_origin = Some((rule.clone(), position));
+            }
+            "direction" => {
+                direction = parse_rule_value(&path, rule)?;
+            }
+            "alignment" => {
+                alignment = parse_rule_value(&path, rule)?;
+            }
+            "parent-alignment" => {
+                parent_alignment = parse_rule_value(&path, rule)?;
+            }
+            "animation" => {
+                let shorthands =
+                    parse_animations(&rule.value).map_err(|_| invalid_value(&path, rule))?;
+                animation = Some((rule.clone(), shorthands));
             }
             "duration" => {
-                let value = rule.value.as_str();
-                let duration = value.parse().unwrap();
+                let duration = parse_rule_value(&path, rule)?;
                 match duration {
                     Duration::Percent(percent) => {
-                        let parent_duration =
-                            parent_duration.expect("no parent duration available");
-                        if parent_duration.is_infinite() {
-                            panic!("parent duration is infinite (fit)");
-                        }
+                        // 親のdurationが未指定またはfitの場合は基準が決まらない
+                        let parent_duration = parent_duration
+                            .filter(|duration| duration.is_finite())
+                            .ok_or_else(|| indefinite_percentage_base(&path, rule))?;
                         rule_target_duration = Some(parent_duration * (percent / 100.0));
                     }
                     Duration::Frame(frames) => {
@@ -282,15 +1183,58 @@ fn convert_tag_element<'a, I, A>(
                         rule_target_duration = Some(f64::INFINITY);
                     }
                 }
+                if rule_target_duration.is_some_and(|duration| duration < 0.0) {
+                    return Err(invalid_value(&path, rule));
+                }
+            }
+            "time-margin" => {
+                time_margin = resolve_time_spacing(&path, rule, fps, parent_duration, true)?;
+            }
+            "delay" | "start" => {
+                let duration = parse_rule_value(&path, rule)?;
+                let offset = resolve_time_offset(&path, rule, &duration, fps, parent_duration)?;
+                if offset < 0.0 {
+                    return Err(invalid_value(&path, rule));
+                }
+                if parent_order == Some(Order::Parallel) {
+                    delay = offset;
+                }
+            }
+            "time-padding" => {
+                time_padding = resolve_time_spacing(&path, rule, fps, parent_duration, false)?;
+            }
+            "transition" => {
+                if rule.value == "none" {
+                    transition = None;
+                    continue;
+                }
+                let shorthand: TransitionShorthand = parse_rule_value(&path, rule)?;
+                let duration =
+                    resolve_time_offset(&path, rule, &shorthand.duration, fps, parent_duration)?;
+                if duration < 0.0 {
+                    return Err(invalid_value(&path, rule));
+                }
+                if parent_order == Some(Order::Sequence) {
+                    transition = Some(Transition {
+                        effect: shorthand.effect,
+                        duration,
+                    });
+                }
             }
             "font-color" => {
-                let value = rule.value.as_str();
-                text_style.color = value.parse().unwrap();
+                text_style.color = parse_rule_value(&path, rule)?;
             }
             "background-color" => {
-                let value = rule.value.as_str();
-                let parsed_value = value.parse().unwrap();
-                background_color = Some(parsed_value);
+                background_color = Some(parse_rule_value(&path, rule)?);
+            }
+            "opacity" => {
+                let Opacity(value) = parse_rule_value(&path, rule)?;
+                opacity = value;
+            }
+            "filter" => {
+                let functions =
+                    parse_filter(rule.value.as_str()).map_err(|_| invalid_value(&path, rule))?;
+                image_effect_style = resolve_filter(&path, rule, functions, resolution)?;
             }
             "font-family" => {
                 let mut font_family = parse_font_family(rule.value.as_str());
@@ -299,93 +1243,113 @@ fn convert_tag_element<'a, I, A>(
                 text_style.font_family = font_family;
             }
             "audio-volume" => {
-                let value = rule.value.as_str();
-                let volume = value.parse().unwrap();
+                let volume = parse_rule_value(&path, rule)?;
                 match volume {
                     AudioVolume::Percent(percent) => {
                         audio_volume = percent / 100.0;
                     }
                 }
             }
+            "audio-filter" => {
+                let filters = parse_audio_filter(rule.value.as_str())
+                    .map_err(|_| invalid_value(&path, rule))?;
+                audio_effect_style = AudioEffectStyle { filters };
+            }
             "width" => {
-                let value = rule.value.as_str();
-                let length = value.parse().unwrap();
-                match length {
-                    Length::Pixel(px) => {
-                        rule_target_width = Some(px);
-                    }
-                    Length::ResolutionWidth(rw) => {
-                        rule_target_width = Some(resolution.width * (rw / 100.0));
-                    }
-                    Length::ResolutionHeight(rh) => {
-                        rule_target_width = Some(resolution.height * (rh / 100.0));
-                    }
-                    Length::Percent(percent) => {
-                        let parent_width = parent_size
-                            .expect("no parent size available for percentage width")
-                            .width;
-                        rule_target_width = Some(parent_width * (percent / 100.0) as f32);
-                    }
+                let length = parse_rule_value(&path, rule)?;
+                let width = resolve_length(&path, rule, &length, resolution, parent_width)?;
+                if width < 0.0 {
+                    return Err(invalid_value(&path, rule));
                 }
+                rule_target_width = Some(width);
             }
             "height" => {
-                let value = rule.value.as_str();
-                let length = value.parse().unwrap();
-                match length {
-                    Length::Pixel(px) => {
-                        rule_target_height = Some(px);
-                    }
-                    Length::ResolutionWidth(rw) => {
-                        rule_target_height = Some(resolution.width * (rw / 100.0));
-                    }
-                    Length::ResolutionHeight(rh) => {
-                        rule_target_height = Some(resolution.height * (rh / 100.0));
-                    }
-                    Length::Percent(percent) => {
-                        let parent_size = parent_size
-                            .expect("no parent size available for percentage height")
-                            .height;
-                        rule_target_height = Some(parent_size * (percent / 100.0) as f32);
-                    }
+                let length = parse_rule_value(&path, rule)?;
+                let height = resolve_length(&path, rule, &length, resolution, parent_height)?;
+                if height < 0.0 {
+                    return Err(invalid_value(&path, rule));
                 }
+                rule_target_height = Some(height);
+            }
+            "margin" => {
+                margin = resolve_edges(&path, rule, resolution, parent_width, true)?;
+            }
+            "margin-top" => {
+                margin.top = resolve_edge(&path, rule, resolution, parent_width, true)?;
+            }
+            "margin-right" => {
+                margin.right = resolve_edge(&path, rule, resolution, parent_width, true)?;
+            }
+            "margin-bottom" => {
+                margin.bottom = resolve_edge(&path, rule, resolution, parent_width, true)?;
+            }
+            "margin-left" => {
+                margin.left = resolve_edge(&path, rule, resolution, parent_width, true)?;
+            }
+            "padding" => {
+                padding = resolve_edges(&path, rule, resolution, parent_width, false)?;
+            }
+            "padding-top" => {
+                padding.top = resolve_edge(&path, rule, resolution, parent_width, false)?;
+            }
+            "padding-right" => {
+                padding.right = resolve_edge(&path, rule, resolution, parent_width, false)?;
+            }
+            "padding-bottom" => {
+                padding.bottom = resolve_edge(&path, rule, resolution, parent_width, false)?;
+            }
+            "padding-left" => {
+                padding.left = resolve_edge(&path, rule, resolution, parent_width, false)?;
+            }
+            "gap" => {
+                // gapの%指定もmargin, paddingと同様に親要素の幅を基準にする
+                gap = resolve_edge(&path, rule, resolution, parent_width, false)?;
+            }
+            "x" | "left" => {
+                let length = parse_rule_value(&path, rule)?;
+                rule_target_x = Some(resolve_length(
+                    &path,
+                    rule,
+                    &length,
+                    resolution,
+                    parent_width,
+                )?);
+            }
+            "y" | "top" => {
+                let length = parse_rule_value(&path, rule)?;
+                rule_target_y = Some(resolve_length(
+                    &path,
+                    rule,
+                    &length,
+                    resolution,
+                    parent_height,
+                )?);
+            }
+            "word-break" => {
+                text_style.word_break = parse_rule_value(&path, rule)?;
+            }
+            "line-break" => {
+                text_style.line_break = parse_rule_value(&path, rule)?;
             }
             "font-size" => {
-                let value = rule.value.as_str();
-                let length = value.parse().unwrap();
-                match length {
-                    Length::Pixel(px) => {
-                        text_style.font_size = px;
-                    }
-                    Length::ResolutionWidth(rw) => {
-                        text_style.font_size = resolution.width * (rw / 100.0);
-                    }
-                    Length::ResolutionHeight(rh) => {
-                        text_style.font_size = resolution.height * (rh / 100.0);
-                    }
-                    Length::Percent(percent) => {
-                        let parent_font_size = parent_text_style
-                            .as_ref()
-                            .expect("no parent text style available for percentage font-size")
-                            .font_size;
-                        text_style.font_size = parent_font_size * (percent / 100.0) as f32;
-                    }
-                }
+                let length = parse_rule_value(&path, rule)?;
+                // font-sizeの%指定は親要素のfont-sizeを基準にする
+                let parent_font_size = parent_text_style.as_ref().map(|style| style.font_size);
+                text_style.font_size =
+                    resolve_length(&path, rule, &length, resolution, parent_font_size)?;
             }
             _ => {}
         }
     }
 
-    // ここでinvalidな値が入っていたらpanicする
-    if let Some(width) = rule_target_width
-        && width < 0.0
-    {
-        panic!("width is expected to be a non-negative number")
-    }
-    if let Some(height) = rule_target_height
-        && height < 0.0
-    {
-        panic!("height is expected to be a non-negative number")
-    }
+    // widthが指定されている場合は、子孫のテキストをその幅で折り返す
+    // 指定されていない場合は、親要素から受け継いだ幅からmargin, paddingを除いた幅で折り返す
+    text_style.wrap_width = match rule_target_width {
+        Some(width) => Some(width),
+        None => text_style
+            .wrap_width
+            .map(|width| (width - margin.horizontal() - padding.horizontal()).max(0.0)),
+    };
 
     // 子要素に渡すdurationを決定（明示的に指定されている場合のみ）
     let duration_for_children = rule_target_duration.filter(|d| d.is_finite());
@@ -430,34 +1394,81 @@ fn convert_tag_element<'a, I, A>(
     let mut start_offset = 0.0;
     let mut children_offset_position = (0.0, 0.0);
     let mut has_infinite_child = false;
+    let mut has_arranged_child = false;
+    // 順番に並べる場合の直前の子要素のduration
+    let mut previous_duration = None;
+
+    // layer-modeがsingleかつparallelの場合は子要素をdirectionの方向に並べる
+    let arranges_children = layer_mode == LayerMode::Single && order == Order::Parallel;
+    // reverseの場合は後ろの子要素から順に並べ、最後に元の順番に戻す
+    let reverses_children = arranges_children && direction.is_reverse();
+    let indices: Vec<usize> = if reverses_children {
+        (0..children.len()).rev().collect()
+    } else {
+        (0..children.len()).collect()
+    };
 
-    for (i, element) in children.iter().enumerate() {
-        let child_object_data = vss_scanner.traverse(&children[..=i], |scanner| match element {
+    for i in indices {
+        let element = &children[i];
+        let child_object_data = vss_scanner.traverse(children, i, |scanner| match element {
             Element::Tag {
                 name,
                 attributes,
                 children,
+                span,
                 ..
             } => convert_tag_element(
                 scanner,
-                start_offset,
+                time_padding.start + start_offset,
                 children_offset_position,
                 name,
                 attributes,
                 children,
+                *span,
                 object_processor_provider,
                 fps,
                 resolution,
                 Some(text_style.clone()),
+                Some(order.clone()),
                 duration_for_children,
                 size_for_children,
+                &custom_properties,
             ),
-            // TODO: VSSプロパティとしてwidth, heightが追加された場合、ここでwidth, heightも必要になる
-            // 仮に横書きであれば水平方向に書いた描画範囲の幅がwidthを超える場合、改行して次の行に続ける必要がある
-            // そのため、折り返しの判定をするために、width(縦書きの場合はheight)が必要になる
-            // 現状は、textの描画サイズがそのままtxtタグの描画サイズになるため、width, heightは不要
-            Element::Text(text) => convert_element_text(text, &text_style),
-        });
+            // 折り返す幅はtext_styleのwrap_widthとして渡す
+            Element::Text(text, _) => Ok((
+                convert_element_text(text, &text_style),
+                TimeSpacing::default(),
+            )),
+        })?;
+        let (mut child_object_data, child_time_margin) = child_object_data;
+        // transitionの時間だけ前の子要素と重ねるため、開始時刻を早める
+        // 前の子要素がない場合や、前後の子要素より長い場合は重ねられる分だけにする
+        // 前の子要素も自身のtransitionで重なっている場合は、その残りの時間までしか重ねない
+        let overlap = match &mut child_object_data {
+            ObjectData::Element {
+                start_time,
+                duration,
+                transition,
+                ..
+            } => {
+                let overlap = match (transition.as_mut(), previous_duration) {
+                    (Some(transition), Some(previous_duration)) if order == Order::Sequence => {
+                        transition.duration =
+                            transition.duration.min(previous_duration).min(*duration);
+                        transition.duration
+                    }
+                    _ => 0.0,
+                };
+                if overlap > 0.0 {
+                    *start_time -= overlap;
+                } else {
+                    *transition = None;
+                }
+                previous_duration = Some(*duration - overlap);
+                overlap
+            }
+            ObjectData::Text(_) => 0.0,
+        };
         // 子要素によって親要素のstyleが変わる場合の処理
         match &child_object_data {
             &ObjectData::Element {
@@ -465,44 +1476,78 @@ fn convert_tag_element<'a, I, A>(
                 ref element_rect,
                 ..
             } => {
+                // 子要素が占める時間はtime-marginを含む
+                let occupied_duration = child_time_margin.start + duration + child_time_margin.end;
                 match order {
                     Order::Sequence => {
-                        start_offset += duration;
-                        target_duration += duration;
+                        start_offset += occupied_duration - overlap;
+                        target_duration += occupied_duration - overlap;
+                        // 負のtime-marginで重なった場合も子要素の終わりまでは含める
+                        target_duration = target_duration.max(start_offset - child_time_margin.end);
                     }
                     Order::Parallel => {
                         if duration.is_finite() {
-                            target_duration = target_duration.max(duration);
+                            target_duration = target_duration
+                                .max(occupied_duration)
+                                .max(child_time_margin.start + duration);
                         } else {
                             has_infinite_child = true;
                         }
                     }
                 }
-                if layer_mode == LayerMode::Single && order == Order::Parallel {
-                    // TODO: 並べる方向を決めるpropertyが来たらそれに従う
-                    children_offset_position.0 += element_rect.width;
-                    target_size.width += element_rect.width;
-                    target_size.height = target_size.height.max(element_rect.height);
+                // 子要素のmargin, paddingを含めたサイズで並べる
+                let (outer_width, outer_height) =
+                    (element_rect.outer_width(), element_rect.outer_height());
+                if arranges_children {
+                    // gapは子要素の間にだけ入る
+                    let leading_gap = if has_arranged_child { gap } else { 0.0 };
+                    match direction {
+                        Direction::Row | Direction::RowReverse => {
+                            children_offset_position.0 += outer_width + gap;
+                            target_size.width += leading_gap + outer_width;
+                            target_size.height = target_size.height.max(outer_height);
+                        }
+                        Direction::Column | Direction::ColumnReverse => {
+                            children_offset_position.1 += outer_height + gap;
+                            target_size.width = target_size.width.max(outer_width);
+                            target_size.height += leading_gap + outer_height;
+                        }
+                    }
+                    has_arranged_child = true;
                 } else {
-                    target_size.width = target_size.width.max(element_rect.width);
-                    target_size.height = target_size.height.max(element_rect.height);
+                    target_size.width = target_size.width.max(outer_width);
+                    target_size.height = target_size.height.max(outer_height);
                 }
             }
             ObjectData::Text(data) => {
                 // 親要素のprocessorを使ってテキストサイズを計算
                 if let ObjectType::Other(processor) = &object_type {
                     let rect_size = processor.calculate_text_size(data);
-                    // TODO: 並べる方向を決めるpropertyが来たらそれに従う
-                    target_size.width += rect_size.width;
-                    target_size.height = target_size.height.max(rect_size.height);
+                    match direction {
+                        Direction::Row | Direction::RowReverse => {
+                            target_size.width += rect_size.width;
+                            target_size.height = target_size.height.max(rect_size.height);
+                        }
+                        Direction::Column | Direction::ColumnReverse => {
+                            target_size.width = target_size.width.max(rect_size.width);
+                            target_size.height += rect_size.height;
+                        }
+                    }
                 }
             }
         }
         object_data_children.push(child_object_data);
     }
+    if reverses_children {
+        object_data_children.reverse();
+    }
     if has_infinite_child && target_duration == 0.0 {
         target_duration = f64::INFINITY
     }
+    // time-paddingはwrapの内側にだけ入り、それ以外のエレメントでは無視する
+    if let ObjectType::Wrap = object_type {
+        target_duration += time_padding.start + time_padding.end;
+    }
 
     // レイアウト用のサイズを計算
     // 一方だけ指定されていたらアス比を維持しつつ収まるように縮小
@@ -526,24 +1571,120 @@ fn convert_tag_element<'a, I, A>(
         (None, None) => (target_size.width, target_size.height),
     };
 
-    ObjectData::Element {
+    // 画像を持つエレメントでobject-fit, object-positionが指定されている場合のみ画像の矩形を計算する
+    let object_fit_rect = match object_type {
+        ObjectType::Other(_)
+            if image_size.width > 0.0
+                && image_size.height > 0.0
+                && (object_fit != ObjectFit::Fill || object_position.is_some()) =>
+        {
+            Some(calc_object_fit_rect(
+                object_fit,
+                &object_position.unwrap_or(ObjectPosition {
+                    x: Length::Percent(50.0),
+                    y: Length::Percent(50.0),
+                }),
+                image_size,
+                RectSize::new(final_layout_width, final_layout_height),
+                resolution,
+            ))
+        }
+        _ => None,
+    };
+
+    let duration = rule_target_duration.unwrap_or(target_duration);
+    let element_rect = ElementRect {
+        alignment,
+        parent_alignment,
+        x: rule_target_x.unwrap_or(offset_position.0),
+        y: rule_target_y.unwrap_or(offset_position.1),
+        width: final_layout_width,
+        height: final_layout_height,
+        margin,
+        padding,
+    };
+
+    let box_rendering_size = RectSize::new(
+        element_rect.padding.horizontal() + element_rect.width,
+        element_rect.padding.vertical() + element_rect.height,
+    );
+    let transform = match transform {
+        Some((rule, functions)) => {
+            resolve_transform(&path, &rule, &functions, resolution, box_rendering_size)?
+        }
+        None => Transform::IDENTITY,
+    };
+    let transform_origin = match transform_origin {
+        Some((rule, position)) => (
+            resolve_length(
+                &path,
+                &rule,
+                &position.x,
+                resolution,
+                Some(box_rendering_size.width),
+            )?,
+            resolve_length(
+                &path,
+                &rule,
+                &position.y,
+                resolution,
+                Some(box_rendering_size.height),
+            )?,
+        ),
+        None => (
+            box_rendering_size.width / 2.0,
+            box_rendering_size.height / 2.0,
+        ),
+    };
+
+    let animations = match animation {
+        Some((rule, shorthands)) => {
+            // キーフレームで指定されていないプロパティは、アニメーションしない場合の値から変化する
+            let base = AnimatedProperties {
+                font_color: Some(text_style.color),
+                ..AnimatedProperties::new(&element_rect, background_color, opacity)
+            };
+            let context = AnimationContext {
+                fps,
+                duration,
+                resolution,
+                parent_size,
+                custom_properties: &custom_properties,
+            };
+            shorthands
+                .iter()
+                .map(|shorthand| {
+                    resolve_animation(&path, &rule, shorthand, vss_scanner, &base, &context)
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        None => vec![],
+    };
+
+    let object_data = ObjectData::Element {
         object_type,
-        // time-margin, time-paddingとかが来たらここまでに計算する
-        start_time: offset_start_time,
-        duration: rule_target_duration.unwrap_or(target_duration),
+        start_time: offset_start_time + time_margin.start + delay,
+        duration,
         audio_volume,
+        audio_effect_style,
+        opacity,
+        transform,
+        transform_origin,
+        image_effect_style,
         background_color,
         attributes: attributes.clone(),
-        element_rect: ElementRect {
-            alignment: Default::default(),
-            parent_alignment: Default::default(),
-            x: offset_position.0,
-            y: offset_position.1,
-            width: final_layout_width,
-            height: final_layout_height,
-        },
+        element_rect,
+        object_fit_rect,
+        animations,
+        transition,
         children: object_data_children,
-    }
+    };
+    // 親要素からはdelayもtime-marginと同様に前に空ける時間として扱う
+    let time_margin = TimeSpacing {
+        start: time_margin.start + delay,
+        ..time_margin
+    };
+    Ok((object_data, time_margin))
 }
 
 fn convert_element_text<I, A>(text: &str, style: &TextStyleData) -> ObjectData<I, A> {
@@ -1,12 +1,32 @@
 mod common;
+mod convert_error;
+mod property_alignment;
+mod property_animation;
+mod property_audio_filter;
 mod property_audio_volume;
 mod property_background_color;
+mod property_custom_property;
+mod property_delay;
+mod property_direction;
 mod property_duration;
+mod property_filter;
 mod property_font_color;
 mod property_font_family;
 mod property_layer_mode;
+mod property_margin_padding;
+mod property_object_fit;
+mod property_opacity;
 mod property_order;
+mod property_position;
+mod property_text_wrap;
+mod property_time_margin_padding;
+mod property_transform;
+mod property_transition;
 mod property_width_height;
+mod selector_attribute;
 mod selector_child;
 mod selector_descendant;
+mod selector_pseudo_class;
+mod selector_sibling;
 mod selector_simple;
+mod selector_specificity;
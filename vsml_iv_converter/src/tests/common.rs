@@ -2,9 +2,9 @@ use core::f64;
 use mockall::mock;
 use std::collections::HashMap;
 use std::sync::Arc;
-use vsml_ast::vsml::Element;
-use vsml_ast::vss::{Rule, VSSSelector, VSSSelectorTree};
-use vsml_core::schemas::{ObjectProcessor, ProcessorInput, RectSize, TextData};
+use vsml_ast::vsml::{Content, Element, Meta, VSML};
+use vsml_ast::vss::{Rule, VSSItem, VSSKeyframes, VSSSelector, VSSSelectorTree};
+use vsml_core::schemas::{IVData, ObjectData, ObjectProcessor, ProcessorInput, RectSize, TextData};
 
 pub fn create_element(name: &str, class: Option<&str>, id: Option<&str>) -> Element {
     let mut attributes = HashMap::new();
@@ -18,6 +18,8 @@ pub fn create_element(name: &str, class: Option<&str>, id: Option<&str>) -> Elem
         name: name.to_string(),
         attributes,
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }
 }
 
@@ -25,9 +27,82 @@ pub fn create_rule(property: &str, value: &str) -> Rule {
     Rule {
         property: property.to_string(),
         value: value.to_string(),
+        important: false,
+        span: Default::default(),
     }
 }
 
+pub fn create_item(selectors: Vec<VSSSelector>, rules: Vec<Rule>) -> VSSItem {
+    VSSItem {
+        selectors: vec![VSSSelectorTree::Selectors(selectors)],
+        rules,
+        span: Default::default(),
+    }
+}
+
+/// 1920x1080, 60fps, 48000Hzの`<cont>`に`elements`を並べて変換する
+pub fn convert_elements(
+    vss_items: Vec<VSSItem>,
+    keyframes: Vec<VSSKeyframes>,
+    elements: Vec<Element>,
+    provider: &impl crate::ObjectProcessorProvider<(), ()>,
+) -> Result<IVData<(), ()>, crate::ConvertError> {
+    let vsml = VSML {
+        meta: Meta {
+            vss_items,
+            keyframes,
+            sources: vec![],
+        },
+        content: Content {
+            width: 1920,
+            height: 1080,
+            fps: Some(60),
+            sampling_rate: Some(48000),
+            elements,
+            span: Default::default(),
+        },
+    };
+    crate::convert(&vsml, provider)
+}
+
+/// `<prl class="group"><mock class="target"/></prl>` を変換する
+pub fn convert_group(
+    vss_items: Vec<VSSItem>,
+    keyframes: Vec<VSSKeyframes>,
+) -> Result<IVData<(), ()>, crate::ConvertError> {
+    let mut group = create_element("prl", Some("group"), None);
+    if let Element::Tag { children, .. } = &mut group {
+        children.push(create_element("mock", Some("target"), None));
+    }
+    convert_elements(
+        vss_items,
+        keyframes,
+        vec![group],
+        &TestObjectProcessorProvider::new(),
+    )
+}
+
+/// `.class { property: value; }` のVSSItemを作る
+pub fn class_rule(class: &str, property: &str, value: &str) -> VSSItem {
+    create_item(
+        vec![VSSSelector::Class(class.to_string())],
+        vec![create_rule(property, value)],
+    )
+}
+
+/// `.target { property: value; }` のVSSItemを作る
+pub fn target_rule(property: &str, value: &str) -> VSSItem {
+    class_rule("target", property, value)
+}
+
+/// 変換結果のルート(cont)のdurationを返す
+pub fn cont_duration(result: &IVData<(), ()>) -> f64 {
+    let ObjectData::Element { duration, .. } = result.object else {
+        panic!("Expected Element");
+    };
+    duration
+}
+
 pub fn create_descendant_selector(parent_class: &str, child_class: &str) -> VSSSelectorTree {
     VSSSelectorTree::Descendant(
         vec![VSSSelector::Class(parent_class.to_string())],
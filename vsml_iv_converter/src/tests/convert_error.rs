@@ -0,0 +1,119 @@
+use super::common::*;
+use crate::{ConvertError, ElementPath};
+use std::collections::HashMap;
+use std::sync::Arc;
+use vsml_ast::position::{Position, Span};
+use vsml_ast::vsml::{Content, Element, Meta, VSML};
+use vsml_ast::vss::{VSSItem, VSSSelector};
+use vsml_core::schemas::ObjectProcessor;
+
+fn create_vsml(elements: Vec<Element>, vss_items: Vec<VSSItem>) -> VSML {
+    VSML {
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
+        content: Content {
+            width: 1920,
+            height: 1080,
+            fps: Some(60),
+            sampling_rate: Some(48000),
+            elements,
+            span: Default::default(),
+        },
+    }
+}
+
+fn element_path(tags: &[&str]) -> ElementPath {
+    ElementPath {
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        span: Default::default(),
+        sources: Default::default(),
+    }
+}
+
+#[test]
+fn processor_not_found() {
+    let vsml = create_vsml(vec![create_element("unknown", None, None)], vec![]);
+
+    let provider: HashMap<String, Arc<dyn ObjectProcessor<(), ()>>> = HashMap::new();
+    let result = crate::convert(&vsml, &provider);
+
+    assert_eq!(
+        result.err(),
+        Some(ConvertError::ProcessorNotFound(element_path(&[
+            "cont", "unknown"
+        ])))
+    );
+}
+
+#[test]
+fn processor_not_found_reports_element_location() {
+    let mut vsml = create_vsml(
+        vec![Element::Tag {
+            name: "unknown".to_string(),
+            attributes: HashMap::new(),
+            children: vec![],
+            style: vec![],
+            span: Span::new(Position::new(200, 14, 5), Position::new(220, 14, 25)),
+        }],
+        vec![],
+    );
+    vsml.meta.sources = vec!["video.vsml".to_string()];
+
+    let provider: HashMap<String, Arc<dyn ObjectProcessor<(), ()>>> = HashMap::new();
+    let error = crate::convert(&vsml, &provider).err().unwrap();
+
+    assert_eq!(
+        error.to_string(),
+        "video.vsml:14:5 <unknown>: processor not found"
+    );
+}
+
+#[test]
+fn negative_width_is_invalid_value() {
+    let vsml = create_vsml(
+        vec![create_element("mock", None, None)],
+        vec![create_item(
+            vec![VSSSelector::Tag("mock".to_string())],
+            vec![create_rule("width", "-10px")],
+        )],
+    );
+
+    let provider = TestObjectProcessorProvider::new();
+    let result = crate::convert(&vsml, &provider);
+
+    assert_eq!(
+        result.err(),
+        Some(ConvertError::InvalidValue(
+            element_path(&["cont", "mock"]),
+            "width".to_string(),
+            "-10px".to_string(),
+        ))
+    );
+}
+
+#[test]
+fn percentage_duration_without_definite_parent() {
+    // 親のcontはdurationを持たないため、子要素の%指定は解決できない
+    let vsml = create_vsml(
+        vec![create_element("mock", None, None)],
+        vec![create_item(
+            vec![VSSSelector::Tag("mock".to_string())],
+            vec![create_rule("duration", "50%")],
+        )],
+    );
+
+    let provider = TestObjectProcessorProvider::new();
+    let result = crate::convert(&vsml, &provider);
+
+    assert_eq!(
+        result.err(),
+        Some(ConvertError::IndefinitePercentageBase(
+            element_path(&["cont", "mock"]),
+            "duration".to_string(),
+            "50%".to_string(),
+        ))
+    );
+}
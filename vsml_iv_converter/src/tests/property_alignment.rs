@@ -0,0 +1,86 @@
+use super::common::*;
+use crate::{ConvertError, ElementPath};
+use vsml_ast::vss::{Rule, VSSSelector};
+use vsml_core::Alignment;
+use vsml_core::schemas::{IVData, ObjectData};
+
+fn convert_with(rules: Vec<Rule>) -> Result<IVData<(), ()>, ConvertError> {
+    let elements = vec![create_element("mock", Some("target"), None)];
+    let vss_items = vec![create_item(
+        vec![VSSSelector::Class("target".to_string())],
+        rules,
+    )];
+
+    convert_elements(
+        vss_items,
+        vec![],
+        elements,
+        &TestObjectProcessorProvider::new(),
+    )
+}
+
+fn target_alignment(result: &IVData<(), ()>) -> (Alignment, Alignment) {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element { element_rect, .. } = &children[0] else {
+        panic!("Expected Element");
+    };
+    (element_rect.alignment, element_rect.parent_alignment)
+}
+
+#[test]
+fn alignment_property_default() {
+    let result = convert_with(vec![]).unwrap();
+
+    assert_eq!(
+        target_alignment(&result),
+        (Alignment::TopLeft, Alignment::TopLeft)
+    );
+}
+
+#[test]
+fn alignment_property_center() {
+    let result = convert_with(vec![
+        create_rule("alignment", "center"),
+        create_rule("parent-alignment", "center"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        target_alignment(&result),
+        (Alignment::Center, Alignment::Center)
+    );
+}
+
+#[test]
+fn alignment_property_bottom() {
+    let result = convert_with(vec![
+        create_rule("alignment", "bottom"),
+        create_rule("parent-alignment", "bottom"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        target_alignment(&result),
+        (Alignment::Bottom, Alignment::Bottom)
+    );
+}
+
+#[test]
+fn alignment_property_invalid_value() {
+    let result = convert_with(vec![create_rule("parent-alignment", "middle")]);
+
+    assert_eq!(
+        result.err(),
+        Some(ConvertError::InvalidValue(
+            ElementPath {
+                tags: vec!["cont".to_string(), "mock".to_string()],
+                span: Default::default(),
+                sources: Default::default(),
+            },
+            "parent-alignment".to_string(),
+            "middle".to_string(),
+        ))
+    );
+}
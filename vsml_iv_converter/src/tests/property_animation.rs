@@ -0,0 +1,227 @@
+use super::common::*;
+use crate::ConvertError;
+use std::collections::HashMap;
+use vsml_ast::vsml::Element;
+use vsml_ast::vss::{Rule, VSSItem, VSSKeyframe, VSSKeyframes, VSSSelector};
+use vsml_core::animation::{
+    Animation, AnimationDirection, AnimationFillMode, AnimationTrack, Keyframe, TimingFunction,
+};
+use vsml_core::schemas::{Color, IVData, ObjectData};
+
+fn create_mock() -> Element {
+    Element::Tag {
+        name: "mock".to_string(),
+        attributes: HashMap::from([("class".to_string(), "target".to_string())]),
+        children: vec![],
+        style: vec![],
+        span: Default::default(),
+    }
+}
+
+fn create_keyframes(name: &str, keyframes: Vec<(Vec<f64>, Vec<Rule>)>) -> VSSKeyframes {
+    VSSKeyframes {
+        name: name.to_string(),
+        keyframes: keyframes
+            .into_iter()
+            .map(|(offsets, rules)| VSSKeyframe {
+                offsets,
+                rules,
+                span: Default::default(),
+            })
+            .collect(),
+        span: Default::default(),
+    }
+}
+
+fn convert_with(
+    vss_items: Vec<VSSItem>,
+    keyframes: Vec<VSSKeyframes>,
+) -> Result<IVData<(), ()>, ConvertError> {
+    let provider = TestObjectProcessorProvider::with(
+        TestObjectProcessorProperty::default().with_duration(4.0),
+    );
+    convert_elements(vss_items, keyframes, vec![create_mock()], &provider)
+}
+
+fn target_item(rules: Vec<Rule>) -> VSSItem {
+    create_item(vec![VSSSelector::Class("target".to_string())], rules)
+}
+
+/// contの子要素のアニメーションを返す
+fn animations(result: &IVData<(), ()>) -> &[Animation] {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element { animations, .. } = &children[0] else {
+        panic!("Expected Element");
+    };
+    animations
+}
+
+fn keyframes<T: Copy>(values: &[(f64, T)]) -> Vec<Keyframe<T>> {
+    values
+        .iter()
+        .map(|&(offset, value)| Keyframe { offset, value })
+        .collect()
+}
+
+#[test]
+fn animation_property() {
+    let result = convert_with(
+        vec![target_item(vec![create_rule(
+            "animation",
+            "slide 50% linear 30f 2 alternate both",
+        )])],
+        vec![create_keyframes(
+            "slide",
+            vec![
+                (vec![0.0], vec![create_rule("x", "0")]),
+                (
+                    vec![50.0],
+                    vec![
+                        create_rule("x", "10rw"),
+                        create_rule("background-color", "red"),
+                    ],
+                ),
+            ],
+        )],
+    )
+    .unwrap();
+
+    assert_eq!(
+        animations(&result),
+        [Animation {
+            tracks: vec![
+                // 100%のキーフレームはアニメーションしない場合の値になる
+                AnimationTrack::X(keyframes(&[(0.0, 0.0), (0.5, 192.0), (1.0, 0.0)])),
+                // 背景色がない場合は透明から変化する
+                AnimationTrack::BackgroundColor(keyframes(&[
+                    (0.0, Color::from(255, 0, 0, 0)),
+                    (0.5, Color::from_rgb(255, 0, 0)),
+                    (1.0, Color::from(255, 0, 0, 0)),
+                ])),
+            ],
+            // %指定はエレメントのdurationを基準にする
+            duration: 2.0,
+            delay: 0.5,
+            iteration_count: 2.0,
+            timing_function: TimingFunction::Linear,
+            direction: AnimationDirection::Alternate,
+            fill_mode: AnimationFillMode::Both,
+        }]
+    );
+}
+
+#[test]
+fn animation_property_size_and_colors() {
+    let result = convert_with(
+        vec![
+            target_item(vec![
+                create_rule("animation", "grow 1s, blink 2s infinite"),
+                create_rule("font-color", "blue"),
+                create_rule("background-color", "black"),
+            ]),
+            create_item(
+                vec![VSSSelector::Tag("cont".to_string())],
+                vec![
+                    create_rule("width", "1000px"),
+                    create_rule("height", "500px"),
+                ],
+            ),
+        ],
+        vec![
+            create_keyframes(
+                "grow",
+                vec![(
+                    vec![100.0],
+                    vec![create_rule("width", "50%"), create_rule("height", "50%")],
+                )],
+            ),
+            create_keyframes(
+                "blink",
+                vec![(
+                    vec![50.0],
+                    vec![
+                        create_rule("font-color", "white"),
+                        create_rule("background-color", "white"),
+                    ],
+                )],
+            ),
+        ],
+    )
+    .unwrap();
+
+    let animations = animations(&result);
+    assert_eq!(animations.len(), 2);
+    assert_eq!(
+        animations[0].tracks,
+        [
+            AnimationTrack::Width(keyframes(&[(0.0, 100.0), (1.0, 500.0)])),
+            AnimationTrack::Height(keyframes(&[(0.0, 100.0), (1.0, 250.0)])),
+        ]
+    );
+    assert_eq!(animations[0].timing_function, TimingFunction::EASE);
+    let (black, white, blue) = (
+        Color::from_rgb(0, 0, 0),
+        Color::WHITE,
+        Color::from_rgb(0, 0, 255),
+    );
+    assert_eq!(
+        animations[1].tracks,
+        [
+            AnimationTrack::BackgroundColor(keyframes(&[(0.0, black), (0.5, white), (1.0, black)])),
+            AnimationTrack::FontColor(keyframes(&[(0.0, blue), (0.5, white), (1.0, blue)])),
+        ]
+    );
+    assert_eq!(animations[1].iteration_count, f64::INFINITY);
+}
+
+#[test]
+fn animation_property_later_keyframes_take_precedence() {
+    let result = convert_with(
+        vec![target_item(vec![create_rule("animation", "move 1s")])],
+        vec![
+            create_keyframes("move", vec![(vec![100.0], vec![create_rule("x", "10px")])]),
+            create_keyframes("move", vec![(vec![100.0], vec![create_rule("y", "20px")])]),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        animations(&result)[0].tracks,
+        [AnimationTrack::Y(keyframes(&[(0.0, 0.0), (1.0, 20.0)]))]
+    );
+}
+
+#[test]
+fn animation_property_errors() {
+    let result = convert_with(
+        vec![target_item(vec![create_rule("animation", "missing 1s")])],
+        vec![],
+    );
+    assert!(matches!(
+        result,
+        Err(ConvertError::UndefinedKeyframes(_, name)) if name == "missing"
+    ));
+
+    let result = convert_with(
+        vec![target_item(vec![create_rule("animation", "fade 1s")])],
+        vec![create_keyframes(
+            "fade",
+            vec![(vec![100.0], vec![create_rule("order", "parallel")])],
+        )],
+    );
+    assert!(matches!(
+        result,
+        Err(ConvertError::NotAnimatable(_, property)) if property == "order"
+    ));
+
+    let result = convert_with(
+        vec![target_item(vec![create_rule("animation", "fade")])],
+        vec![],
+    );
+    assert!(matches!(
+        result,
+        Err(ConvertError::InvalidValue(_, property, _)) if property == "animation"
+    ));
+}
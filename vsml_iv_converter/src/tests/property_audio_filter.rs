@@ -0,0 +1,122 @@
+use super::common::*;
+use crate::ConvertError;
+use vsml_core::schemas::{IVData, ObjectData};
+use vsml_core::{AudioEffectStyle, AudioFilter};
+
+/// prlとその子要素のaudio-filterを返す
+fn audio_effect_styles(result: &IVData<(), ()>) -> (&AudioEffectStyle, &AudioEffectStyle) {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element {
+        audio_effect_style: group_style,
+        children,
+        ..
+    } = &children[0]
+    else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element {
+        audio_effect_style, ..
+    } = &children[0]
+    else {
+        panic!("Expected Element");
+    };
+    (group_style, audio_effect_style)
+}
+
+#[test]
+fn audio_filter_property_default() {
+    let result = convert_group(vec![], vec![]).unwrap();
+
+    let (group_style, style) = audio_effect_styles(&result);
+    assert!(group_style.filters.is_empty());
+    assert!(style.filters.is_empty());
+}
+
+#[test]
+fn audio_filter_property_effects() {
+    let result = convert_group(
+        vec![class_rule(
+            "target",
+            "audio-filter",
+            "high-pass(100Hz) eq(3kHz, 4dB, 2) compressor(-20dB, 3) gain(150%)",
+        )],
+        vec![],
+    )
+    .unwrap();
+
+    assert_eq!(
+        audio_effect_styles(&result).1.filters,
+        [
+            AudioFilter::HighPass {
+                frequency: 100.0,
+                q: std::f32::consts::FRAC_1_SQRT_2,
+            },
+            AudioFilter::Equalizer {
+                frequency: 3000.0,
+                gain: 4.0,
+                q: 2.0,
+            },
+            AudioFilter::Compressor {
+                threshold: -20.0,
+                ratio: 3.0,
+                attack: 0.01,
+                release: 0.1,
+            },
+            AudioFilter::Gain(1.5),
+        ]
+    );
+}
+
+#[test]
+fn audio_filter_property_not_inherited() {
+    let result = convert_group(
+        vec![class_rule("group", "audio-filter", "reverb(2s)")],
+        vec![],
+    )
+    .unwrap();
+
+    // 子要素の音声はまとめてからエフェクトが適用される
+    let (group_style, style) = audio_effect_styles(&result);
+    assert_eq!(
+        group_style.filters,
+        [AudioFilter::Reverb {
+            decay: 2.0,
+            mix: 0.3,
+        }]
+    );
+    assert!(style.filters.is_empty());
+}
+
+#[test]
+fn audio_filter_property_none() {
+    let result = convert_group(
+        vec![
+            class_rule("target", "audio-filter", "low-pass(1kHz)"),
+            class_rule("target", "audio-filter", "none"),
+        ],
+        vec![],
+    )
+    .unwrap();
+
+    assert!(audio_effect_styles(&result).1.filters.is_empty());
+}
+
+#[test]
+fn audio_filter_property_invalid_value() {
+    for value in [
+        "low-pass(1000)",
+        "eq(1kHz)",
+        "compressor(-10dB, 0.5)",
+        "reverb(-1s)",
+        "chorus()",
+        "gain(2",
+    ] {
+        let result = convert_group(vec![class_rule("target", "audio-filter", value)], vec![]);
+        assert!(
+            matches!(result, Err(ConvertError::InvalidValue(_, ref property, _)) if property == "audio-filter"),
+            "{value}: {result:?}"
+        );
+    }
+}
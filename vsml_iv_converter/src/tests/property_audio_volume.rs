@@ -14,6 +14,8 @@ fn audio_volume_property_percent() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -21,21 +23,27 @@ fn audio_volume_property_percent() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("audio-volume", "50%")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -56,6 +64,8 @@ fn audio_volume_property_over_100_percent() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -63,21 +73,27 @@ fn audio_volume_property_over_100_percent() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("audio-volume", "150%")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -98,6 +114,8 @@ fn audio_volume_property_zero_percent() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -105,21 +123,27 @@ fn audio_volume_property_zero_percent() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("audio-volume", "0%")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
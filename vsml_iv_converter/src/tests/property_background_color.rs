@@ -14,6 +14,8 @@ fn background_color_property_hex() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -21,21 +23,27 @@ fn background_color_property_hex() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("background-color", "#ff0000")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -59,6 +67,8 @@ fn background_color_property_rgb() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -66,21 +76,27 @@ fn background_color_property_rgb() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("background-color", "rgb(0, 255, 0)")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -104,6 +120,8 @@ fn background_color_property_rgba() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -111,21 +129,27 @@ fn background_color_property_rgba() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("background-color", "rgba(100, 150, 200, 128)")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
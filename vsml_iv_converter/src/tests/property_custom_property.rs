@@ -0,0 +1,189 @@
+use super::common::*;
+use crate::{ConvertError, ElementPath};
+use vsml_ast::vsml::Element;
+use vsml_ast::vss::{VSSItem, VSSSelector};
+use vsml_core::schemas::{Color, IVData, ObjectData, TextData};
+
+/// `<cont><seq><mock class="target">Hello</mock></seq></cont>` を変換する
+fn convert_with(vss_items: Vec<VSSItem>) -> Result<IVData<(), ()>, ConvertError> {
+    let mut mock = create_element("mock", Some("target"), None);
+    if let Element::Tag { children, .. } = &mut mock {
+        children.push(Element::Text("Hello".to_string(), Default::default()));
+    }
+    let mut seq = create_element("seq", None, None);
+    if let Element::Tag { children, .. } = &mut seq {
+        children.push(mock);
+    }
+
+    let provider = TestObjectProcessorProvider::with(
+        TestObjectProcessorProperty::default().with_duration(1.0),
+    );
+    convert_elements(vss_items, vec![], vec![seq], &provider)
+}
+
+/// seq > mock の要素とそのテキストを取り出す
+fn target(result: &IVData<(), ()>) -> (&ObjectData<(), ()>, &[TextData]) {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element { children, .. } = &children[0] else {
+        panic!("Expected Element");
+    };
+    let target = &children[0];
+    let ObjectData::Element { children, .. } = target else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Text(text_data) = &children[0] else {
+        panic!("Expected Text");
+    };
+    (target, text_data)
+}
+
+#[test]
+fn custom_property_is_inherited_by_descendants() {
+    // cont { --accent: #ffcc00; }
+    // .target { font-color: var(--accent); }
+    let result = convert_with(vec![
+        create_item(
+            vec![VSSSelector::Tag("cont".to_string())],
+            vec![create_rule("--accent", "#ffcc00")],
+        ),
+        create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule("font-color", "var(--accent)")],
+        ),
+    ])
+    .unwrap();
+
+    let (_, text_data) = target(&result);
+    assert_eq!(text_data[0].style.color, Color::from_rgb(255, 204, 0));
+}
+
+#[test]
+fn custom_property_is_overridden_by_descendant() {
+    // cont { --accent: #ffcc00; }
+    // seq { --accent: #ff0000; }
+    // .target { font-color: var(--accent); }
+    let result = convert_with(vec![
+        create_item(
+            vec![VSSSelector::Tag("cont".to_string())],
+            vec![create_rule("--accent", "#ffcc00")],
+        ),
+        create_item(
+            vec![VSSSelector::Tag("seq".to_string())],
+            vec![create_rule("--accent", "#ff0000")],
+        ),
+        create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule("font-color", "var(--accent)")],
+        ),
+    ])
+    .unwrap();
+
+    let (_, text_data) = target(&result);
+    assert_eq!(text_data[0].style.color, Color::from_rgb(255, 0, 0));
+}
+
+#[test]
+fn custom_property_referencing_other_custom_property() {
+    // cont { --base: 2; --duration: var(--base)s; }
+    // .target { duration: var(--duration); }
+    let result = convert_with(vec![
+        create_item(
+            vec![VSSSelector::Tag("cont".to_string())],
+            vec![
+                create_rule("--duration", "var(--base)s"),
+                create_rule("--base", "2"),
+            ],
+        ),
+        create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule("duration", "var(--duration)")],
+        ),
+    ])
+    .unwrap();
+
+    let (ObjectData::Element { duration, .. }, _) = target(&result) else {
+        panic!("Expected Element");
+    };
+    assert_eq!(*duration, 2.0);
+}
+
+#[test]
+fn var_is_not_substituted_inside_identifier_or_string() {
+    // cont { --font: Arial; }
+    // .target { font-family: "Envar(x)", "var(--font)", var(--font); }
+    let result = convert_with(vec![
+        create_item(
+            vec![VSSSelector::Tag("cont".to_string())],
+            vec![create_rule("--font", "Arial")],
+        ),
+        create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule(
+                "font-family",
+                r#""Envar(x)", "var(--font)", var(--font)"#,
+            )],
+        ),
+    ])
+    .unwrap();
+
+    let (_, text_data) = target(&result);
+    assert_eq!(
+        text_data[0].style.font_family,
+        ["Envar(x)", "var(--font)", "Arial"]
+    );
+}
+
+#[test]
+fn var_uses_fallback_when_undefined() {
+    // .target { font-color: var(--missing, rgb(0, 255, 0)); }
+    let result = convert_with(vec![create_item(
+        vec![VSSSelector::Class("target".to_string())],
+        vec![create_rule("font-color", "var(--missing, rgb(0, 255, 0))")],
+    )])
+    .unwrap();
+
+    let (_, text_data) = target(&result);
+    assert_eq!(text_data[0].style.color, Color::from_rgb(0, 255, 0));
+}
+
+fn element_path(tags: &[&str]) -> ElementPath {
+    ElementPath {
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        span: Default::default(),
+        sources: Default::default(),
+    }
+}
+
+#[test]
+fn var_without_fallback_fails_when_undefined() {
+    let result = convert_with(vec![create_item(
+        vec![VSSSelector::Class("target".to_string())],
+        vec![create_rule("font-color", "var(--missing)")],
+    )]);
+
+    assert_eq!(
+        result.err(),
+        Some(ConvertError::UndefinedCustomProperty(
+            element_path(&["cont", "seq", "mock"]),
+            "--missing".to_string(),
+        ))
+    );
+}
+
+#[test]
+fn cyclic_custom_property_fails() {
+    let result = convert_with(vec![create_item(
+        vec![VSSSelector::Tag("cont".to_string())],
+        vec![
+            create_rule("--a", "var(--b)"),
+            create_rule("--b", "var(--a)"),
+        ],
+    )]);
+
+    assert!(matches!(
+        result.err(),
+        Some(ConvertError::CyclicCustomProperty(path, _)) if path == element_path(&["cont"])
+    ));
+}
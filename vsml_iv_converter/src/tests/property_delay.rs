@@ -0,0 +1,110 @@
+use super::common::*;
+use crate::{ConvertError, ElementPath};
+use vsml_ast::vss::{Rule, VSSSelector};
+use vsml_core::schemas::{IVData, ObjectData};
+
+/// `<cont><mock/><mock class="target"/></cont>` を変換する (mockは1秒)
+fn convert_with(
+    cont_rules: Vec<Rule>,
+    target_rules: Vec<Rule>,
+) -> Result<IVData<(), ()>, ConvertError> {
+    let vss_items = vec![
+        create_item(vec![VSSSelector::Tag("cont".to_string())], cont_rules),
+        create_item(vec![VSSSelector::Class("target".to_string())], target_rules),
+    ];
+    let elements = vec![
+        create_element("mock", None, None),
+        create_element("mock", Some("target"), None),
+    ];
+
+    let provider = TestObjectProcessorProvider::with(
+        TestObjectProcessorProperty::default().with_duration(1.0),
+    );
+    convert_elements(vss_items, vec![], elements, &provider)
+}
+
+/// contのdurationと子要素の開始時刻を返す
+fn timeline(result: &IVData<(), ()>) -> (f64, Vec<f64>) {
+    let ObjectData::Element {
+        duration, children, ..
+    } = &result.object
+    else {
+        panic!("Expected Element");
+    };
+    let start_times = children
+        .iter()
+        .map(|child| {
+            let ObjectData::Element { start_time, .. } = child else {
+                panic!("Expected Element");
+            };
+            *start_time
+        })
+        .collect();
+    (*duration, start_times)
+}
+
+#[test]
+fn delay_property_in_parallel() {
+    let result = convert_with(
+        vec![create_rule("order", "parallel")],
+        vec![create_rule("delay", "3s")],
+    )
+    .unwrap();
+
+    // 遅らせた分も親のdurationに含まれる
+    assert_eq!(timeline(&result), (4.0, vec![0.0, 3.0]));
+}
+
+#[test]
+fn start_property_is_alias_of_delay() {
+    let result = convert_with(
+        vec![create_rule("order", "parallel")],
+        vec![create_rule("start", "90f")],
+    )
+    .unwrap();
+
+    assert_eq!(timeline(&result), (2.5, vec![0.0, 1.5]));
+}
+
+#[test]
+fn delay_property_percent() {
+    let result = convert_with(
+        vec![
+            create_rule("order", "parallel"),
+            create_rule("duration", "10s"),
+        ],
+        vec![create_rule("delay", "50%")],
+    )
+    .unwrap();
+
+    assert_eq!(timeline(&result), (10.0, vec![0.0, 5.0]));
+}
+
+#[test]
+fn delay_property_is_ignored_in_sequence() {
+    let result = convert_with(vec![], vec![create_rule("delay", "0.5s")]).unwrap();
+
+    // 後の兄弟要素を押し下げないよう、順番に並べる親要素の中では開始時刻を変えない
+    assert_eq!(timeline(&result), (2.0, vec![0.0, 1.0]));
+}
+
+#[test]
+fn delay_property_negative_is_invalid_value() {
+    let result = convert_with(
+        vec![create_rule("order", "parallel")],
+        vec![create_rule("delay", "-1s")],
+    );
+
+    assert_eq!(
+        result.err(),
+        Some(ConvertError::InvalidValue(
+            ElementPath {
+                tags: vec!["cont".to_string(), "mock".to_string()],
+                span: Default::default(),
+                sources: Default::default(),
+            },
+            "delay".to_string(),
+            "-1s".to_string(),
+        ))
+    );
+}
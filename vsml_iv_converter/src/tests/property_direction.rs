@@ -0,0 +1,121 @@
+use super::common::*;
+use vsml_ast::vss::{VSSItem, VSSSelector, VSSSelectorTree};
+use vsml_core::schemas::{IVData, ObjectData};
+
+/// `layer` の中に3つのmockを置き、指定したdirectionで変換する
+fn convert_with_direction(direction: &str) -> IVData<(), ()> {
+    let elements = vec![
+        create_element("mock", Some("first"), None),
+        create_element("mock", Some("second"), None),
+        create_element("mock", Some("third"), None),
+    ];
+
+    let vss_items = vec![VSSItem {
+        selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Tag(
+            "cont".to_string(),
+        )])],
+        rules: vec![
+            create_rule("order", "parallel"),
+            create_rule("layer-mode", "single"),
+            create_rule("direction", direction),
+        ],
+        span: Default::default(),
+    }];
+
+    convert_elements(
+        vss_items,
+        vec![],
+        elements,
+        &TestObjectProcessorProvider::new(),
+    )
+    .unwrap()
+}
+
+/// contのサイズを返す
+fn size(result: &IVData<(), ()>) -> (f32, f32) {
+    let ObjectData::Element { element_rect, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    (element_rect.width, element_rect.height)
+}
+
+/// 子要素のclassと位置を文書順に返す
+fn positions(result: &IVData<(), ()>) -> Vec<(String, f32, f32)> {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    children
+        .iter()
+        .map(|child| {
+            let ObjectData::Element {
+                element_rect,
+                attributes,
+                ..
+            } = child
+            else {
+                panic!("Expected Element");
+            };
+            (attributes["class"].clone(), element_rect.x, element_rect.y)
+        })
+        .collect()
+}
+
+#[test]
+fn direction_property_row() {
+    let result = convert_with_direction("row");
+
+    assert_eq!(size(&result), (300.0, 100.0));
+    assert_eq!(
+        positions(&result),
+        [
+            ("first".to_string(), 0.0, 0.0),
+            ("second".to_string(), 100.0, 0.0),
+            ("third".to_string(), 200.0, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn direction_property_column() {
+    let result = convert_with_direction("column");
+
+    assert_eq!(size(&result), (100.0, 300.0));
+    assert_eq!(
+        positions(&result),
+        [
+            ("first".to_string(), 0.0, 0.0),
+            ("second".to_string(), 0.0, 100.0),
+            ("third".to_string(), 0.0, 200.0),
+        ]
+    );
+}
+
+#[test]
+fn direction_property_row_reverse() {
+    let result = convert_with_direction("row-reverse");
+
+    assert_eq!(size(&result), (300.0, 100.0));
+    assert_eq!(
+        positions(&result),
+        [
+            ("first".to_string(), 200.0, 0.0),
+            ("second".to_string(), 100.0, 0.0),
+            ("third".to_string(), 0.0, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn direction_property_column_reverse() {
+    let result = convert_with_direction("column-reverse");
+
+    assert_eq!(size(&result), (100.0, 300.0));
+    assert_eq!(
+        positions(&result),
+        [
+            ("first".to_string(), 0.0, 200.0),
+            ("second".to_string(), 0.0, 100.0),
+            ("third".to_string(), 0.0, 0.0),
+        ]
+    );
+}
@@ -14,6 +14,8 @@ fn duration_property_seconds() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -21,21 +23,27 @@ fn duration_property_seconds() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("duration", "5.5s")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -56,6 +64,8 @@ fn duration_property_frames() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -63,21 +73,27 @@ fn duration_property_frames() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("duration", "120f")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -105,7 +121,11 @@ fn duration_property_percent() {
                 attrs
             },
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         }],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![
@@ -114,28 +134,35 @@ fn duration_property_percent() {
                 "parent".to_string(),
             )])],
             rules: vec![create_rule("duration", "10s")],
+            span: Default::default(),
         },
         VSSItem {
             selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Class(
                 "child".to_string(),
             )])],
             rules: vec![create_rule("duration", "50%")],
+            span: Default::default(),
         },
     ];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -163,6 +190,8 @@ fn duration_property_fit() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -170,21 +199,27 @@ fn duration_property_fit() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("duration", "fit")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -0,0 +1,106 @@
+use super::common::*;
+use crate::ConvertError;
+use vsml_core::schemas::{Color, IVData, ObjectData};
+use vsml_core::{ImageEffectStyle, ImageFilter};
+
+/// prlとその子要素のfilterを返す
+fn image_effect_styles(result: &IVData<(), ()>) -> (&ImageEffectStyle, &ImageEffectStyle) {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element {
+        image_effect_style: group_style,
+        children,
+        ..
+    } = &children[0]
+    else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element {
+        image_effect_style, ..
+    } = &children[0]
+    else {
+        panic!("Expected Element");
+    };
+    (group_style, image_effect_style)
+}
+
+#[test]
+fn filter_property_default() {
+    let result = convert_group(vec![], vec![]).unwrap();
+
+    let (group_style, style) = image_effect_styles(&result);
+    assert!(group_style.filters.is_empty());
+    assert!(style.filters.is_empty());
+}
+
+#[test]
+fn filter_property_functions() {
+    let result = convert_group(
+        vec![class_rule(
+            "target",
+            "filter",
+            "blur(5rw) brightness(120%) hue-rotate(180deg) drop-shadow(4px 2rh 8px #ff0000)",
+        )],
+        vec![],
+    )
+    .unwrap();
+
+    assert_eq!(
+        image_effect_styles(&result).1.filters,
+        [
+            ImageFilter::Blur(96.0),
+            ImageFilter::Brightness(1.2),
+            ImageFilter::HueRotate(std::f32::consts::PI),
+            ImageFilter::DropShadow {
+                x: 4.0,
+                y: 21.6,
+                blur: 8.0,
+                color: Color::from_rgb(255, 0, 0),
+            },
+        ]
+    );
+}
+
+#[test]
+fn filter_property_not_inherited() {
+    let result =
+        convert_group(vec![class_rule("group", "filter", "grayscale(1)")], vec![]).unwrap();
+
+    // 子要素は親要素の画像ごとfilterが適用される
+    let (group_style, style) = image_effect_styles(&result);
+    assert_eq!(group_style.filters, [ImageFilter::Grayscale(1.0)]);
+    assert!(style.filters.is_empty());
+}
+
+#[test]
+fn filter_property_none() {
+    let result = convert_group(
+        vec![
+            class_rule("target", "filter", "sepia(1)"),
+            class_rule("target", "filter", "none"),
+        ],
+        vec![],
+    )
+    .unwrap();
+
+    assert!(image_effect_styles(&result).1.filters.is_empty());
+}
+
+#[test]
+fn filter_property_invalid_value() {
+    for value in [
+        "blur(10%)",
+        "blur(-1px)",
+        "contrast(-50%)",
+        "sharpen(1)",
+        "drop-shadow(1px)",
+        "invert(1",
+    ] {
+        let result = convert_group(vec![class_rule("target", "filter", value)], vec![]);
+        assert!(
+            matches!(result, Err(ConvertError::InvalidValue(_, ref property, _)) if property == "filter"),
+            "{value}: {result:?}"
+        );
+    }
+}
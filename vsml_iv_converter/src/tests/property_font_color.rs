@@ -13,7 +13,9 @@ fn font_color_property_hex() {
             attrs.insert("class".to_string(), "target".to_string());
             attrs
         },
-        children: vec![Element::Text("Hello".to_string())],
+        children: vec![Element::Text("Hello".to_string(), Default::default())],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -21,21 +23,27 @@ fn font_color_property_hex() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("font-color", "#ff0000")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -62,7 +70,9 @@ fn font_color_property_rgb() {
             attrs.insert("class".to_string(), "target".to_string());
             attrs
         },
-        children: vec![Element::Text("Hello".to_string())],
+        children: vec![Element::Text("Hello".to_string(), Default::default())],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -70,21 +80,27 @@ fn font_color_property_rgb() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("font-color", "rgb(0, 255, 0)")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -111,7 +127,9 @@ fn font_color_property_rgba() {
             attrs.insert("class".to_string(), "target".to_string());
             attrs
         },
-        children: vec![Element::Text("Hello".to_string())],
+        children: vec![Element::Text("Hello".to_string(), Default::default())],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -119,21 +137,27 @@ fn font_color_property_rgba() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("font-color", "rgba(100, 150, 200, 128)")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
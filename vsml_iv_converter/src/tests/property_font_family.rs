@@ -13,7 +13,9 @@ fn font_family_property_single() {
             attrs.insert("class".to_string(), "target".to_string());
             attrs
         },
-        children: vec![Element::Text("Hello".to_string())],
+        children: vec![Element::Text("Hello".to_string(), Default::default())],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -21,21 +23,27 @@ fn font_family_property_single() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("font-family", "Arial")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -62,7 +70,9 @@ fn font_family_property_multiple() {
             attrs.insert("class".to_string(), "target".to_string());
             attrs
         },
-        children: vec![Element::Text("Hello".to_string())],
+        children: vec![Element::Text("Hello".to_string(), Default::default())],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -70,21 +80,27 @@ fn font_family_property_multiple() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("font-family", "Arial, 'MS Gothic', sans-serif")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -120,8 +136,12 @@ fn font_family_property_nested_inheritance() {
                 attrs.insert("class".to_string(), "child".to_string());
                 attrs
             },
-            children: vec![Element::Text("Hello".to_string())],
+            children: vec![Element::Text("Hello".to_string(), Default::default())],
+            style: vec![],
+            span: Default::default(),
         }],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![
@@ -130,28 +150,35 @@ fn font_family_property_nested_inheritance() {
                 "parent".to_string(),
             )])],
             rules: vec![create_rule("font-family", "Georgia")],
+            span: Default::default(),
         },
         VSSItem {
             selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Class(
                 "child".to_string(),
             )])],
             rules: vec![create_rule("font-family", "Arial")],
+            span: Default::default(),
         },
     ];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
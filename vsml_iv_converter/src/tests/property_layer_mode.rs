@@ -11,11 +11,15 @@ fn layer_mode_property_single() {
             name: "mock".to_string(),
             attributes: HashMap::new(),
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         },
         Element::Tag {
             name: "mock".to_string(),
             attributes: HashMap::new(),
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         },
     ];
 
@@ -27,21 +31,27 @@ fn layer_mode_property_single() {
             create_rule("order", "parallel"),
             create_rule("layer-mode", "single"),
         ],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     if let ObjectData::Element {
         element_rect,
@@ -64,11 +74,15 @@ fn layer_mode_property_multi() {
             name: "mock".to_string(),
             attributes: HashMap::new(),
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         },
         Element::Tag {
             name: "mock".to_string(),
             attributes: HashMap::new(),
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         },
     ];
 
@@ -80,21 +94,27 @@ fn layer_mode_property_multi() {
             create_rule("order", "parallel"),
             create_rule("layer-mode", "multi"),
         ],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     if let ObjectData::Element {
         element_rect,
@@ -0,0 +1,187 @@
+use super::common::*;
+use crate::{ConvertError, ElementPath};
+use vsml_ast::vsml::Element;
+use vsml_ast::vss::{VSSItem, VSSSelector};
+use vsml_core::Edges;
+use vsml_core::schemas::{IVData, ObjectData};
+
+fn convert_with(
+    elements: Vec<Element>,
+    vss_items: Vec<VSSItem>,
+) -> Result<IVData<(), ()>, ConvertError> {
+    convert_elements(
+        vss_items,
+        vec![],
+        elements,
+        &TestObjectProcessorProvider::new(),
+    )
+}
+
+/// 横に並べるlayerとしてcontを変換する
+fn convert_layer(count: usize, vss_items: Vec<VSSItem>) -> IVData<(), ()> {
+    let elements = (0..count)
+        .map(|_| create_element("mock", Some("target"), None))
+        .collect();
+    let mut items = vec![create_item(
+        vec![VSSSelector::Tag("cont".to_string())],
+        vec![
+            create_rule("order", "parallel"),
+            create_rule("layer-mode", "single"),
+        ],
+    )];
+    items.extend(vss_items);
+    convert_with(elements, items).unwrap()
+}
+
+fn edges(top: f32, right: f32, bottom: f32, left: f32) -> Edges {
+    Edges {
+        top,
+        right,
+        bottom,
+        left,
+    }
+}
+
+fn cont_size(result: &IVData<(), ()>) -> (f32, f32) {
+    let ObjectData::Element { element_rect, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    (element_rect.width, element_rect.height)
+}
+
+fn children_x(result: &IVData<(), ()>) -> Vec<f32> {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    children
+        .iter()
+        .map(|child| {
+            let ObjectData::Element { element_rect, .. } = child else {
+                panic!("Expected Element");
+            };
+            element_rect.x
+        })
+        .collect()
+}
+
+fn first_child_edges(result: &IVData<(), ()>) -> (Edges, Edges) {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element { element_rect, .. } = &children[0] else {
+        panic!("Expected Element");
+    };
+    (element_rect.margin, element_rect.padding)
+}
+
+#[test]
+fn padding_property_shorthand() {
+    let result = convert_layer(
+        1,
+        vec![create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule("padding", "10px 20px")],
+        )],
+    );
+
+    let (_, padding) = first_child_edges(&result);
+    assert_eq!(padding, edges(10.0, 20.0, 10.0, 20.0));
+    // 親のサイズはpaddingを含めた子要素の大きさになる
+    assert_eq!(cont_size(&result), (140.0, 120.0));
+}
+
+#[test]
+fn margin_property_in_layer() {
+    let result = convert_layer(
+        2,
+        vec![create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule("margin", "10px")],
+        )],
+    );
+
+    let (margin, _) = first_child_edges(&result);
+    assert_eq!(margin, edges(10.0, 10.0, 10.0, 10.0));
+    assert_eq!(children_x(&result), [0.0, 120.0]);
+    assert_eq!(cont_size(&result), (240.0, 120.0));
+}
+
+#[test]
+fn margin_property_longhand_overrides_shorthand() {
+    let result = convert_layer(
+        1,
+        vec![create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![
+                create_rule("margin", "1px 2px 3px 4px"),
+                create_rule("margin-left", "0"),
+                create_rule("margin-top", "-5px"),
+            ],
+        )],
+    );
+
+    let (margin, _) = first_child_edges(&result);
+    assert_eq!(margin, edges(-5.0, 2.0, 3.0, 0.0));
+}
+
+#[test]
+fn margin_property_percent_uses_parent_width() {
+    let result = convert_with(
+        vec![create_element("mock", Some("target"), None)],
+        vec![
+            create_item(
+                vec![VSSSelector::Tag("cont".to_string())],
+                vec![
+                    create_rule("width", "1000px"),
+                    create_rule("height", "500px"),
+                ],
+            ),
+            create_item(
+                vec![VSSSelector::Class("target".to_string())],
+                vec![create_rule("margin", "1% 2%")],
+            ),
+        ],
+    )
+    .unwrap();
+
+    let (margin, _) = first_child_edges(&result);
+    assert_eq!(margin, edges(10.0, 20.0, 10.0, 20.0));
+}
+
+#[test]
+fn gap_property_in_layer() {
+    let result = convert_layer(
+        3,
+        vec![create_item(
+            vec![VSSSelector::Tag("cont".to_string())],
+            vec![create_rule("gap", "20px")],
+        )],
+    );
+
+    assert_eq!(children_x(&result), [0.0, 120.0, 240.0]);
+    assert_eq!(cont_size(&result), (340.0, 100.0));
+}
+
+#[test]
+fn padding_property_negative_is_invalid_value() {
+    let result = convert_with(
+        vec![create_element("mock", Some("target"), None)],
+        vec![create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule("padding", "10px -1px")],
+        )],
+    );
+
+    assert_eq!(
+        result.err(),
+        Some(ConvertError::InvalidValue(
+            ElementPath {
+                tags: vec!["cont".to_string(), "mock".to_string()],
+                span: Default::default(),
+                sources: Default::default(),
+            },
+            "padding".to_string(),
+            "10px -1px".to_string(),
+        ))
+    );
+}
@@ -0,0 +1,128 @@
+use super::common::*;
+use vsml_ast::vsml::{Content, Meta, VSML};
+use vsml_ast::vss::{Rule, VSSItem, VSSSelector, VSSSelectorTree};
+use vsml_core::ObjectFitRect;
+use vsml_core::schemas::ObjectData;
+
+/// 200x100の画像を持つmockを指定したスタイルで変換し、object_fit_rectを返す
+fn convert_object_fit_rect(rules: Vec<Rule>) -> Option<ObjectFitRect> {
+    let vss_items = vec![VSSItem {
+        selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Class(
+            "target".to_string(),
+        )])],
+        rules,
+        span: Default::default(),
+    }];
+
+    let vsml = VSML {
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
+        content: Content {
+            width: 1920,
+            height: 1080,
+            fps: Some(60),
+            sampling_rate: Some(48000),
+            elements: vec![create_element("mock", Some("target"), None)],
+            span: Default::default(),
+        },
+    };
+
+    let provider = TestObjectProcessorProvider::with(
+        TestObjectProcessorProperty::default().with_image_size(200.0, 100.0),
+    );
+    let result = crate::convert(&vsml, &provider).unwrap();
+
+    let ObjectData::Element { children, .. } = result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element {
+        object_fit_rect, ..
+    } = children[0]
+    else {
+        panic!("Expected Element");
+    };
+    object_fit_rect
+}
+
+fn square_box(size: &str, object_fit: &str) -> Vec<Rule> {
+    vec![
+        create_rule("width", size),
+        create_rule("height", size),
+        create_rule("object-fit", object_fit),
+    ]
+}
+
+fn rect(x: f32, y: f32, width: f32, height: f32) -> Option<ObjectFitRect> {
+    Some(ObjectFitRect {
+        x,
+        y,
+        width,
+        height,
+    })
+}
+
+#[test]
+fn object_fit_property_fill() {
+    // fillは矩形いっぱいに描画するだけなので計算しない
+    assert_eq!(convert_object_fit_rect(square_box("100px", "fill")), None);
+}
+
+#[test]
+fn object_fit_property_contain() {
+    assert_eq!(
+        convert_object_fit_rect(square_box("100px", "contain")),
+        rect(0.0, 25.0, 100.0, 50.0)
+    );
+}
+
+#[test]
+fn object_fit_property_cover() {
+    assert_eq!(
+        convert_object_fit_rect(square_box("100px", "cover")),
+        rect(-50.0, 0.0, 200.0, 100.0)
+    );
+}
+
+#[test]
+fn object_fit_property_none() {
+    assert_eq!(
+        convert_object_fit_rect(square_box("400px", "none")),
+        rect(100.0, 150.0, 200.0, 100.0)
+    );
+}
+
+#[test]
+fn object_fit_property_scale_down() {
+    // 縮小が必要な場合はcontainと同じ
+    assert_eq!(
+        convert_object_fit_rect(square_box("100px", "scale-down")),
+        rect(0.0, 25.0, 100.0, 50.0)
+    );
+    // 拡大が必要な場合はnoneと同じ
+    assert_eq!(
+        convert_object_fit_rect(square_box("400px", "scale-down")),
+        rect(100.0, 150.0, 200.0, 100.0)
+    );
+}
+
+#[test]
+fn object_position_property_keywords() {
+    let mut rules = square_box("100px", "contain");
+    rules.push(create_rule("object-position", "left top"));
+
+    assert_eq!(convert_object_fit_rect(rules), rect(0.0, 0.0, 100.0, 50.0));
+}
+
+#[test]
+fn object_position_property_length() {
+    let mut rules = square_box("100px", "contain");
+    rules.push(create_rule("object-position", "10px 100%"));
+
+    assert_eq!(
+        convert_object_fit_rect(rules),
+        rect(10.0, 50.0, 100.0, 50.0)
+    );
+}
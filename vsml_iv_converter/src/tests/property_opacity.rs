@@ -0,0 +1,114 @@
+use super::common::*;
+use crate::ConvertError;
+use vsml_ast::vss::{VSSKeyframe, VSSKeyframes};
+use vsml_core::animation::{AnimationTrack, Keyframe};
+use vsml_core::schemas::{IVData, ObjectData};
+
+/// prlとその子要素のopacityを返す
+fn opacities(result: &IVData<(), ()>) -> (f32, f32) {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element {
+        opacity: group_opacity,
+        children,
+        ..
+    } = &children[0]
+    else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element { opacity, .. } = &children[0] else {
+        panic!("Expected Element");
+    };
+    (*group_opacity, *opacity)
+}
+
+#[test]
+fn opacity_property_default() {
+    let result = convert_group(vec![], vec![]).unwrap();
+
+    assert_eq!(opacities(&result), (1.0, 1.0));
+}
+
+#[test]
+fn opacity_property_number_and_percent() {
+    let result = convert_group(
+        vec![
+            class_rule("group", "opacity", "0.5"),
+            class_rule("target", "opacity", "20%"),
+        ],
+        vec![],
+    )
+    .unwrap();
+
+    // 子要素には継承されず、描画時に親要素の画像ごと半透明になる
+    assert_eq!(opacities(&result), (0.5, 0.2));
+}
+
+#[test]
+fn opacity_property_clamped() {
+    let result = convert_group(
+        vec![
+            class_rule("group", "opacity", "2"),
+            class_rule("target", "opacity", "-1"),
+        ],
+        vec![],
+    )
+    .unwrap();
+
+    assert_eq!(opacities(&result), (1.0, 0.0));
+}
+
+#[test]
+fn opacity_property_invalid_value() {
+    let result = convert_group(vec![class_rule("target", "opacity", "half")], vec![]);
+
+    assert!(matches!(
+        result,
+        Err(ConvertError::InvalidValue(_, property, _)) if property == "opacity"
+    ));
+}
+
+#[test]
+fn opacity_property_animation() {
+    let result = convert_group(
+        vec![
+            class_rule("target", "opacity", "0.8"),
+            class_rule("target", "animation", "fade-in 1s"),
+        ],
+        vec![VSSKeyframes {
+            name: "fade-in".to_string(),
+            keyframes: vec![VSSKeyframe {
+                offsets: vec![0.0],
+                rules: vec![create_rule("opacity", "0")],
+                span: Default::default(),
+            }],
+            span: Default::default(),
+        }],
+    )
+    .unwrap();
+
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element { children, .. } = &children[0] else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element { animations, .. } = &children[0] else {
+        panic!("Expected Element");
+    };
+    // 100%のキーフレームはopacityプロパティの値になる
+    assert_eq!(
+        animations[0].tracks,
+        [AnimationTrack::Opacity(vec![
+            Keyframe {
+                offset: 0.0,
+                value: 0.0
+            },
+            Keyframe {
+                offset: 1.0,
+                value: 0.8
+            },
+        ])]
+    );
+}
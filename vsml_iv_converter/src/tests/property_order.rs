@@ -1,7 +1,9 @@
 use super::common::*;
+use crate::{ConvertError, ElementPath};
 use std::collections::HashMap;
+use vsml_ast::position::{Position, SourceId, Span};
 use vsml_ast::vsml::{Content, Element, Meta, VSML};
-use vsml_ast::vss::{VSSItem, VSSSelector, VSSSelectorTree};
+use vsml_ast::vss::{Rule, VSSItem, VSSSelector, VSSSelectorTree};
 use vsml_core::schemas::ObjectData;
 
 #[test]
@@ -15,6 +17,8 @@ fn order_property_sequence() {
                 attrs
             },
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         },
         Element::Tag {
             name: "mock".to_string(),
@@ -24,6 +28,8 @@ fn order_property_sequence() {
                 attrs
             },
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         },
     ];
 
@@ -32,23 +38,29 @@ fn order_property_sequence() {
             "cont".to_string(),
         )])],
         rules: vec![create_rule("order", "sequence")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::with(
         TestObjectProcessorProperty::default().with_duration(1.0),
     );
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element {
         children, duration, ..
@@ -79,6 +91,8 @@ fn order_property_parallel() {
                 attrs
             },
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         },
         Element::Tag {
             name: "mock".to_string(),
@@ -88,6 +102,8 @@ fn order_property_parallel() {
                 attrs
             },
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         },
     ];
 
@@ -96,23 +112,29 @@ fn order_property_parallel() {
             "cont".to_string(),
         )])],
         rules: vec![create_rule("order", "parallel")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::with(
         TestObjectProcessorProperty::default().with_duration(1.0),
     );
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element {
         children, duration, ..
@@ -131,3 +153,63 @@ fn order_property_parallel() {
     };
     assert_eq!(start_time, 0.0);
 }
+
+#[test]
+fn order_property_invalid_value_reports_rule_location() {
+    let elements = vec![Element::Tag {
+        name: "mock".to_string(),
+        attributes: HashMap::new(),
+        children: vec![],
+        style: vec![],
+        span: Span::new(Position::new(200, 14, 5), Position::new(220, 14, 25)),
+    }];
+
+    let vss_items = vec![VSSItem {
+        selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Tag(
+            "mock".to_string(),
+        )])],
+        rules: vec![Rule {
+            span: Span::new(Position::new(8, 2, 3), Position::new(23, 2, 18))
+                .with_source(SourceId(1)),
+            ..create_rule("order", "diagonal")
+        }],
+        span: Default::default(),
+    }];
+
+    let vsml = VSML {
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec!["video.vsml".to_string(), "style.vss".to_string()],
+        },
+        content: Content {
+            width: 1920,
+            height: 1080,
+            fps: Some(60),
+            sampling_rate: Some(48000),
+            elements,
+            span: Default::default(),
+        },
+    };
+
+    let provider = TestObjectProcessorProvider::new();
+    let error = crate::convert(&vsml, &provider).err().unwrap();
+
+    assert_eq!(
+        error,
+        ConvertError::InvalidValue(
+            ElementPath {
+                tags: vec!["cont".to_string(), "mock".to_string()],
+                span: Span::new(Position::new(8, 2, 3), Position::new(23, 2, 18))
+                    .with_source(SourceId(1)),
+                sources: ["video.vsml".to_string(), "style.vss".to_string()].into(),
+            },
+            "order".to_string(),
+            "diagonal".to_string(),
+        )
+    );
+    assert_eq!(
+        error.to_string(),
+        "style.vss:2:3 <mock>: invalid value \"diagonal\" for \"order\""
+    );
+}
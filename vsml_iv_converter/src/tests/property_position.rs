@@ -0,0 +1,129 @@
+use super::common::*;
+use std::collections::HashMap;
+use vsml_ast::vsml::Element;
+use vsml_ast::vss::{VSSItem, VSSSelector};
+use vsml_core::schemas::{IVData, ObjectData};
+
+fn create_mock() -> Element {
+    Element::Tag {
+        name: "mock".to_string(),
+        attributes: {
+            let mut attrs = HashMap::new();
+            attrs.insert("class".to_string(), "target".to_string());
+            attrs
+        },
+        children: vec![],
+        style: vec![],
+        span: Default::default(),
+    }
+}
+
+fn convert_with(elements: Vec<Element>, vss_items: Vec<VSSItem>) -> IVData<(), ()> {
+    convert_elements(
+        vss_items,
+        vec![],
+        elements,
+        &TestObjectProcessorProvider::new(),
+    )
+    .unwrap()
+}
+
+/// contの子要素の位置を返す
+fn positions(result: &IVData<(), ()>) -> Vec<(f32, f32)> {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    children
+        .iter()
+        .map(|child| {
+            let ObjectData::Element { element_rect, .. } = child else {
+                panic!("Expected Element");
+            };
+            (element_rect.x, element_rect.y)
+        })
+        .collect()
+}
+
+#[test]
+fn position_property_pixel() {
+    let result = convert_with(
+        vec![create_mock()],
+        vec![create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule("x", "120px"), create_rule("y", "-40px")],
+        )],
+    );
+
+    assert_eq!(positions(&result), [(120.0, -40.0)]);
+}
+
+#[test]
+fn position_property_resolution() {
+    let result = convert_with(
+        vec![create_mock()],
+        vec![create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule("x", "10rw"), create_rule("y", "10rh")],
+        )],
+    );
+
+    assert_eq!(positions(&result), [(192.0, 108.0)]);
+}
+
+#[test]
+fn position_property_percent() {
+    let result = convert_with(
+        vec![create_mock()],
+        vec![
+            create_item(
+                vec![VSSSelector::Tag("cont".to_string())],
+                vec![
+                    create_rule("width", "1000px"),
+                    create_rule("height", "500px"),
+                ],
+            ),
+            create_item(
+                vec![VSSSelector::Class("target".to_string())],
+                vec![create_rule("x", "50%"), create_rule("y", "10%")],
+            ),
+        ],
+    );
+
+    assert_eq!(positions(&result), [(500.0, 50.0)]);
+}
+
+#[test]
+fn position_property_left_top_alias() {
+    let result = convert_with(
+        vec![create_mock()],
+        vec![create_item(
+            vec![VSSSelector::Class("target".to_string())],
+            vec![create_rule("left", "30px"), create_rule("top", "60px")],
+        )],
+    );
+
+    assert_eq!(positions(&result), [(30.0, 60.0)]);
+}
+
+#[test]
+fn position_property_overrides_single_layer_offset() {
+    // layer内では左から順に並べられるが、xを指定した要素はその位置に置かれる
+    let result = convert_with(
+        vec![create_element("mock", None, None), create_mock()],
+        vec![
+            create_item(
+                vec![VSSSelector::Tag("cont".to_string())],
+                vec![
+                    create_rule("order", "parallel"),
+                    create_rule("layer-mode", "single"),
+                ],
+            ),
+            create_item(
+                vec![VSSSelector::Class("target".to_string())],
+                vec![create_rule("x", "10px")],
+            ),
+        ],
+    );
+
+    assert_eq!(positions(&result), [(0.0, 0.0), (10.0, 0.0)]);
+}
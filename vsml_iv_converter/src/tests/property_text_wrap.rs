@@ -0,0 +1,138 @@
+use super::common::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use vsml_ast::vsml::Element;
+use vsml_ast::vss::{VSSItem, VSSSelector};
+use vsml_core::schemas::{
+    IVData, LineBreak, ObjectData, ObjectProcessor, RectSize, TextStyleData, WordBreak,
+};
+
+/// 1行が幅600px・高さ40pxになるテキストを、wrap_widthに合わせて折り返したサイズを返すmock
+fn create_wrapping_provider() -> HashMap<String, Arc<dyn ObjectProcessor<(), ()>>> {
+    let mut mock = MockTestObjectProcessor::new();
+    mock.expect_default_duration().return_const(1.0);
+    mock.expect_default_image_size()
+        .return_const(RectSize::ZERO);
+    mock.expect_calculate_text_size()
+        .returning(|text_data| match text_data[0].style.wrap_width {
+            Some(width) if width < 600.0 => RectSize {
+                width,
+                height: 40.0 * (600.0 / width).ceil(),
+            },
+            _ => RectSize {
+                width: 600.0,
+                height: 40.0,
+            },
+        });
+    HashMap::from([(
+        "txt".to_string(),
+        Arc::new(mock) as Arc<dyn ObjectProcessor<(), ()>>,
+    )])
+}
+
+/// `<cont><txt>...</txt></cont>` を変換する
+fn convert_with(vss_items: Vec<VSSItem>) -> IVData<(), ()> {
+    let mut txt = create_element("txt", None, None);
+    if let Element::Tag { children, .. } = &mut txt {
+        children.push(Element::Text(
+            "吾輩は猫である。名前はまだ無い。".to_string(),
+            Default::default(),
+        ));
+    }
+
+    convert_elements(vss_items, vec![], vec![txt], &create_wrapping_provider()).unwrap()
+}
+
+/// txtのサイズとテキストのスタイルを返す
+fn txt_layout(result: &IVData<(), ()>) -> ((f32, f32), TextStyleData) {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Element {
+        element_rect,
+        children,
+        ..
+    } = &children[0]
+    else {
+        panic!("Expected Element");
+    };
+    let ObjectData::Text(text_data) = &children[0] else {
+        panic!("Expected Text");
+    };
+    (
+        (element_rect.width, element_rect.height),
+        text_data[0].style.clone(),
+    )
+}
+
+#[test]
+fn text_without_width_is_not_wrapped() {
+    let result = convert_with(vec![]);
+
+    let (size, style) = txt_layout(&result);
+    assert_eq!(style.wrap_width, None);
+    assert_eq!(size, (600.0, 40.0));
+}
+
+#[test]
+fn text_is_wrapped_to_width() {
+    let result = convert_with(vec![create_item(
+        vec![VSSSelector::Tag("txt".to_string())],
+        vec![create_rule("width", "200px")],
+    )]);
+
+    let (size, style) = txt_layout(&result);
+    assert_eq!(style.wrap_width, Some(200.0));
+    // 折り返した分の高さがレイアウトに反映される
+    assert_eq!(size, (200.0, 120.0));
+}
+
+#[test]
+fn text_is_wrapped_to_ancestor_width() {
+    let result = convert_with(vec![create_item(
+        vec![VSSSelector::Tag("cont".to_string())],
+        vec![create_rule("width", "300px")],
+    )]);
+
+    let (size, style) = txt_layout(&result);
+    assert_eq!(style.wrap_width, Some(300.0));
+    assert_eq!(size, (300.0, 80.0));
+}
+
+#[test]
+fn text_is_wrapped_to_ancestor_width_without_margin_and_padding() {
+    let result = convert_with(vec![
+        create_item(
+            vec![VSSSelector::Tag("cont".to_string())],
+            vec![create_rule("width", "400px")],
+        ),
+        create_item(
+            vec![VSSSelector::Tag("txt".to_string())],
+            vec![
+                create_rule("margin", "0 30px"),
+                create_rule("padding", "0 20px"),
+            ],
+        ),
+    ]);
+
+    // txtの中で使える幅は、contの幅からtxtの左右のmargin, paddingを除いた幅になる
+    let (size, style) = txt_layout(&result);
+    assert_eq!(style.wrap_width, Some(300.0));
+    assert_eq!(size, (300.0, 80.0));
+}
+
+#[test]
+fn word_break_and_line_break_properties() {
+    let result = convert_with(vec![create_item(
+        vec![VSSSelector::Tag("cont".to_string())],
+        vec![
+            create_rule("word-break", "break-all"),
+            create_rule("line-break", "strict"),
+        ],
+    )]);
+
+    // word-break, line-breakは子孫に継承される
+    let (_, style) = txt_layout(&result);
+    assert_eq!(style.word_break, WordBreak::BreakAll);
+    assert_eq!(style.line_break, LineBreak::Strict);
+}
@@ -0,0 +1,148 @@
+use super::common::*;
+use crate::{ConvertError, ElementPath};
+use vsml_ast::vss::{VSSItem, VSSSelector};
+use vsml_core::schemas::{IVData, ObjectData};
+
+/// 1秒のmockを `count` 個contに並べて変換する
+fn convert_with(count: usize, vss_items: Vec<VSSItem>) -> Result<IVData<(), ()>, ConvertError> {
+    let elements = (0..count)
+        .map(|_| create_element("mock", Some("target"), None))
+        .collect();
+
+    let provider = TestObjectProcessorProvider::with(
+        TestObjectProcessorProperty::default().with_duration(1.0),
+    );
+    convert_elements(vss_items, vec![], elements, &provider)
+}
+
+fn start_times(result: &IVData<(), ()>) -> Vec<f64> {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    children
+        .iter()
+        .map(|child| {
+            let ObjectData::Element { start_time, .. } = child else {
+                panic!("Expected Element");
+            };
+            *start_time
+        })
+        .collect()
+}
+
+#[test]
+fn time_margin_property_in_sequence() {
+    let result = convert_with(3, vec![target_rule("time-margin", "0.5s 0")]).unwrap();
+
+    assert_eq!(start_times(&result), [0.5, 2.0, 3.5]);
+    assert_eq!(cont_duration(&result), 4.5);
+}
+
+#[test]
+fn time_margin_property_frames() {
+    let result = convert_with(2, vec![target_rule("time-margin", "0 30f")]).unwrap();
+
+    assert_eq!(start_times(&result), [0.0, 1.5]);
+    assert_eq!(cont_duration(&result), 3.0);
+}
+
+#[test]
+fn time_margin_property_negative_overlaps() {
+    let result = convert_with(3, vec![target_rule("time-margin", "0 -0.5s")]).unwrap();
+
+    assert_eq!(start_times(&result), [0.0, 0.5, 1.0]);
+    // 最後の子要素の終わりまでは含まれる
+    assert_eq!(cont_duration(&result), 2.0);
+}
+
+#[test]
+fn time_margin_property_percent() {
+    let result = convert_with(
+        1,
+        vec![
+            create_item(
+                vec![VSSSelector::Tag("cont".to_string())],
+                vec![create_rule("duration", "10s")],
+            ),
+            target_rule("time-margin", "10%"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(start_times(&result), [1.0]);
+}
+
+#[test]
+fn time_margin_property_in_parallel() {
+    let result = convert_with(
+        2,
+        vec![
+            create_item(
+                vec![VSSSelector::Tag("cont".to_string())],
+                vec![create_rule("order", "parallel")],
+            ),
+            target_rule("time-margin", "1s 0.5s"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(start_times(&result), [1.0, 1.0]);
+    assert_eq!(cont_duration(&result), 2.5);
+}
+
+#[test]
+fn time_padding_property() {
+    let result = convert_with(
+        2,
+        vec![create_item(
+            vec![VSSSelector::Tag("cont".to_string())],
+            vec![create_rule("time-padding", "1s 2s")],
+        )],
+    )
+    .unwrap();
+
+    assert_eq!(start_times(&result), [1.0, 2.0]);
+    assert_eq!(cont_duration(&result), 5.0);
+}
+
+#[test]
+fn time_margin_property_fit_is_invalid_value() {
+    let result = convert_with(1, vec![target_rule("time-margin", "fit")]);
+
+    assert_eq!(
+        result.err(),
+        Some(ConvertError::InvalidValue(
+            ElementPath {
+                tags: vec!["cont".to_string(), "mock".to_string()],
+                span: Default::default(),
+                sources: Default::default(),
+            },
+            "time-margin".to_string(),
+            "fit".to_string(),
+        ))
+    );
+}
+
+#[test]
+fn time_padding_property_negative_is_invalid_value() {
+    let result = convert_with(
+        1,
+        vec![create_item(
+            vec![VSSSelector::Tag("cont".to_string())],
+            vec![create_rule("time-padding", "-1s")],
+        )],
+    );
+
+    assert_eq!(
+        result.err(),
+        Some(ConvertError::InvalidValue(
+            ElementPath {
+                tags: vec!["cont".to_string()],
+                span: Default::default(),
+                sources: Default::default(),
+            },
+            "time-padding".to_string(),
+            "-1s".to_string(),
+        ))
+    );
+}
@@ -0,0 +1,117 @@
+use super::common::*;
+use crate::ConvertError;
+use vsml_ast::vss::{VSSItem, VSSSelector};
+use vsml_core::Transform;
+use vsml_core::schemas::{IVData, ObjectData};
+
+/// 幅200px、高さ100px、padding 10pxのmockを変換する
+fn convert_with(vss_items: Vec<VSSItem>) -> Result<IVData<(), ()>, ConvertError> {
+    let mut items = vec![create_item(
+        vec![VSSSelector::Class("target".to_string())],
+        vec![
+            create_rule("width", "200px"),
+            create_rule("height", "100px"),
+            create_rule("padding", "10px"),
+        ],
+    )];
+    items.extend(vss_items);
+
+    convert_elements(
+        items,
+        vec![],
+        vec![create_element("mock", Some("target"), None)],
+        &TestObjectProcessorProvider::new(),
+    )
+}
+
+/// mockのtransformとtransform-originを返す
+fn transform(result: &IVData<(), ()>) -> (Transform, (f32, f32)) {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    let &ObjectData::Element {
+        transform,
+        transform_origin,
+        ..
+    } = &children[0]
+    else {
+        panic!("Expected Element");
+    };
+    (transform, transform_origin)
+}
+
+#[test]
+fn transform_property_default() {
+    let result = convert_with(vec![]).unwrap();
+
+    // transform-originの初期値は背景を描画する矩形の中心
+    assert_eq!(transform(&result), (Transform::IDENTITY, (110.0, 60.0)));
+}
+
+#[test]
+fn transform_property_functions() {
+    let result = convert_with(vec![target_rule(
+        "transform",
+        "translate(10px, 50%) scale(2, 3)",
+    )])
+    .unwrap();
+
+    // 左の変換関数ほど後に適用され、translateの%指定はpaddingを含むサイズが基準になる
+    assert_eq!(
+        transform(&result).0,
+        Transform::translate(10.0, 60.0) * Transform::scale(2.0, 3.0)
+    );
+
+    let result = convert_with(vec![target_rule(
+        "transform",
+        "rotate(90deg) skewX(45deg) translateY(10rh)",
+    )])
+    .unwrap();
+    assert_eq!(
+        transform(&result).0,
+        Transform::rotate(std::f32::consts::FRAC_PI_2)
+            * Transform::skew(std::f32::consts::FRAC_PI_4, 0.0)
+            * Transform::translate(0.0, 108.0)
+    );
+}
+
+#[test]
+fn transform_property_none() {
+    let result = convert_with(vec![
+        target_rule("transform", "scale(2)"),
+        target_rule("transform", "none"),
+    ])
+    .unwrap();
+
+    assert_eq!(transform(&result).0, Transform::IDENTITY);
+}
+
+#[test]
+fn transform_origin_property() {
+    let result = convert_with(vec![target_rule("transform-origin", "left top")]).unwrap();
+    assert_eq!(transform(&result).1, (0.0, 0.0));
+
+    let result = convert_with(vec![target_rule("transform-origin", "25% 10px")]).unwrap();
+    assert_eq!(transform(&result).1, (55.0, 10.0));
+
+    let result = convert_with(vec![target_rule("transform-origin", "bottom")]).unwrap();
+    assert_eq!(transform(&result).1, (110.0, 120.0));
+}
+
+#[test]
+fn transform_property_invalid_value() {
+    for (property, value) in [
+        ("transform", "rotate(10)"),
+        ("transform", "spin(10deg)"),
+        ("transform", "translate(1px, 2px, 3px)"),
+        ("transform", "scale(2"),
+        ("transform-origin", "top bottom"),
+        ("transform-origin", "1px 2px 3px"),
+    ] {
+        let result = convert_with(vec![target_rule(property, value)]);
+        assert!(
+            matches!(result, Err(ConvertError::InvalidValue(_, ref p, _)) if p == property),
+            "{property}: {value}: {result:?}"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use super::common::*;
+use crate::ConvertError;
+use vsml_ast::vss::{VSSItem, VSSSelector};
+use vsml_core::schemas::{
+    Color, IVData, ObjectData, Transition, TransitionDirection, TransitionEffect,
+};
+
+/// 1秒のmockを `count` 個contに並べて変換する
+fn convert_with(count: usize, vss_items: Vec<VSSItem>) -> Result<IVData<(), ()>, ConvertError> {
+    let elements = (0..count)
+        .map(|_| create_element("mock", Some("target"), None))
+        .collect();
+
+    let provider = TestObjectProcessorProvider::with(
+        TestObjectProcessorProperty::default().with_duration(1.0),
+    );
+    convert_elements(vss_items, vec![], elements, &provider)
+}
+
+/// contの子要素の開始時刻とtransitionを返す
+fn transitions(result: &IVData<(), ()>) -> Vec<(f64, Option<Transition>)> {
+    let ObjectData::Element { children, .. } = &result.object else {
+        panic!("Expected Element");
+    };
+    children
+        .iter()
+        .map(|child| {
+            let &ObjectData::Element {
+                start_time,
+                transition,
+                ..
+            } = child
+            else {
+                panic!("Expected Element");
+            };
+            (start_time, transition)
+        })
+        .collect()
+}
+
+#[test]
+fn transition_property_overlaps_previous_child() {
+    let result = convert_with(3, vec![target_rule("transition", "crossfade 0.5s")]).unwrap();
+
+    let crossfade = Some(Transition {
+        effect: TransitionEffect::Crossfade,
+        duration: 0.5,
+    });
+    // 最初の子要素は前の子要素がないので切り替えない
+    assert_eq!(
+        transitions(&result),
+        [(0.0, None), (0.5, crossfade), (1.0, crossfade)]
+    );
+    assert_eq!(cont_duration(&result), 2.0);
+}
+
+#[test]
+fn transition_property_effects() {
+    let result = convert_with(2, vec![target_rule("transition", "slide 30f up")]).unwrap();
+    assert_eq!(
+        transitions(&result)[1],
+        (
+            0.5,
+            Some(Transition {
+                effect: TransitionEffect::Slide(TransitionDirection::Up),
+                duration: 0.5,
+            })
+        )
+    );
+
+    let result = convert_with(2, vec![target_rule("transition", "wipe 0.25s")]).unwrap();
+    assert_eq!(
+        transitions(&result)[1].1,
+        Some(Transition {
+            effect: TransitionEffect::Wipe(TransitionDirection::Left),
+            duration: 0.25,
+        })
+    );
+
+    let result = convert_with(
+        2,
+        vec![target_rule("transition", "fade-through-color 0.5s white")],
+    )
+    .unwrap();
+    assert_eq!(
+        transitions(&result)[1].1,
+        Some(Transition {
+            effect: TransitionEffect::FadeThroughColor(Color::WHITE),
+            duration: 0.5,
+        })
+    );
+}
+
+#[test]
+fn transition_property_clamped_to_child_duration() {
+    let result = convert_with(2, vec![target_rule("transition", "crossfade 3s")]).unwrap();
+
+    // 前後の子要素より長くは重ならない
+    assert_eq!(transitions(&result)[1].0, 0.0);
+    assert_eq!(transitions(&result)[1].1.unwrap().duration, 1.0);
+    assert_eq!(cont_duration(&result), 1.0);
+}
+
+#[test]
+fn transition_property_clamped_to_remaining_previous_child() {
+    let result = convert_with(3, vec![target_rule("transition", "crossfade 1s")]).unwrap();
+
+    // 2つ目の子要素は最初の子要素と完全に重なるので、3つ目の子要素は2つ目の子要素とは重ならない
+    let crossfade = Some(Transition {
+        effect: TransitionEffect::Crossfade,
+        duration: 1.0,
+    });
+    assert_eq!(
+        transitions(&result),
+        [(0.0, None), (0.0, crossfade), (1.0, None)]
+    );
+    assert_eq!(cont_duration(&result), 2.0);
+}
+
+#[test]
+fn transition_property_percent() {
+    let result = convert_with(
+        2,
+        vec![
+            create_item(
+                vec![VSSSelector::Tag("cont".to_string())],
+                vec![create_rule("duration", "10s")],
+            ),
+            target_rule("transition", "crossfade 5%"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(transitions(&result)[1].0, 0.5);
+}
+
+#[test]
+fn transition_property_ignored_in_parallel() {
+    let result = convert_with(
+        2,
+        vec![
+            create_item(
+                vec![VSSSelector::Tag("cont".to_string())],
+                vec![create_rule("order", "parallel")],
+            ),
+            target_rule("transition", "crossfade 0.5s"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(transitions(&result), [(0.0, None), (0.0, None)]);
+    assert_eq!(cont_duration(&result), 1.0);
+}
+
+#[test]
+fn transition_property_none() {
+    let result = convert_with(
+        2,
+        vec![
+            target_rule("transition", "crossfade 0.5s"),
+            target_rule("transition", "none"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(transitions(&result), [(0.0, None), (1.0, None)]);
+}
+
+#[test]
+fn transition_property_invalid_value() {
+    for value in ["crossfade", "dissolve 1s", "crossfade -1s", "crossfade fit"] {
+        let result = convert_with(2, vec![target_rule("transition", value)]);
+        assert!(
+            matches!(result, Err(ConvertError::InvalidValue(_, ref property, _)) if property == "transition"),
+            "{value}: {result:?}"
+        );
+    }
+}
@@ -15,6 +15,8 @@ fn width_property_pixel() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -22,21 +24,27 @@ fn width_property_pixel() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("width", "500px")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -60,6 +68,8 @@ fn width_property_resolution_width() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -67,21 +77,27 @@ fn width_property_resolution_width() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("width", "50rw")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -105,6 +121,8 @@ fn width_property_resolution_height() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -112,21 +130,27 @@ fn width_property_resolution_height() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("width", "50rh")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -157,7 +181,11 @@ fn width_property_percent() {
                 attrs
             },
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         }],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![
@@ -166,28 +194,35 @@ fn width_property_percent() {
                 "parent".to_string(),
             )])],
             rules: vec![create_rule("width", "800px")],
+            span: Default::default(),
         },
         VSSItem {
             selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Class(
                 "child".to_string(),
             )])],
             rules: vec![create_rule("width", "50%")],
+            span: Default::default(),
         },
     ];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -219,6 +254,8 @@ fn height_property_pixel() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -226,21 +263,27 @@ fn height_property_pixel() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("height", "300px")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -264,6 +307,8 @@ fn height_property_resolution_width() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -271,21 +316,27 @@ fn height_property_resolution_width() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("height", "25rw")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -309,6 +360,8 @@ fn height_property_resolution_height() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -316,21 +369,27 @@ fn height_property_resolution_height() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("height", "75rh")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -361,7 +420,11 @@ fn height_property_percent() {
                 attrs
             },
             children: vec![],
+            style: vec![],
+            span: Default::default(),
         }],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![
@@ -370,28 +433,35 @@ fn height_property_percent() {
                 "parent".to_string(),
             )])],
             rules: vec![create_rule("height", "600px")],
+            span: Default::default(),
         },
         VSSItem {
             selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Class(
                 "child".to_string(),
             )])],
             rules: vec![create_rule("height", "50%")],
+            span: Default::default(),
         },
     ];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -423,6 +493,8 @@ fn width_and_height_property_both_pixel() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -433,21 +505,27 @@ fn width_and_height_property_both_pixel() {
             create_rule("width", "640px"),
             create_rule("height", "480px"),
         ],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -472,6 +550,8 @@ fn width_property_with_aspect_ratio() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -479,21 +559,27 @@ fn width_property_with_aspect_ratio() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("width", "50px")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -520,6 +606,8 @@ fn height_property_with_aspect_ratio() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -527,21 +615,27 @@ fn height_property_with_aspect_ratio() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("height", "200px")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -568,6 +662,8 @@ fn width_and_height_property_mixed_units() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -575,21 +671,27 @@ fn width_and_height_property_mixed_units() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("width", "50rw"), create_rule("height", "50rh")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::new();
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -615,6 +717,8 @@ fn width_property_without_default_size() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -622,23 +726,29 @@ fn width_property_without_default_size() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("width", "500px")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::with(
         TestObjectProcessorProperty::default().without_default_size(),
     );
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -663,6 +773,8 @@ fn height_property_without_default_size() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -670,23 +782,29 @@ fn height_property_without_default_size() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("height", "300px")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::with(
         TestObjectProcessorProperty::default().without_default_size(),
     );
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -711,6 +829,8 @@ fn width_and_height_property_without_default_size() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -721,23 +841,29 @@ fn width_and_height_property_without_default_size() {
             create_rule("width", "640px"),
             create_rule("height", "480px"),
         ],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
     let provider = TestObjectProcessorProvider::with(
         TestObjectProcessorProperty::default().without_default_size(),
     );
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -762,6 +888,8 @@ fn width_property_with_custom_default_size() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -769,16 +897,22 @@ fn width_property_with_custom_default_size() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("width", "80px")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
@@ -786,7 +920,7 @@ fn width_property_with_custom_default_size() {
     let provider = TestObjectProcessorProvider::with(
         TestObjectProcessorProperty::default().with_image_size(200.0, 150.0),
     );
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
@@ -812,6 +946,8 @@ fn height_property_with_custom_default_size() {
             attrs
         },
         children: vec![],
+        style: vec![],
+        span: Default::default(),
     }];
 
     let vss_items = vec![VSSItem {
@@ -819,16 +955,22 @@ fn height_property_with_custom_default_size() {
             "target".to_string(),
         )])],
         rules: vec![create_rule("height", "75px")],
+        span: Default::default(),
     }];
 
     let vsml = VSML {
-        meta: Meta { vss_items },
+        meta: Meta {
+            vss_items,
+            keyframes: vec![],
+            sources: vec![],
+        },
         content: Content {
             width: 1920,
             height: 1080,
             fps: Some(60),
             sampling_rate: Some(48000),
             elements,
+            span: Default::default(),
         },
     };
 
@@ -836,7 +978,7 @@ fn height_property_with_custom_default_size() {
     let provider = TestObjectProcessorProvider::with(
         TestObjectProcessorProperty::default().with_image_size(200.0, 150.0),
     );
-    let result = crate::convert(&vsml, &provider);
+    let result = crate::convert(&vsml, &provider).unwrap();
 
     let ObjectData::Element { children, .. } = result.object else {
         panic!("Expected Element");
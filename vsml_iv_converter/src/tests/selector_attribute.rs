@@ -0,0 +1,135 @@
+use super::common::*;
+use crate::{TraverseEntry, VssScanner};
+use std::collections::HashMap;
+use vsml_ast::vsml::Element;
+use vsml_ast::vss::{VSSItem, VSSSelector, VSSSelectorAttributeValue, VSSSelectorTree};
+
+fn create_element_with_attribute(name: &str, attribute: &str, value: &str) -> Element {
+    Element::Tag {
+        name: name.to_string(),
+        attributes: HashMap::from([(attribute.to_string(), value.to_string())]),
+        children: vec![],
+        style: vec![],
+        span: Default::default(),
+    }
+}
+
+fn count_matches(element: Element, name: &str, value: VSSSelectorAttributeValue) -> usize {
+    let elements = vec![element];
+    let vss_items = vec![VSSItem {
+        selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Attribute(
+            name.to_string(),
+            value,
+        )])],
+        rules: vec![create_rule("color", "red")],
+        span: Default::default(),
+    }];
+
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![TraverseEntry::new(&elements, 0)];
+    scanner.scan().count()
+}
+
+#[test]
+fn matches_attribute_presence() {
+    let element = create_element_with_attribute("vid", "src", "video.mp4");
+    assert_eq!(
+        count_matches(element.clone(), "src", VSSSelectorAttributeValue::None),
+        1
+    );
+    assert_eq!(
+        count_matches(element, "alt", VSSSelectorAttributeValue::None),
+        0
+    );
+}
+
+#[test]
+fn matches_attribute_equal() {
+    let element = create_element_with_attribute("vid", "src", "video.mp4");
+    assert_eq!(
+        count_matches(
+            element.clone(),
+            "src",
+            VSSSelectorAttributeValue::Equal("video.mp4".to_string())
+        ),
+        1
+    );
+    assert_eq!(
+        count_matches(
+            element,
+            "src",
+            VSSSelectorAttributeValue::Equal("video".to_string())
+        ),
+        0
+    );
+}
+
+#[test]
+fn matches_attribute_contain() {
+    let element = create_element_with_attribute("txt", "class", "title  main");
+    assert_eq!(
+        count_matches(
+            element.clone(),
+            "class",
+            VSSSelectorAttributeValue::Contain("main".to_string())
+        ),
+        1
+    );
+    assert_eq!(
+        count_matches(
+            element,
+            "class",
+            VSSSelectorAttributeValue::Contain("mai".to_string())
+        ),
+        0
+    );
+}
+
+#[test]
+fn matches_attribute_start_end_include() {
+    let element = create_element_with_attribute("vid", "src", "video.mp4");
+    assert_eq!(
+        count_matches(
+            element.clone(),
+            "src",
+            VSSSelectorAttributeValue::StartWith("video".to_string())
+        ),
+        1
+    );
+    assert_eq!(
+        count_matches(
+            element.clone(),
+            "src",
+            VSSSelectorAttributeValue::EndWith(".mp4".to_string())
+        ),
+        1
+    );
+    assert_eq!(
+        count_matches(
+            element.clone(),
+            "src",
+            VSSSelectorAttributeValue::Include("deo.m".to_string())
+        ),
+        1
+    );
+    assert_eq!(
+        count_matches(
+            element.clone(),
+            "src",
+            VSSSelectorAttributeValue::EndWith(".mov".to_string())
+        ),
+        0
+    );
+}
+
+#[test]
+fn empty_value_never_matches_substring_operators() {
+    let element = create_element_with_attribute("vid", "src", "video.mp4");
+    for value in [
+        VSSSelectorAttributeValue::StartWith(String::new()),
+        VSSSelectorAttributeValue::EndWith(String::new()),
+        VSSSelectorAttributeValue::Include(String::new()),
+    ] {
+        assert_eq!(count_matches(element.clone(), "src", value), 0);
+    }
+}
@@ -1,5 +1,5 @@
 use super::common::*;
-use crate::VssScanner;
+use crate::{TraverseEntry, VssScanner};
 use vsml_ast::vss::{VSSItem, VSSSelector, VSSSelectorTree};
 
 #[test]
@@ -21,10 +21,14 @@ fn matches_direct_child() {
             )])),
         )],
         rules: vec![create_rule("color", "red")],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
-    scanner.traverse_stack = vec![&parent_elements, &child_elements];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![
+        TraverseEntry::new(&parent_elements, 0),
+        TraverseEntry::new(&child_elements, 0),
+    ];
 
     assert_eq!(scanner.scan().count(), 1);
 }
@@ -52,10 +56,15 @@ fn no_match_for_indirect_child() {
             )])),
         )],
         rules: vec![create_rule("color", "red")],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
-    scanner.traverse_stack = vec![&root_elements, &middle_elements, &leaf_elements];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![
+        TraverseEntry::new(&root_elements, 0),
+        TraverseEntry::new(&middle_elements, 0),
+        TraverseEntry::new(&leaf_elements, 0),
+    ];
 
     assert_eq!(scanner.scan().count(), 0);
 }
@@ -86,10 +95,15 @@ fn matches_nested_child_selectors() {
             )),
         )],
         rules: vec![create_rule("color", "red")],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
-    scanner.traverse_stack = vec![&root_elements, &middle_elements, &leaf_elements];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![
+        TraverseEntry::new(&root_elements, 0),
+        TraverseEntry::new(&middle_elements, 0),
+        TraverseEntry::new(&leaf_elements, 0),
+    ];
 
     assert_eq!(scanner.scan().count(), 1);
 }
@@ -120,10 +134,15 @@ fn no_match_for_wrong_child_order() {
             )),
         )],
         rules: vec![create_rule("color", "red")],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
-    scanner.traverse_stack = vec![&root_elements, &middle_elements, &leaf_elements];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![
+        TraverseEntry::new(&root_elements, 0),
+        TraverseEntry::new(&middle_elements, 0),
+        TraverseEntry::new(&leaf_elements, 0),
+    ];
 
     assert_eq!(scanner.scan().count(), 0);
 }
@@ -158,14 +177,15 @@ fn mixed_child_and_descendant() {
             )),
         )],
         rules: vec![create_rule("color", "red")],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
     scanner.traverse_stack = vec![
-        &root_elements,
-        &middle_elements,
-        &other_elements,
-        &child_elements,
+        TraverseEntry::new(&root_elements, 0),
+        TraverseEntry::new(&middle_elements, 0),
+        TraverseEntry::new(&other_elements, 0),
+        TraverseEntry::new(&child_elements, 0),
     ];
 
     assert_eq!(scanner.scan().count(), 1);
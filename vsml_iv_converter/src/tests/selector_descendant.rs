@@ -1,5 +1,5 @@
 use super::common::*;
-use crate::VssScanner;
+use crate::{TraverseEntry, VssScanner};
 use vsml_ast::vss::{VSSItem, VSSSelector, VSSSelectorTree};
 
 #[test]
@@ -13,10 +13,14 @@ fn matches_simple_descendant_selector() {
     let vss_items = vec![VSSItem {
         selectors: vec![create_descendant_selector("parent", "child")],
         rules: vec![rule],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
-    scanner.traverse_stack = vec![&elements, &child_elements];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![
+        TraverseEntry::new(&elements, 0),
+        TraverseEntry::new(&child_elements, 0),
+    ];
 
     assert_eq!(scanner.scan().count(), 1);
 }
@@ -32,10 +36,14 @@ fn no_match_for_non_existent_class() {
     let vss_items = vec![VSSItem {
         selectors: vec![create_descendant_selector("parent", "child")],
         rules: vec![rule],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
-    scanner.traverse_stack = vec![&elements, &child_elements];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![
+        TraverseEntry::new(&elements, 0),
+        TraverseEntry::new(&child_elements, 0),
+    ];
 
     assert_eq!(scanner.scan().count(), 0);
 }
@@ -53,10 +61,14 @@ fn matches_multiple_rules() {
             create_rule("color", "red"),
             create_rule("background", "blue"),
         ],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
-    scanner.traverse_stack = vec![&elements, &child_elements];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![
+        TraverseEntry::new(&elements, 0),
+        TraverseEntry::new(&child_elements, 0),
+    ];
 
     assert_eq!(scanner.scan().count(), 2);
 }
@@ -89,10 +101,15 @@ fn no_match_for_incorrect_hierarchy() {
             )),
         )],
         rules: vec![create_rule("color", "red")],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
-    scanner.traverse_stack = vec![&root_elements, &middle_elements, &leaf_elements];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![
+        TraverseEntry::new(&root_elements, 0),
+        TraverseEntry::new(&middle_elements, 0),
+        TraverseEntry::new(&leaf_elements, 0),
+    ];
 
     // .b .a .c はマッチしてはいけない(.aは.bの子孫ではないため)
     assert_eq!(scanner.scan().count(), 0);
@@ -0,0 +1,133 @@
+use super::common::*;
+use crate::{TraverseEntry, VssScanner};
+use vsml_ast::vsml::Element;
+use vsml_ast::vss::{VSSItem, VSSSelector, VSSSelectorTree};
+
+/// `seq > prl{selector}` のようなセレクタで、children[index]がマッチするかを返す
+fn is_match(selector: VSSSelector, children: &[Element], index: usize) -> bool {
+    let seq = [create_element("seq", None, None)];
+    let vss_items = vec![VSSItem {
+        selectors: vec![VSSSelectorTree::Child(
+            vec![VSSSelector::Tag("seq".to_string())],
+            Box::new(VSSSelectorTree::Selectors(vec![
+                VSSSelector::Tag("prl".to_string()),
+                selector,
+            ])),
+        )],
+        rules: vec![create_rule("duration", "2s")],
+        span: Default::default(),
+    }];
+
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![
+        TraverseEntry::new(&seq, 0),
+        TraverseEntry::new(children, index),
+    ];
+    scanner.scan().count() == 1
+}
+
+fn create_prl_children(count: usize) -> Vec<Element> {
+    (0..count)
+        .map(|_| create_element("prl", None, None))
+        .collect()
+}
+
+#[test]
+fn matches_first_child() {
+    // <seq>
+    //   <prl></prl>  <!-- seq > prl:first-child としてマッチする -->
+    //   <prl></prl>
+    // </seq>
+    let children = create_prl_children(2);
+    let first_child = || VSSSelector::PseudoClass("first-child".to_string());
+
+    assert!(is_match(first_child(), &children, 0));
+    assert!(!is_match(first_child(), &children, 1));
+}
+
+#[test]
+fn matches_last_child() {
+    // <seq>
+    //   <prl></prl>
+    //   <prl></prl>  <!-- seq > prl:last-child としてマッチする -->
+    // </seq>
+    let children = create_prl_children(2);
+    let last_child = || VSSSelector::PseudoClass("last-child".to_string());
+
+    assert!(!is_match(last_child(), &children, 0));
+    assert!(is_match(last_child(), &children, 1));
+}
+
+#[test]
+fn first_and_last_child_ignore_text() {
+    // <seq>
+    //   テキスト
+    //   <prl></prl>  <!-- テキストは無視されるため、:first-child と :last-child の両方にマッチする -->
+    //   テキスト
+    // </seq>
+    let children = [
+        Element::Text("テキスト".to_string(), Default::default()),
+        create_element("prl", None, None),
+        Element::Text("テキスト".to_string(), Default::default()),
+    ];
+
+    assert!(is_match(
+        VSSSelector::PseudoClass("first-child".to_string()),
+        &children,
+        1
+    ));
+    assert!(is_match(
+        VSSSelector::PseudoClass("last-child".to_string()),
+        &children,
+        1
+    ));
+}
+
+#[test]
+fn matches_nth_child() {
+    let children = create_prl_children(6);
+    let matched_positions = |a, b| {
+        (0..children.len())
+            .filter(|&i| is_match(VSSSelector::NthChild(a, b), &children, i))
+            .map(|i| i + 1)
+            .collect::<Vec<_>>()
+    };
+
+    // :nth-child(2n)
+    assert_eq!(matched_positions(2, 0), vec![2, 4, 6]);
+    // :nth-child(2n+1)
+    assert_eq!(matched_positions(2, 1), vec![1, 3, 5]);
+    // :nth-child(3)
+    assert_eq!(matched_positions(0, 3), vec![3]);
+    // :nth-child(-n+3)
+    assert_eq!(matched_positions(-1, 3), vec![1, 2, 3]);
+    // :nth-child(3n-1)
+    assert_eq!(matched_positions(3, -1), vec![2, 5]);
+}
+
+#[test]
+fn matches_not() {
+    // <seq>
+    //   <prl class="bgm"></prl>
+    //   <prl></prl>  <!-- seq > prl:not(.bgm) としてマッチする -->
+    // </seq>
+    let children = [
+        create_element("prl", Some("bgm"), None),
+        create_element("prl", None, None),
+    ];
+    let not_bgm = || VSSSelector::Not(vec![VSSSelector::Class("bgm".to_string())]);
+
+    assert!(!is_match(not_bgm(), &children, 0));
+    assert!(is_match(not_bgm(), &children, 1));
+}
+
+#[test]
+fn no_match_for_unknown_pseudo_class() {
+    let children = create_prl_children(1);
+
+    assert!(!is_match(
+        VSSSelector::PseudoClass("hover".to_string()),
+        &children,
+        0
+    ));
+}
@@ -0,0 +1,277 @@
+use super::common::*;
+use crate::{TraverseEntry, VssScanner};
+use vsml_ast::vsml::Element;
+use vsml_ast::vss::{VSSItem, VSSSelector, VSSSelectorTree};
+
+fn create_adj_sibling_selector(elder_tag: &str, younger_tag: &str) -> VSSSelectorTree {
+    VSSSelectorTree::AdjSibling(
+        vec![VSSSelector::Tag(elder_tag.to_string())],
+        Box::new(VSSSelectorTree::Selectors(vec![VSSSelector::Tag(
+            younger_tag.to_string(),
+        )])),
+    )
+}
+
+fn create_sibling_selector(elder_tag: &str, younger_tag: &str) -> VSSSelectorTree {
+    VSSSelectorTree::Sibling(
+        vec![VSSSelector::Tag(elder_tag.to_string())],
+        Box::new(VSSSelectorTree::Selectors(vec![VSSSelector::Tag(
+            younger_tag.to_string(),
+        )])),
+    )
+}
+
+fn count_matches(selector: VSSSelectorTree, traverse_stack: Vec<TraverseEntry>) -> usize {
+    let vss_items = vec![VSSItem {
+        selectors: vec![selector],
+        rules: vec![create_rule("color", "red")],
+        span: Default::default(),
+    }];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = traverse_stack;
+    scanner.scan().count()
+}
+
+#[test]
+fn matches_adjacent_sibling() {
+    // <seq>
+    //   <txt></txt>
+    //   <aud></aud>  <!-- txt + aud としてマッチする -->
+    // </seq>
+    let seq = vec![create_element("seq", None, None)];
+    let children = [
+        create_element("txt", None, None),
+        create_element("aud", None, None),
+    ];
+
+    assert_eq!(
+        count_matches(
+            create_adj_sibling_selector("txt", "aud"),
+            vec![
+                TraverseEntry::new(&seq, 0),
+                TraverseEntry::new(&children, 1)
+            ]
+        ),
+        1
+    );
+}
+
+#[test]
+fn no_match_for_non_adjacent_sibling() {
+    // <seq>
+    //   <txt></txt>
+    //   <vid></vid>
+    //   <aud></aud>  <!-- txtの直後ではないため、txt + aud としてはマッチしない -->
+    // </seq>
+    let seq = vec![create_element("seq", None, None)];
+    let children = [
+        create_element("txt", None, None),
+        create_element("vid", None, None),
+        create_element("aud", None, None),
+    ];
+
+    assert_eq!(
+        count_matches(
+            create_adj_sibling_selector("txt", "aud"),
+            vec![
+                TraverseEntry::new(&seq, 0),
+                TraverseEntry::new(&children, 2)
+            ]
+        ),
+        0
+    );
+    // 先頭の要素には兄がいないためマッチしない
+    assert_eq!(
+        count_matches(
+            create_adj_sibling_selector("txt", "txt"),
+            vec![
+                TraverseEntry::new(&seq, 0),
+                TraverseEntry::new(&children, 0)
+            ]
+        ),
+        0
+    );
+}
+
+#[test]
+fn matches_general_sibling() {
+    // <seq>
+    //   <txt></txt>
+    //   <vid></vid>
+    //   <aud></aud>  <!-- txt ~ aud としてマッチする -->
+    // </seq>
+    let seq = vec![create_element("seq", None, None)];
+    let children = [
+        create_element("txt", None, None),
+        create_element("vid", None, None),
+        create_element("aud", None, None),
+    ];
+
+    assert_eq!(
+        count_matches(
+            create_sibling_selector("txt", "aud"),
+            vec![
+                TraverseEntry::new(&seq, 0),
+                TraverseEntry::new(&children, 2)
+            ]
+        ),
+        1
+    );
+    // 弟はマッチの対象にならない
+    assert_eq!(
+        count_matches(
+            create_sibling_selector("aud", "vid"),
+            vec![
+                TraverseEntry::new(&seq, 0),
+                TraverseEntry::new(&children, 1)
+            ]
+        ),
+        0
+    );
+}
+
+#[test]
+fn adjacent_sibling_ignores_text() {
+    // <seq>
+    //   <txt></txt>
+    //   テキスト
+    //   <aud></aud>  <!-- テキストは無視されるため、txt + aud としてマッチする -->
+    // </seq>
+    let seq = vec![create_element("seq", None, None)];
+    let children = [
+        create_element("txt", None, None),
+        Element::Text("テキスト".to_string(), Default::default()),
+        create_element("aud", None, None),
+    ];
+
+    assert_eq!(
+        count_matches(
+            create_adj_sibling_selector("txt", "aud"),
+            vec![
+                TraverseEntry::new(&seq, 0),
+                TraverseEntry::new(&children, 2)
+            ]
+        ),
+        1
+    );
+}
+
+#[test]
+fn matches_sibling_combined_with_child() {
+    // <seq class="subtitles">
+    //   <txt></txt>
+    //   <aud></aud>
+    //   <txt></txt>
+    //   <aud></aud>  <!-- .subtitles > txt + aud + txt + aud としてマッチする -->
+    // </seq>
+    let seq = vec![create_element("seq", Some("subtitles"), None)];
+    let children = [
+        create_element("txt", None, None),
+        create_element("aud", None, None),
+        create_element("txt", None, None),
+        create_element("aud", None, None),
+    ];
+    let tag = |name: &str| vec![VSSSelector::Tag(name.to_string())];
+    let selector = VSSSelectorTree::Child(
+        vec![VSSSelector::Class("subtitles".to_string())],
+        Box::new(VSSSelectorTree::AdjSibling(
+            tag("txt"),
+            Box::new(VSSSelectorTree::AdjSibling(
+                tag("aud"),
+                Box::new(VSSSelectorTree::AdjSibling(
+                    tag("txt"),
+                    Box::new(VSSSelectorTree::Selectors(tag("aud"))),
+                )),
+            )),
+        )),
+    );
+
+    assert_eq!(
+        count_matches(
+            selector,
+            vec![
+                TraverseEntry::new(&seq, 0),
+                TraverseEntry::new(&children, 3)
+            ]
+        ),
+        1
+    );
+}
+
+fn tag(name: &str) -> Vec<VSSSelector> {
+    vec![VSSSelector::Tag(name.to_string())]
+}
+
+#[test]
+fn matches_adjacent_sibling_before_farther_sibling() {
+    // <seq>
+    //   <txt></txt>
+    //   <vid></vid>
+    //   <vid></vid>
+    //   <aud></aud>  <!-- 1つ目のvidがtxtの直後なので、txt + vid ~ aud としてマッチする -->
+    // </seq>
+    let seq = vec![create_element("seq", None, None)];
+    let children = [
+        create_element("txt", None, None),
+        create_element("vid", None, None),
+        create_element("vid", None, None),
+        create_element("aud", None, None),
+    ];
+    let selector = VSSSelectorTree::AdjSibling(
+        tag("txt"),
+        Box::new(VSSSelectorTree::Sibling(
+            tag("vid"),
+            Box::new(VSSSelectorTree::Selectors(tag("aud"))),
+        )),
+    );
+
+    assert_eq!(
+        count_matches(
+            selector,
+            vec![
+                TraverseEntry::new(&seq, 0),
+                TraverseEntry::new(&children, 3)
+            ]
+        ),
+        1
+    );
+}
+
+#[test]
+fn matches_sibling_of_farther_ancestor() {
+    // <seq>
+    //   <txt></txt>
+    //   <prl>          <!-- txtの弟 -->
+    //     <prl>        <!-- 近い方のprlはtxtの弟ではない -->
+    //       <aud></aud>  <!-- txt ~ prl aud としてマッチする -->
+    //     </prl>
+    //   </prl>
+    // </seq>
+    let seq = vec![create_element("seq", None, None)];
+    let children = [
+        create_element("txt", None, None),
+        create_element("prl", None, None),
+    ];
+    let inner = [create_element("prl", None, None)];
+    let leaf = [create_element("aud", None, None)];
+    let selector = VSSSelectorTree::Sibling(
+        tag("txt"),
+        Box::new(VSSSelectorTree::Descendant(
+            tag("prl"),
+            Box::new(VSSSelectorTree::Selectors(tag("aud"))),
+        )),
+    );
+
+    assert_eq!(
+        count_matches(
+            selector,
+            vec![
+                TraverseEntry::new(&seq, 0),
+                TraverseEntry::new(&children, 1),
+                TraverseEntry::new(&inner, 0),
+                TraverseEntry::new(&leaf, 0),
+            ]
+        ),
+        1
+    );
+}
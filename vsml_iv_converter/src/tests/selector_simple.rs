@@ -1,5 +1,5 @@
 use super::common::*;
-use crate::VssScanner;
+use crate::{TraverseEntry, VssScanner};
 use vsml_ast::vss::{VSSItem, VSSSelector, VSSSelectorTree};
 
 #[test]
@@ -13,10 +13,11 @@ fn matches_simple_selector() {
             "test".to_string(),
         )])],
         rules: vec![rule],
+        span: Default::default(),
     }];
 
-    let mut scanner = VssScanner::new(&vss_items);
-    scanner.traverse_stack = vec![&elements];
+    let mut scanner = VssScanner::new(&vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![TraverseEntry::new(&elements, 0)];
 
     assert_eq!(scanner.scan().count(), 1);
 }
@@ -0,0 +1,183 @@
+use super::common::*;
+use crate::{TraverseEntry, VssScanner};
+use vsml_ast::vsml::Element;
+use vsml_ast::vss::{Rule, VSSItem, VSSSelector, VSSSelectorAttributeValue, VSSSelectorTree};
+
+fn create_important_rule(property: &str, value: &str) -> Rule {
+    Rule {
+        important: true,
+        ..create_rule(property, value)
+    }
+}
+
+/// 最終的に採用される(最後に返される)値を取得する
+fn scan_last_value(vss_items: &[VSSItem]) -> String {
+    scan_last_value_with_style(vss_items, vec![])
+}
+
+/// style属性を指定したエレメントで、最終的に採用される値を取得する
+fn scan_last_value_with_style(vss_items: &[VSSItem], style: Vec<Rule>) -> String {
+    let mut element = create_element("txt", Some("title"), Some("main"));
+    if let Element::Tag { style: s, .. } = &mut element {
+        *s = style;
+    }
+    let elements = [element];
+    let mut scanner = VssScanner::new(vss_items, &[], Default::default());
+    scanner.traverse_stack = vec![TraverseEntry::new(&elements, 0)];
+    scanner.scan().last().unwrap().value.clone()
+}
+
+#[test]
+fn id_rule_wins_over_later_tag_rule() {
+    // #main { font-color: red; }
+    // txt { font-color: blue; }
+    let vss_items = vec![
+        create_item(
+            vec![VSSSelector::Id("main".to_string())],
+            vec![create_rule("font-color", "red")],
+        ),
+        create_item(
+            vec![VSSSelector::Tag("txt".to_string())],
+            vec![create_rule("font-color", "blue")],
+        ),
+    ];
+
+    assert_eq!(scan_last_value(&vss_items), "red");
+}
+
+#[test]
+fn class_rule_wins_over_later_tag_rule() {
+    // txt.title { font-color: red; }
+    // txt { font-color: blue; }
+    let vss_items = vec![
+        create_item(
+            vec![
+                VSSSelector::Tag("txt".to_string()),
+                VSSSelector::Class("title".to_string()),
+            ],
+            vec![create_rule("font-color", "red")],
+        ),
+        create_item(
+            vec![VSSSelector::Tag("txt".to_string())],
+            vec![create_rule("font-color", "blue")],
+        ),
+    ];
+
+    assert_eq!(scan_last_value(&vss_items), "red");
+}
+
+#[test]
+fn later_rule_wins_with_same_specificity() {
+    // .title { font-color: red; }
+    // [class] { font-color: blue; }
+    let vss_items = vec![
+        create_item(
+            vec![VSSSelector::Class("title".to_string())],
+            vec![create_rule("font-color", "red")],
+        ),
+        create_item(
+            vec![VSSSelector::Attribute(
+                "class".to_string(),
+                VSSSelectorAttributeValue::None,
+            )],
+            vec![create_rule("font-color", "blue")],
+        ),
+    ];
+
+    assert_eq!(scan_last_value(&vss_items), "blue");
+}
+
+#[test]
+fn most_specific_matching_selector_is_used() {
+    // #main, seq { font-color: red; }
+    // .title { font-color: blue; }
+    let vss_items = vec![
+        VSSItem {
+            selectors: vec![
+                VSSSelectorTree::Selectors(vec![VSSSelector::Id("main".to_string())]),
+                VSSSelectorTree::Selectors(vec![VSSSelector::Tag("seq".to_string())]),
+            ],
+            rules: vec![create_rule("font-color", "red")],
+            span: Default::default(),
+        },
+        create_item(
+            vec![VSSSelector::Class("title".to_string())],
+            vec![create_rule("font-color", "blue")],
+        ),
+    ];
+
+    assert_eq!(scan_last_value(&vss_items), "red");
+}
+
+#[test]
+fn important_rule_wins_over_more_specific_rule() {
+    // txt { font-color: red !important; }
+    // #main { font-color: blue; }
+    let vss_items = vec![
+        create_item(
+            vec![VSSSelector::Tag("txt".to_string())],
+            vec![create_important_rule("font-color", "red")],
+        ),
+        create_item(
+            vec![VSSSelector::Id("main".to_string())],
+            vec![create_rule("font-color", "blue")],
+        ),
+    ];
+
+    assert_eq!(scan_last_value(&vss_items), "red");
+}
+
+#[test]
+fn specificity_applies_between_important_rules() {
+    // #main { font-color: red !important; }
+    // txt { font-color: blue !important; }
+    let vss_items = vec![
+        create_item(
+            vec![VSSSelector::Id("main".to_string())],
+            vec![create_important_rule("font-color", "red")],
+        ),
+        create_item(
+            vec![VSSSelector::Tag("txt".to_string())],
+            vec![create_important_rule("font-color", "blue")],
+        ),
+    ];
+
+    assert_eq!(scan_last_value(&vss_items), "red");
+}
+
+#[test]
+fn style_attribute_wins_over_id_rule() {
+    // #main { font-color: red; }
+    // <txt style="font-color: blue">
+    let vss_items = vec![create_item(
+        vec![VSSSelector::Id("main".to_string())],
+        vec![create_rule("font-color", "red")],
+    )];
+
+    assert_eq!(
+        scan_last_value_with_style(&vss_items, vec![create_rule("font-color", "blue")]),
+        "blue"
+    );
+}
+
+#[test]
+fn important_rule_wins_over_style_attribute() {
+    // txt { font-color: red !important; }
+    // <txt style="font-color: blue">
+    let vss_items = vec![create_item(
+        vec![VSSSelector::Tag("txt".to_string())],
+        vec![create_important_rule("font-color", "red")],
+    )];
+
+    assert_eq!(
+        scan_last_value_with_style(&vss_items, vec![create_rule("font-color", "blue")]),
+        "red"
+    );
+    assert_eq!(
+        scan_last_value_with_style(
+            &vss_items,
+            vec![create_important_rule("font-color", "blue")]
+        ),
+        "blue"
+    );
+}
@@ -1,19 +1,29 @@
-use crate::vss_parser::VSSParseError;
 use roxmltree::{Document, Node, NodeType};
 use std::error::Error;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
 use thiserror::Error;
+use vsml_ast::position::{Position, SourceId, Span};
 use vsml_ast::vsml::{Content, Element, Meta, VSML};
 
 mod vss_parser;
 
+pub use crate::vss_parser::VSSParseError;
+
 #[derive(Debug, Error, PartialEq)]
 pub enum VSMLParseError<VSSError> {
     #[error("XML Parse Error: {0}")]
     XMLParseError(#[from] roxmltree::Error),
     #[error("VSS load error: {0}")]
     VSSLoadError(VSSError),
-    #[error("VSS parse error: {0}")]
-    VSSParseError(#[from] VSSParseError),
+    /// 外部の.vssファイルのエラーの場合は、そのファイルのパスを持つ
+    #[error(
+        "VSS parse error: {path}{1}",
+        path = .0.as_ref().map(|path| format!("{path}:")).unwrap_or_default()
+    )]
+    VSSParseError(Option<String>, VSSParseError),
+    #[error("circular VSS import: {}", .0.join(" -> "))]
+    VSSImportCycleError(Vec<String>),
     #[error("both src and row text were specified in the style tag")]
     BothSrcAndTextInStyleError,
     #[error("style tag must be specified as at least one of src or row text")]
@@ -44,12 +54,18 @@ pub enum VSMLParseError<VSSError> {
     MixedTagAndTextError,
 }
 
-pub fn parse<L>(vsml_string: &str, vss_loader: &L) -> Result<VSML, VSMLParseError<L::Err>>
+/// `vsml_path` はVSMLファイルのパスで、エラーの表示などのために `Meta::sources` の先頭に記録される
+pub fn parse<L>(
+    vsml_string: &str,
+    vsml_path: &str,
+    vss_loader: &L,
+) -> Result<VSML, VSMLParseError<L::Err>>
 where
     L: VSSLoader,
 {
     // vsml文字列をXMLとして解釈し、DOMツリーを生成
     let doc = Document::parse(vsml_string)?;
+    let line_index = LineIndex::new(vsml_string);
     let root = doc.root();
     let mut root_children = root.children();
     let vsml = root_children.next().unwrap();
@@ -79,13 +95,13 @@ where
                 if meta.is_some() {
                     return Err(VSMLParseError::MultipleMetaElementsError);
                 }
-                meta = Some(parse_meta(child, vss_loader)?);
+                meta = Some(parse_meta(child, vsml_path, vss_loader, &line_index)?);
             }
             "cont" => {
                 if content.is_some() {
                     return Err(VSMLParseError::MultipleContentElementsError);
                 }
-                content = Some(parse_content(child)?);
+                content = Some(parse_content(child, &line_index)?);
             }
             _ => return Err(VSMLParseError::InvalidElementInVSMLError),
         }
@@ -94,17 +110,30 @@ where
         return Err(VSMLParseError::ContentElementNotFoundError);
     }
     Ok(VSML {
-        meta: meta.unwrap_or_else(|| Meta { vss_items: vec![] }),
+        meta: meta.unwrap_or_else(|| Meta {
+            vss_items: vec![],
+            keyframes: vec![],
+            sources: vec![vsml_path.to_owned()],
+        }),
         content: content.unwrap(),
     })
 }
 
-fn parse_meta<L>(node: Node, vss_loader: &L) -> Result<Meta, VSMLParseError<L::Err>>
+fn parse_meta<L>(
+    node: Node,
+    vsml_path: &str,
+    vss_loader: &L,
+    line_index: &LineIndex,
+) -> Result<Meta, VSMLParseError<L::Err>>
 where
     L: VSSLoader,
 {
     assert!(node.has_tag_name("meta"));
-    let mut vss_items = vec![];
+    let mut meta = Meta {
+        vss_items: vec![],
+        keyframes: vec![],
+        sources: vec![vsml_path.to_owned()],
+    };
     for child in node.children() {
         match child.node_type() {
             NodeType::Root => unreachable!(),
@@ -125,14 +154,20 @@ where
                     if child.text().is_some() {
                         return Err(VSMLParseError::BothSrcAndTextInStyleError);
                     }
-                    vss_items.extend(vss_parser::parse(
-                        vss_loader
-                            .load(src)
-                            .map_err(VSMLParseError::VSSLoadError)?
-                            .as_str(),
-                    )?);
+                    let vss = vss_loader.load(src).map_err(VSMLParseError::VSSLoadError)?;
+                    let mut importing = vec![normalize_path(Path::new(src))];
+                    load_vss(
+                        &vss,
+                        Position::START,
+                        Some(src),
+                        vss_loader,
+                        &mut importing,
+                        &mut meta,
+                    )?;
                 } else if let Some(vss_text) = child.text() {
-                    vss_items.extend(vss_parser::parse(vss_text)?);
+                    // 位置をVSMLファイル上の位置で表すため、テキストノードの開始位置を求める
+                    let origin = line_index.position(child.first_child().unwrap().range().start);
+                    load_vss(vss_text, origin, None, vss_loader, &mut vec![], &mut meta)?;
                 } else {
                     return Err(VSMLParseError::NoSrcAndTextInStyleError);
                 }
@@ -140,10 +175,92 @@ where
             _ => return Err(VSMLParseError::InvalidElementInMetaError),
         }
     }
-    Ok(Meta { vss_items })
+    Ok(meta)
+}
+
+/// VSSをパースし、`@import` で指定されたVSSを再帰的に読み込んで、読み込んだ順にmetaのvss_items, keyframesへ追加する
+/// `path` はVSSファイルのパスで、VSMLファイルに直接記述されたVSSの場合はNone
+/// VSSファイルのパスはmetaのsourcesに追加され、パース結果の範囲はそのファイルを指す
+/// `importing` は読み込み中のVSSファイルのパスの一覧で、循環の検出に使う
+fn load_vss<L>(
+    vss: &str,
+    origin: Position,
+    path: Option<&str>,
+    vss_loader: &L,
+    importing: &mut Vec<String>,
+    meta: &mut Meta,
+) -> Result<(), VSMLParseError<L::Err>>
+where
+    L: VSSLoader,
+{
+    let source = match path {
+        Some(path) => match meta.sources.iter().position(|source| source == path) {
+            Some(index) => SourceId(index),
+            None => {
+                meta.sources.push(path.to_owned());
+                SourceId(meta.sources.len() - 1)
+            }
+        },
+        None => SourceId::VSML,
+    };
+    let style_sheet = vss_parser::parse(vss, source, origin)
+        .map_err(|e| VSMLParseError::VSSParseError(path.map(str::to_owned), e))?;
+    for import in style_sheet.imports {
+        let import_path = resolve_import_path(path, &import);
+        if importing.contains(&import_path) {
+            let mut cycle = importing.clone();
+            cycle.push(import_path);
+            return Err(VSMLParseError::VSSImportCycleError(cycle));
+        }
+        let vss = vss_loader
+            .load(&import_path)
+            .map_err(VSMLParseError::VSSLoadError)?;
+        importing.push(import_path.clone());
+        load_vss(
+            &vss,
+            Position::START,
+            Some(&import_path),
+            vss_loader,
+            importing,
+            meta,
+        )?;
+        importing.pop();
+    }
+    meta.vss_items.extend(style_sheet.items);
+    meta.keyframes.extend(style_sheet.keyframes);
+    Ok(())
+}
+
+/// `@import` のパスを、読み込み元のVSSファイルのディレクトリからの相対パスとして解決する
+fn resolve_import_path(base: Option<&str>, path: &str) -> String {
+    let path = Path::new(path);
+    match base.and_then(|base| Path::new(base).parent()) {
+        Some(dir) if path.is_relative() => normalize_path(&dir.join(path)),
+        _ => normalize_path(path),
+    }
+}
+
+/// 循環の検出のため、パス中の `.` と `..` を取り除く
+fn normalize_path(path: &Path) -> String {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized.to_string_lossy().into_owned()
 }
 
-fn parse_content<L>(node: Node) -> Result<Content, VSMLParseError<L>> {
+fn parse_content<L>(node: Node, line_index: &LineIndex) -> Result<Content, VSMLParseError<L>> {
     assert!(node.has_tag_name("cont"));
     let resolution = node
         .attribute("resolution")
@@ -174,11 +291,11 @@ fn parse_content<L>(node: Node) -> Result<Content, VSMLParseError<L>> {
         .transpose()?;
     let elements: Vec<Element> = node
         .children()
-        .filter_map(|child| parse_element(child).transpose())
+        .filter_map(|child| parse_element(child, line_index).transpose())
         .collect::<Result<Vec<_>, _>>()?;
     // TagとTextの混合をチェック
     let has_tag = elements.iter().any(|e| matches!(e, Element::Tag { .. }));
-    let has_text = elements.iter().any(|e| matches!(e, Element::Text(_)));
+    let has_text = elements.iter().any(|e| matches!(e, Element::Text(..)));
     if has_tag && has_text {
         return Err(VSMLParseError::MixedTagAndTextError);
     }
@@ -188,23 +305,35 @@ fn parse_content<L>(node: Node) -> Result<Content, VSMLParseError<L>> {
         fps,
         sampling_rate,
         elements,
+        span: line_index.span(node.range()),
     })
 }
 
-fn parse_element<L>(node: Node) -> Result<Option<Element>, VSMLParseError<L>> {
+fn parse_element<L>(
+    node: Node,
+    line_index: &LineIndex,
+) -> Result<Option<Element>, VSMLParseError<L>> {
     match node.node_type() {
         NodeType::Root => unreachable!(),
         NodeType::Element => {
             let children: Vec<Element> = node
                 .children()
-                .filter_map(|child| parse_element(child).transpose())
+                .filter_map(|child| parse_element(child, line_index).transpose())
                 .collect::<Result<Vec<_>, _>>()?;
             // TagとTextの混合をチェック
             let has_tag = children.iter().any(|e| matches!(e, Element::Tag { .. }));
-            let has_text = children.iter().any(|e| matches!(e, Element::Text(_)));
+            let has_text = children.iter().any(|e| matches!(e, Element::Text(..)));
             if has_tag && has_text {
                 return Err(VSMLParseError::MixedTagAndTextError);
             }
+            let style = match node.attribute_node("style") {
+                Some(attr) => {
+                    let origin = line_index.position(attr.range_value().start);
+                    vss_parser::parse_rules(attr.value(), origin)
+                        .map_err(|e| VSMLParseError::VSSParseError(None, e))?
+                }
+                None => vec![],
+            };
             Ok(Some(Element::Tag {
                 name: node.tag_name().name().to_owned(),
                 attributes: node
@@ -212,16 +341,48 @@ fn parse_element<L>(node: Node) -> Result<Option<Element>, VSMLParseError<L>> {
                     .map(|attr| (attr.name().to_owned(), attr.value().to_owned()))
                     .collect(),
                 children,
+                style,
+                span: line_index.span(node.range()),
             }))
         }
         NodeType::PI | NodeType::Comment => Ok(None),
         NodeType::Text => {
             let text = node.text().unwrap().trim();
-            Ok((!text.is_empty()).then(|| Element::Text(text.to_owned())))
+            Ok((!text.is_empty())
+                .then(|| Element::Text(text.to_owned(), line_index.span(node.range()))))
         }
     }
 }
 
+/// バイト単位のオフセットから行・列を求めるための、各行の開始オフセットの一覧
+struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(text: &'a str) -> LineIndex<'a> {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        LineIndex { text, line_starts }
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+        Position::new(
+            offset,
+            line,
+            self.text[line_start..offset].chars().count() + 1,
+        )
+    }
+
+    fn span(&self, range: Range<usize>) -> Span {
+        Span::new(self.position(range.start), self.position(range.end))
+    }
+}
+
 #[cfg_attr(test, mockall::automock(type Err=std::convert::Infallible;))]
 pub trait VSSLoader {
     type Err: Error;
@@ -262,7 +423,7 @@ mod tests {
             .times(1)
             .returning(|_| Ok(".styled { font-color: red; }".to_owned()));
         assert_eq!(
-            parse(vsml, &mock_vss_loader),
+            parse(vsml, "video.vsml", &mock_vss_loader),
             Ok(VSML {
                 meta: Meta {
                     vss_items: vec![
@@ -273,7 +434,12 @@ mod tests {
                             rules: vec![Rule {
                                 property: "font-color".to_owned(),
                                 value: "red".to_owned(),
+                                important: false,
+                                span: Span::new(Position::new(10, 1, 11), Position::new(25, 1, 26))
+                                    .with_source(SourceId(1)),
                             },],
+                            span: Span::new(Position::new(0, 1, 1), Position::new(28, 1, 29))
+                                .with_source(SourceId(1)),
                         },
                         VSSItem {
                             selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Tag(
@@ -282,9 +448,14 @@ mod tests {
                             rules: vec![Rule {
                                 property: "height".to_owned(),
                                 value: "100rh".to_owned(),
+                                important: false,
+                                span: Span::new(Position::new(77, 6, 9), Position::new(90, 6, 22)),
                             }],
+                            span: Span::new(Position::new(63, 5, 7), Position::new(99, 7, 8)),
                         },
                     ],
+                    keyframes: vec![],
+                    sources: vec!["video.vsml".to_owned(), "hoge.vss".to_owned()],
                 },
                 content: Content {
                     width: 1920,
@@ -302,6 +473,11 @@ mod tests {
                                     .cloned()
                                     .collect(),
                                 children: vec![],
+                                style: vec![],
+                                span: Span::new(
+                                    Position::new(180, 12, 7),
+                                    Position::new(204, 12, 31)
+                                ),
                             },
                             Element::Tag {
                                 name: "layer".to_owned(),
@@ -313,7 +489,18 @@ mod tests {
                                             .iter()
                                             .cloned()
                                             .collect(),
-                                        children: vec![Element::Text("これは文章です".to_owned()),],
+                                        children: vec![Element::Text(
+                                            "これは文章です".to_owned(),
+                                            Span::new(
+                                                Position::new(247, 14, 29),
+                                                Position::new(268, 14, 36)
+                                            )
+                                        )],
+                                        style: vec![],
+                                        span: Span::new(
+                                            Position::new(227, 14, 9),
+                                            Position::new(274, 14, 42)
+                                        ),
                                     },
                                     Element::Tag {
                                         name: "txt".to_owned(),
@@ -322,13 +509,30 @@ mod tests {
                                             .cloned()
                                             .collect(),
                                         children: vec![Element::Text(
-                                            "これもまた文章です".to_owned()
-                                        ),],
+                                            "これもまた文章です".to_owned(),
+                                            Span::new(
+                                                Position::new(303, 15, 29),
+                                                Position::new(330, 15, 38)
+                                            )
+                                        )],
+                                        style: vec![],
+                                        span: Span::new(
+                                            Position::new(283, 15, 9),
+                                            Position::new(336, 15, 44)
+                                        ),
                                     },
                                 ],
+                                style: vec![],
+                                span: Span::new(
+                                    Position::new(211, 13, 7),
+                                    Position::new(351, 16, 15)
+                                ),
                             },
                         ],
+                        style: vec![],
+                        span: Span::new(Position::new(168, 11, 5), Position::new(362, 17, 11)),
                     },],
+                    span: Span::new(Position::new(125, 10, 3), Position::new(372, 18, 10)),
                 },
             })
         );
@@ -349,9 +553,13 @@ mod tests {
 </vsml>"#;
         let mock_vss_loader = MockVSSLoader::new();
         assert_eq!(
-            parse(vsml, &mock_vss_loader),
+            parse(vsml, "video.vsml", &mock_vss_loader),
             Ok(VSML {
-                meta: Meta { vss_items: vec![] },
+                meta: Meta {
+                    vss_items: vec![],
+                    keyframes: vec![],
+                    sources: vec!["video.vsml".to_owned()],
+                },
                 content: Content {
                     width: 1920,
                     height: 1080,
@@ -365,8 +573,14 @@ mod tests {
                         )]),
                         // TODO: 現状生文字列は前後がtrimされる
                         // この挙動が正しいかどうかは要検討
-                        children: vec![Element::Text("<   \n\t\n>&\"'㋐".to_owned())]
+                        children: vec![Element::Text(
+                            "<   \n\t\n>&\"'㋐".to_owned(),
+                            Span::new(Position::new(299, 6, 60), Position::new(385, 9, 5))
+                        )],
+                        style: vec![],
+                        span: Span::new(Position::new(124, 5, 5), Position::new(391, 9, 11)),
                     }],
+                    span: Span::new(Position::new(21, 3, 1), Position::new(399, 10, 8)),
                 },
             })
         );
@@ -384,7 +598,7 @@ mod tests {
 </cont>
 </vsml>"#;
         let mock_vss_loader = MockVSSLoader::new();
-        let result = parse(vsml, &mock_vss_loader);
+        let result = parse(vsml, "video.vsml", &mock_vss_loader);
         assert_eq!(result, Err(VSMLParseError::MixedTagAndTextError));
     }
 
@@ -397,7 +611,220 @@ mod tests {
 </cont>
 </vsml>"#;
         let mock_vss_loader = MockVSSLoader::new();
-        let result = parse(vsml, &mock_vss_loader);
+        let result = parse(vsml, "video.vsml", &mock_vss_loader);
         assert_eq!(result, Err(VSMLParseError::MixedTagAndTextError));
     }
+
+    #[test]
+    fn test_parse_vsml_style_attribute() {
+        let vsml = r#"<vsml>
+  <cont resolution="1920x1080">
+    <txt style="font-color: red; duration: 2s !important">hello</txt>
+  </cont>
+</vsml>"#;
+        let mock_vss_loader = MockVSSLoader::new();
+        assert_eq!(
+            parse(vsml, "video.vsml", &mock_vss_loader),
+            Ok(VSML {
+                meta: Meta {
+                    vss_items: vec![],
+                    keyframes: vec![],
+                    sources: vec!["video.vsml".to_owned()],
+                },
+                content: Content {
+                    width: 1920,
+                    height: 1080,
+                    fps: None,
+                    sampling_rate: None,
+                    elements: vec![Element::Tag {
+                        name: "txt".to_owned(),
+                        attributes: HashMap::from([(
+                            "style".to_owned(),
+                            "font-color: red; duration: 2s !important".to_owned()
+                        )]),
+                        children: vec![Element::Text(
+                            "hello".to_owned(),
+                            Span::new(Position::new(97, 3, 59), Position::new(102, 3, 64))
+                        )],
+                        style: vec![
+                            Rule {
+                                property: "font-color".to_owned(),
+                                value: "red".to_owned(),
+                                important: false,
+                                span: Span::new(Position::new(55, 3, 17), Position::new(70, 3, 32)),
+                            },
+                            Rule {
+                                property: "duration".to_owned(),
+                                value: "2s".to_owned(),
+                                important: true,
+                                span: Span::new(Position::new(72, 3, 34), Position::new(95, 3, 57)),
+                            },
+                        ],
+                        span: Span::new(Position::new(43, 3, 5), Position::new(108, 3, 70)),
+                    }],
+                    span: Span::new(Position::new(9, 2, 3), Position::new(118, 4, 10)),
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_vsml_style_attribute_parse_error_position() {
+        let vsml = r#"<vsml>
+  <cont resolution="1920x1080">
+    <txt style="font-color red">hello</txt>
+  </cont>
+</vsml>"#;
+        let mock_vss_loader = MockVSSLoader::new();
+        assert_eq!(
+            parse(vsml, "video.vsml", &mock_vss_loader),
+            Err(VSMLParseError::VSSParseError(
+                None,
+                VSSParseError::UnexpectedToken(Position::new(66, 3, 28), "red".to_owned())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_vsml_vss_parse_error_position() {
+        let vsml = r#"<vsml>
+  <meta>
+    <style>
+      seq {
+        duration 1s;
+      }
+    </style>
+  </meta>
+  <cont resolution="1920x1080"></cont>
+</vsml>"#;
+        let mock_vss_loader = MockVSSLoader::new();
+        assert_eq!(
+            parse(vsml, "video.vsml", &mock_vss_loader),
+            Err(VSMLParseError::VSSParseError(
+                None,
+                VSSParseError::UnexpectedToken(Position::new(57, 5, 18), "1s".to_owned())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_vsml_vss_file_parse_error_position() {
+        let vsml = r#"<vsml>
+  <meta>
+    <style src="theme.vss" />
+  </meta>
+  <cont resolution="1920x1080"></cont>
+</vsml>"#;
+        let mut mock_vss_loader = MockVSSLoader::new();
+        mock_vss_loader
+            .expect_load()
+            .times(1)
+            .returning(|_| Ok("txt {\n  font-color: red;\n".to_owned()));
+        assert_eq!(
+            parse(vsml, "video.vsml", &mock_vss_loader),
+            Err(VSMLParseError::VSSParseError(
+                Some("theme.vss".to_owned()),
+                VSSParseError::MissingCloseBrace(Position::new(25, 3, 1))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_vsml_vss_import() {
+        let vsml = r#"<vsml>
+  <meta>
+    <style src="theme/main.vss" />
+  </meta>
+  <cont resolution="1920x1080"></cont>
+</vsml>"#;
+        let mut mock_vss_loader = MockVSSLoader::new();
+        mock_vss_loader
+            .expect_load()
+            .times(3)
+            .returning(|path| match path {
+                "theme/main.vss" => Ok(
+                    "@import \"base.vss\";\n@import \"../common/./colors.vss\";\nmain {}"
+                        .to_owned(),
+                ),
+                "theme/base.vss" => Ok("base {}".to_owned()),
+                "common/colors.vss" => Ok("colors {}".to_owned()),
+                path => panic!("unexpected path {path}"),
+            });
+        let meta = parse(vsml, "video.vsml", &mock_vss_loader).unwrap().meta;
+        // importされたVSSは、import元のVSSより前に読み込まれる
+        let selectors = meta
+            .vss_items
+            .iter()
+            .map(|item| &item.selectors[0])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            selectors,
+            ["base", "colors", "main"]
+                .map(|tag| VSSSelectorTree::Selectors(vec![VSSSelector::Tag(tag.to_owned())]))
+                .iter()
+                .collect::<Vec<_>>()
+        );
+        // 範囲はそれぞれのVSSファイルを指す
+        assert_eq!(
+            meta.sources,
+            [
+                "video.vsml",
+                "theme/main.vss",
+                "theme/base.vss",
+                "common/colors.vss"
+            ]
+        );
+        let item_sources = meta
+            .vss_items
+            .iter()
+            .map(|item| item.span.source)
+            .collect::<Vec<_>>();
+        assert_eq!(item_sources, [SourceId(2), SourceId(3), SourceId(1)]);
+    }
+
+    #[test]
+    fn test_parse_vsml_vss_import_cycle() {
+        let vsml = r#"<vsml>
+  <meta>
+    <style src="theme/main.vss" />
+  </meta>
+  <cont resolution="1920x1080"></cont>
+</vsml>"#;
+        let mut mock_vss_loader = MockVSSLoader::new();
+        mock_vss_loader.expect_load().returning(|path| match path {
+            "theme/main.vss" => Ok("@import \"parts/a.vss\";".to_owned()),
+            "theme/parts/a.vss" => Ok("@import \"../main.vss\";".to_owned()),
+            path => panic!("unexpected path {path}"),
+        });
+        assert_eq!(
+            parse(vsml, "video.vsml", &mock_vss_loader),
+            Err(VSMLParseError::VSSImportCycleError(vec![
+                "theme/main.vss".to_owned(),
+                "theme/parts/a.vss".to_owned(),
+                "theme/main.vss".to_owned(),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parse_vsml_vss_import_parse_error() {
+        let vsml = r#"<vsml>
+  <meta>
+    <style>@import "theme.vss";</style>
+  </meta>
+  <cont resolution="1920x1080"></cont>
+</vsml>"#;
+        let mut mock_vss_loader = MockVSSLoader::new();
+        mock_vss_loader
+            .expect_load()
+            .times(1)
+            .returning(|_| Ok("txt {".to_owned()));
+        assert_eq!(
+            parse(vsml, "video.vsml", &mock_vss_loader),
+            Err(VSMLParseError::VSSParseError(
+                Some("theme.vss".to_owned()),
+                VSSParseError::MissingCloseBrace(Position::new(5, 1, 6))
+            ))
+        );
+    }
 }
@@ -3,123 +3,685 @@ use nom::Parser;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{crlf, newline, space1};
-use nom::combinator::{all_consuming, iterator, map, peek, success};
-use nom::multi::{many0, many1};
+use nom::combinator::{iterator, map, peek, success};
+use nom::error::{ErrorKind, ParseError};
+use nom::multi::many1;
 use nom::sequence::terminated;
 use regex::Regex;
+use std::cell::Cell;
 use std::sync::LazyLock;
 use thiserror::Error;
-use vsml_ast::vss::{Rule, VSSItem, VSSSelector, VSSSelectorTree};
+use vsml_ast::position::{Position, SourceId, Span};
+use vsml_ast::vss::{
+    Rule, VSSItem, VSSKeyframe, VSSKeyframes, VSSSelector, VSSSelectorAttributeValue,
+    VSSSelectorTree,
+};
 
+/// VSSの構文エラー
+/// 位置はVSSが記述されたファイル(VSMLファイルまたは外部の.vssファイル)上の位置
 #[derive(Debug, Error, PartialEq)]
-pub enum VSSParseError {}
+pub enum VSSParseError {
+    #[error("{0}: unexpected token {1:?}")]
+    UnexpectedToken(Position, String),
+    #[error("{0}: unterminated comment")]
+    UnterminatedComment(Position),
+    #[error("{0}: expected '{{'")]
+    MissingOpenBrace(Position),
+    #[error("{0}: expected '}}'")]
+    MissingCloseBrace(Position),
+    #[error("{0}: invalid selector {1:?}")]
+    InvalidSelector(Position, String),
+}
+
+impl VSSParseError {
+    /// エラーが発生した位置
+    pub fn position(&self) -> Position {
+        match *self {
+            VSSParseError::UnexpectedToken(position, _)
+            | VSSParseError::UnterminatedComment(position)
+            | VSSParseError::MissingOpenBrace(position)
+            | VSSParseError::MissingCloseBrace(position)
+            | VSSParseError::InvalidSelector(position, _) => position,
+        }
+    }
+}
+
+/// パーサ内部で使うエラーの種類
+#[derive(Debug, PartialEq)]
+enum FailureKind {
+    /// nomのコンビネータが返したエラー
+    Nom(ErrorKind),
+    UnexpectedToken,
+    UnterminatedComment,
+    MissingOpenBrace,
+    MissingCloseBrace,
+    InvalidSelector,
+}
+
+/// パーサ内部で使うエラー
+/// `input` はエラーが発生した地点以降の入力
+#[derive(Debug, PartialEq)]
+struct Failure<'a> {
+    input: &'a str,
+    kind: FailureKind,
+}
+
+impl<'a> ParseError<&'a str> for Failure<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Failure {
+            input,
+            kind: FailureKind::Nom(kind),
+        }
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl Failure<'_> {
+    fn into_parse_error(self, locator: &Locator) -> VSSParseError {
+        let position = locator.position(self.input);
+        match self.kind {
+            FailureKind::Nom(_) | FailureKind::UnexpectedToken => {
+                VSSParseError::UnexpectedToken(position, next_token(self.input).to_owned())
+            }
+            FailureKind::UnterminatedComment => VSSParseError::UnterminatedComment(position),
+            FailureKind::MissingOpenBrace => VSSParseError::MissingOpenBrace(position),
+            FailureKind::MissingCloseBrace => VSSParseError::MissingCloseBrace(position),
+            FailureKind::InvalidSelector => {
+                VSSParseError::InvalidSelector(position, next_token(self.input).to_owned())
+            }
+        }
+    }
+}
+
+type VSSResult<'a, O> = IResult<&'a str, O, Failure<'a>>;
+
+/// パース中の入力の残りから、ソースファイル上の位置を求める
+struct Locator<'a> {
+    vss: &'a str,
+    source: SourceId,
+    origin: Position,
+    /// 直前に求めた位置のキャッシュ(vss上のオフセットとその位置)
+    last: Cell<(usize, Position)>,
+}
+
+impl<'a> Locator<'a> {
+    fn new(vss: &'a str, source: SourceId, origin: Position) -> Locator<'a> {
+        Locator {
+            vss,
+            source,
+            origin,
+            last: Cell::new((0, origin)),
+        }
+    }
+
+    /// `rest` の先頭の位置
+    fn position(&self, rest: &str) -> Position {
+        let offset = self.vss.len() - rest.len();
+        let (last_offset, last_position) = self.last.get();
+        // 位置は概ね前から順に求められるので、直前の位置から読み進める
+        let position = if last_offset <= offset {
+            last_position.advance(&self.vss[last_offset..offset])
+        } else {
+            self.origin.advance(&self.vss[..offset])
+        };
+        self.last.set((offset, position));
+        position
+    }
+
+    /// `start_rest` の先頭から `end_rest` の先頭までの範囲
+    fn span(&self, start_rest: &str, end_rest: &str) -> Span {
+        Span::new(self.position(start_rest), self.position(end_rest)).with_source(self.source)
+    }
+}
+
+fn fail<O>(input: &str, kind: FailureKind) -> VSSResult<'_, O> {
+    Err(nom::Err::Failure(Failure { input, kind }))
+}
+
+/// エラーメッセージ用に、入力の先頭のトークンを切り出す
+fn next_token(input: &str) -> &str {
+    const DELIMITERS: &[char] = &['{', '}', ';', ':', ','];
+    match input.chars().next() {
+        None => "",
+        Some(c) if DELIMITERS.contains(&c) => &input[..c.len_utf8()],
+        Some(_) => {
+            let end = input
+                .find(|c: char| c.is_whitespace() || DELIMITERS.contains(&c))
+                .unwrap_or(input.len());
+            &input[..end]
+        }
+    }
+}
+
+/// VSSのパース結果
+#[derive(Debug, PartialEq)]
+pub struct StyleSheet {
+    /// `@import` で指定されたパス(記述順)
+    pub imports: Vec<String>,
+    pub items: Vec<VSSItem>,
+    /// `@keyframes` の定義(記述順)
+    pub keyframes: Vec<VSSKeyframes>,
+}
 
-pub fn parse(vss: &str) -> Result<Vec<VSSItem>, VSSParseError> {
-    match parse_vss_item_list(vss) {
+/// VSSの文字列をパースする
+/// `source` は `vss` が記述されたソースファイルで、パース結果の範囲に記録される
+/// `origin` は `vss` の先頭文字のソースファイル上の位置で、エラーの位置の計算に使われる
+pub fn parse(vss: &str, source: SourceId, origin: Position) -> Result<StyleSheet, VSSParseError> {
+    let locator = Locator::new(vss, source, origin);
+    finish(&locator, parse_style_sheet(&locator, vss))
+}
+
+/// VSMLファイルの `style` アトリビュートのような `<property>: <value>; ...` の形式のルールの列をパースする
+pub fn parse_rules(style: &str, origin: Position) -> Result<Vec<Rule>, VSSParseError> {
+    let locator = Locator::new(style, SourceId::VSML, origin);
+    finish(&locator, parse_vss_rule_list(&locator, style))
+}
+
+fn finish<T>(locator: &Locator, result: VSSResult<T>) -> Result<T, VSSParseError> {
+    match result {
         Ok((_, result)) => Ok(result),
-        Err(_) => {
-            todo!()
+        Err(nom::Err::Error(failure) | nom::Err::Failure(failure)) => {
+            Err(failure.into_parse_error(locator))
         }
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never return Incomplete"),
     }
 }
 
-fn parse_vss_item_list(input: &str) -> IResult<&str, Vec<VSSItem>> {
+fn parse_style_sheet<'a>(locator: &Locator, input: &'a str) -> VSSResult<'a, StyleSheet> {
+    let (mut input, _) = skip_comment_or_whitespace(input)?;
+    // CSSと同様に `@import` は他のルールより前にのみ記述できる
+    let mut imports = vec![];
+    while input.starts_with("@import") {
+        let (i, path) = parse_vss_import(input)?;
+        imports.push(path);
+        (input, _) = skip_comment_or_whitespace(i)?;
+    }
+    let (input, (items, keyframes)) = parse_vss_item_list(locator, input)?;
+    Ok((
+        input,
+        StyleSheet {
+            imports,
+            items,
+            keyframes,
+        },
+    ))
+}
+
+// @import "<path>";
+fn parse_vss_import(input: &str) -> VSSResult<'_, String> {
+    static PATH: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"^(?:"(?:[^\\"]|\\.)*"|'(?:[^\\']|\\.)*')"#).unwrap());
+    let (input, _) = tag("@import")(input)?;
     let (input, _) = skip_comment_or_whitespace(input)?;
-    all_consuming(many0(terminated(
-        parse_vss_item,
-        skip_comment_or_whitespace,
-    )))
-    .parse(input)
+    let Ok((input, quoted)) = regex_matches(&PATH)(input) else {
+        return fail(input, FailureKind::UnexpectedToken);
+    };
+    let (input, _) = skip_comment_or_whitespace(input)?;
+    let Ok((input, _)) = tag::<_, _, ()>(";")(input) else {
+        return fail(input, FailureKind::UnexpectedToken);
+    };
+    Ok((input, unescape(&quoted[1..quoted.len() - 1])))
 }
 
-fn parse_vss_item(input: &str) -> IResult<&str, VSSItem> {
-    let mut iter = iterator(
-        input,
-        terminated(parse_vss_selector, (tag(","), skip_comment_or_whitespace)),
-    );
-    let mut selectors = iter.by_ref().collect::<Vec<_>>();
-    let (mut input, _) = iter.finish()?;
-    if let Ok((i, selector)) = parse_vss_selector(input) {
-        input = i;
-        selectors.push(selector);
+/// `@keyframes` はルールの間の任意の位置に記述できる
+fn parse_vss_item_list<'a>(
+    locator: &Locator,
+    input: &'a str,
+) -> VSSResult<'a, (Vec<VSSItem>, Vec<VSSKeyframes>)> {
+    let (mut input, _) = skip_comment_or_whitespace(input)?;
+    let mut items = vec![];
+    let mut keyframes = vec![];
+    while !input.is_empty() {
+        if input.starts_with("@keyframes") {
+            let (i, item) = parse_vss_keyframes(locator, input)?;
+            keyframes.push(item);
+            (input, _) = skip_comment_or_whitespace(i)?;
+            continue;
+        }
+        if input.starts_with(['}', '@']) {
+            return fail(input, FailureKind::UnexpectedToken);
+        }
+        let (i, item) = parse_vss_item(locator, input, &[])?;
+        items.extend(item);
+        (input, _) = skip_comment_or_whitespace(i)?;
     }
+    Ok((input, (items, keyframes)))
+}
+
+// @keyframes <name> { <offset>, <offset> { <rules> } ... }
+fn parse_vss_keyframes<'a>(locator: &Locator, input: &'a str) -> VSSResult<'a, VSSKeyframes> {
+    static NAME: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^-?[_a-zA-Z][-_0-9a-zA-Z]*").unwrap());
+    let start = input;
+    let (input, _) = tag("@keyframes")(input)?;
     let (input, _) = skip_comment_or_whitespace(input)?;
-    let (input, _) = tag("{")(input)?;
+    let Ok((input, name)) = regex_matches(&NAME)(input) else {
+        return fail(input, FailureKind::UnexpectedToken);
+    };
     let (input, _) = skip_comment_or_whitespace(input)?;
-    let mut iter = iterator(
+    let Ok((mut input, _)) = tag::<_, _, ()>("{")(input) else {
+        return fail(input, FailureKind::MissingOpenBrace);
+    };
+    let mut keyframes = vec![];
+    loop {
+        let (i, _) = skip_comment_or_whitespace(input)?;
+        if let Ok((i, _)) = tag::<_, _, ()>("}")(i) {
+            input = i;
+            break;
+        }
+        if i.is_empty() {
+            return fail(i, FailureKind::MissingCloseBrace);
+        }
+        let (i, keyframe) = parse_vss_keyframe(locator, i)?;
+        keyframes.push(keyframe);
+        input = i;
+    }
+    Ok((
         input,
-        terminated(parse_vss_rule, (tag(";"), skip_comment_or_whitespace)),
-    );
-    let mut rules = iter.by_ref().collect::<Vec<_>>();
-    let (mut input, _) = iter.finish()?;
-    if let Ok((i, rule)) = parse_vss_rule(input) {
+        VSSKeyframes {
+            name: name.to_owned(),
+            keyframes,
+            span: locator.span(start, input),
+        },
+    ))
+}
+
+// <offset>, <offset> { <rules> }
+fn parse_vss_keyframe<'a>(locator: &Locator, input: &'a str) -> VSSResult<'a, VSSKeyframe> {
+    static PERCENTAGE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^(?:\d+(?:\.\d*)?|\.\d+)%").unwrap());
+    let start = input;
+    let mut input = input;
+    let mut offsets = vec![];
+    loop {
+        let (i, _) = skip_comment_or_whitespace(input)?;
+        let (i, offset) = if let Ok((i, _)) = tag::<_, _, ()>("from")(i) {
+            (i, 0.0)
+        } else if let Ok((i, _)) = tag::<_, _, ()>("to")(i) {
+            (i, 100.0)
+        } else if let Ok((rest, percentage)) = regex_matches(&PERCENTAGE)(i) {
+            let offset: f64 = percentage[..percentage.len() - 1].parse().unwrap();
+            if offset > 100.0 {
+                return fail(i, FailureKind::InvalidSelector);
+            }
+            (rest, offset)
+        } else {
+            return fail(i, FailureKind::InvalidSelector);
+        };
+        offsets.push(offset);
+        let (i, _) = skip_comment_or_whitespace(i)?;
+        if let Ok((i, _)) = tag::<_, _, ()>(",")(i) {
+            input = i;
+            continue;
+        }
+        let Ok((i, _)) = tag::<_, _, ()>("{")(i) else {
+            return fail(i, FailureKind::MissingOpenBrace);
+        };
+        input = i;
+        break;
+    }
+    let mut rules = vec![];
+    loop {
+        let (i, _) = skip_comment_or_whitespace(input)?;
+        if let Ok((i, _)) = tag::<_, _, ()>("}")(i) {
+            input = i;
+            break;
+        }
+        if i.is_empty() {
+            return fail(i, FailureKind::MissingCloseBrace);
+        }
+        let (i, rule) = parse_vss_rule(locator, i).map_err(|e| {
+            e.map(|failure| Failure {
+                kind: FailureKind::UnexpectedToken,
+                ..failure
+            })
+        })?;
+        rules.push(rule);
+        let (i, _) = skip_comment_or_whitespace(i)?;
+        if let Ok((i, _)) = tag::<_, _, ()>(";")(i) {
+            input = i;
+        } else if let Ok((i, _)) = tag::<_, _, ()>("}")(i) {
+            input = i;
+            break;
+        } else if i.is_empty() {
+            return fail(i, FailureKind::MissingCloseBrace);
+        } else {
+            return fail(i, FailureKind::UnexpectedToken);
+        }
+    }
+    Ok((
+        input,
+        VSSKeyframe {
+            offsets,
+            rules,
+            span: locator.span(start, input),
+        },
+    ))
+}
+
+/// `parents` はネストされたルールの場合の親のセレクタで、トップレベルのルールの場合は空
+/// ネストされたルールは親のセレクタと結合し、親のルールの後に並べて返す
+fn parse_vss_item<'a>(
+    locator: &Locator,
+    input: &'a str,
+    parents: &[VSSSelectorTree],
+) -> VSSResult<'a, Vec<VSSItem>> {
+    let start = input;
+    let mut selectors = vec![];
+    let mut input = input;
+    loop {
+        let (i, chain) = parse_vss_selector_chain(input, !parents.is_empty())?;
+        if parents.is_empty() {
+            selectors.push(chain.into_tree());
+        } else {
+            selectors.extend(parents.iter().map(|parent| chain.nest(parent)));
+        }
+        let Ok((i, _)) = tag::<_, _, ()>(",")(i) else {
+            input = i;
+            break;
+        };
         input = i;
+    }
+    // parse_vss_selector_chainは `{` の直前で止まっている
+    let (mut input, _) = tag("{")(input)?;
+    let mut rules = vec![];
+    let mut nested_items = vec![];
+    loop {
+        let (i, _) = skip_comment_or_whitespace(input)?;
+        if let Ok((i, _)) = tag::<_, _, ()>("}")(i) {
+            input = i;
+            break;
+        }
+        if i.is_empty() {
+            return fail(i, FailureKind::MissingCloseBrace);
+        }
+        if is_nested_item(i) {
+            let (i, items) = parse_vss_item(locator, i, &selectors)?;
+            nested_items.extend(items);
+            input = i;
+            continue;
+        }
+        let (i, rule) = parse_vss_rule(locator, i).map_err(|e| {
+            e.map(|failure| Failure {
+                kind: FailureKind::UnexpectedToken,
+                ..failure
+            })
+        })?;
         rules.push(rule);
+        let (i, _) = skip_comment_or_whitespace(i)?;
+        if let Ok((i, _)) = tag::<_, _, ()>(";")(i) {
+            input = i;
+        } else if let Ok((i, _)) = tag::<_, _, ()>("}")(i) {
+            input = i;
+            break;
+        } else if i.is_empty() {
+            return fail(i, FailureKind::MissingCloseBrace);
+        } else {
+            return fail(i, FailureKind::UnexpectedToken);
+        }
     }
+    let span = locator.span(start, input);
     let (input, _) = skip_comment_or_whitespace(input)?;
-    let (input, _) = tag("}")(input)?;
-    let (input, _) = skip_comment_or_whitespace(input)?;
 
-    Ok((input, VSSItem { selectors, rules }))
+    let mut items = vec![VSSItem {
+        selectors,
+        rules,
+        span,
+    }];
+    items.append(&mut nested_items);
+    Ok((input, items))
+}
+
+/// ブロック内の次の要素が宣言ではなくネストされたルールであるか
+/// `txt:first-child { ... }` のように宣言と区別できない場合があるため、`;` や `}` より先に `{` が来るかで判定する
+fn is_nested_item(input: &str) -> bool {
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => return true,
+            ';' | '}' => return false,
+            '"' | '\'' => {
+                // 文字列中の記号は無視する
+                while let Some(q) = chars.next() {
+                    if q == '\\' {
+                        chars.next();
+                    } else if q == c {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+fn parse_vss_rule_list<'a>(locator: &Locator, input: &'a str) -> VSSResult<'a, Vec<Rule>> {
+    let mut input = input;
+    let mut rules = vec![];
+    loop {
+        let (i, _) = skip_comment_or_whitespace(input)?;
+        if i.is_empty() {
+            return Ok((i, rules));
+        }
+        let (i, rule) = parse_vss_rule(locator, i).map_err(|e| {
+            e.map(|failure| Failure {
+                kind: FailureKind::UnexpectedToken,
+                ..failure
+            })
+        })?;
+        rules.push(rule);
+        let (i, _) = skip_comment_or_whitespace(i)?;
+        if let Ok((i, _)) = tag::<_, _, ()>(";")(i) {
+            input = i;
+        } else if i.is_empty() {
+            return Ok((i, rules));
+        } else {
+            return fail(i, FailureKind::UnexpectedToken);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Combinator {
+    Descendant,
+    Child,
+    Sibling,
+    AdjSibling,
+}
+
+/// `&` を含むことのある、結合子で区切られたセレクタ
+/// `a > &.b c` は `compounds` が `[a, &.b, c]`、`combinators` が `[Child, Descendant]` となる
+#[derive(Debug)]
+struct SelectorChain {
+    compounds: Vec<Compound>,
+    combinators: Vec<Combinator>,
+}
+
+/// `.a.b` や `&.b` のような結合子を含まないセレクタ
+#[derive(Debug)]
+struct Compound {
+    /// `&` を含むか
+    nesting: bool,
+    selectors: Vec<VSSSelector>,
 }
 
-fn parse_vss_selector(input: &str) -> IResult<&str, VSSSelectorTree> {
-    enum Operator {
-        Descendant,
-        Child,
-        Sibling,
-        AdjSibling,
+impl SelectorChain {
+    fn into_tree(self) -> VSSSelectorTree {
+        build_selector_tree(
+            self.compounds
+                .into_iter()
+                .map(|compound| compound.selectors)
+                .collect(),
+            self.combinators,
+        )
     }
+
+    /// `&` を親のセレクタに置き換える
+    /// `&` を含まない場合は、`& ` が先頭にあるものとみなして親の子孫を指す
+    fn nest(&self, parent: &VSSSelectorTree) -> VSSSelectorTree {
+        let implicit_nesting = Compound {
+            nesting: true,
+            selectors: vec![],
+        };
+        let (source_compounds, source_combinators): (Vec<_>, Vec<_>) =
+            if self.compounds.iter().any(|compound| compound.nesting) {
+                (self.compounds.iter().collect(), self.combinators.clone())
+            } else {
+                (
+                    std::iter::once(&implicit_nesting)
+                        .chain(&self.compounds)
+                        .collect(),
+                    std::iter::once(Combinator::Descendant)
+                        .chain(self.combinators.iter().copied())
+                        .collect(),
+                )
+            };
+        let mut compounds = vec![];
+        let mut combinators = vec![];
+        for (i, compound) in source_compounds.into_iter().enumerate() {
+            if i > 0 {
+                combinators.push(source_combinators[i - 1]);
+            }
+            if compound.nesting {
+                let (mut parent_compounds, parent_combinators) = flatten_selector_tree(parent);
+                let mut last = parent_compounds.pop().unwrap();
+                last.extend(compound.selectors.iter().cloned());
+                compounds.extend(parent_compounds);
+                compounds.push(last);
+                combinators.extend(parent_combinators);
+            } else {
+                compounds.push(compound.selectors.clone());
+            }
+        }
+        build_selector_tree(compounds, combinators)
+    }
+}
+
+fn build_selector_tree(
+    mut compounds: Vec<Vec<VSSSelector>>,
+    combinators: Vec<Combinator>,
+) -> VSSSelectorTree {
+    let last = VSSSelectorTree::Selectors(compounds.pop().unwrap());
+    compounds
+        .into_iter()
+        .zip(combinators)
+        .rev()
+        .fold(last, |acc, (selectors, combinator)| match combinator {
+            Combinator::Descendant => VSSSelectorTree::Descendant(selectors, Box::new(acc)),
+            Combinator::Child => VSSSelectorTree::Child(selectors, Box::new(acc)),
+            Combinator::Sibling => VSSSelectorTree::Sibling(selectors, Box::new(acc)),
+            Combinator::AdjSibling => VSSSelectorTree::AdjSibling(selectors, Box::new(acc)),
+        })
+}
+
+fn flatten_selector_tree(tree: &VSSSelectorTree) -> (Vec<Vec<VSSSelector>>, Vec<Combinator>) {
+    let mut compounds = vec![];
+    let mut combinators = vec![];
+    let mut tree = tree;
+    loop {
+        let (selectors, combinator, rest) = match tree {
+            VSSSelectorTree::Selectors(selectors) => {
+                compounds.push(selectors.clone());
+                return (compounds, combinators);
+            }
+            VSSSelectorTree::Descendant(selectors, rest) => {
+                (selectors, Combinator::Descendant, rest)
+            }
+            VSSSelectorTree::Child(selectors, rest) => (selectors, Combinator::Child, rest),
+            VSSSelectorTree::Sibling(selectors, rest) => (selectors, Combinator::Sibling, rest),
+            VSSSelectorTree::AdjSibling(selectors, rest) => {
+                (selectors, Combinator::AdjSibling, rest)
+            }
+        };
+        compounds.push(selectors.clone());
+        combinators.push(combinator);
+        tree = rest;
+    }
+}
+
+/// `nested` がtrueの場合は、ネストされたルールのセレクタとして `&` や先頭の結合子を受け付ける
+fn parse_vss_selector_chain(input: &str, nested: bool) -> VSSResult<'_, SelectorChain> {
     let (mut i, _) = skip_comment_or_whitespace(input)?;
-    let mut buffer = Vec::new();
+    let mut compounds = vec![];
+    let mut combinators = vec![];
+    // `> txt` のように結合子から始まる場合は、`&` が省略されているとみなす
+    if nested && let Ok((input, combinator)) = parse_combinator(i) {
+        compounds.push(Compound {
+            nesting: true,
+            selectors: vec![],
+        });
+        combinators.push(combinator);
+        (i, _) = skip_comment_or_whitespace(input)?;
+    }
     loop {
-        let (input, selectors) = parse_vss_selector_selectors(i)?;
-        let (input, op) = alt((tag("+"), tag(">"), tag("~"), success(""))).parse(input)?;
-        let op = match op {
-            ">" => Operator::Child,
-            "+" => Operator::Sibling,
-            "~" => Operator::AdjSibling,
-            "" => {
+        let (input, nesting) = match tag::<_, _, ()>("&")(i) {
+            Ok((input, _)) if nested => (input, true),
+            Ok(_) => return fail(i, FailureKind::InvalidSelector),
+            Err(_) => (i, false),
+        };
+        let (input, selectors) = match parse_vss_selector_selectors(input) {
+            Ok(result) => result,
+            // `&` のみの場合
+            Err(nom::Err::Error(_)) if nesting => (skip_comment_or_whitespace(input)?.0, vec![]),
+            Err(nom::Err::Error(_)) => {
+                // セレクタの途中で宣言やブロックの終わりが来た場合は `{` の書き忘れとみなす
+                let kind = if i.is_empty() || i.starts_with([';', ':', '}']) {
+                    FailureKind::MissingOpenBrace
+                } else {
+                    FailureKind::InvalidSelector
+                };
+                return fail(i, kind);
+            }
+            Err(e) => return Err(e),
+        };
+        compounds.push(Compound { nesting, selectors });
+        let combinator = match parse_combinator(input) {
+            Ok((input, combinator)) => {
+                i = input;
+                combinator
+            }
+            Err(_) => {
                 if peek(alt((tag::<_, _, ()>(","), tag("{"))))
                     .parse(input)
                     .is_ok()
                 {
                     return Ok((
                         input,
-                        buffer.into_iter().rev().fold(
-                            VSSSelectorTree::Selectors(selectors),
-                            |acc, (selectors, op)| match op {
-                                Operator::Descendant => {
-                                    VSSSelectorTree::Descendant(selectors, Box::new(acc))
-                                }
-                                Operator::Child => VSSSelectorTree::Child(selectors, Box::new(acc)),
-                                Operator::Sibling => {
-                                    VSSSelectorTree::Sibling(selectors, Box::new(acc))
-                                }
-                                Operator::AdjSibling => {
-                                    VSSSelectorTree::AdjSibling(selectors, Box::new(acc))
-                                }
-                            },
-                        ),
+                        SelectorChain {
+                            compounds,
+                            combinators,
+                        },
                     ));
                 }
-                Operator::Descendant
+                i = input;
+                Combinator::Descendant
             }
-            _ => unreachable!(),
         };
-        buffer.push((selectors, op));
-        let (input, ()) = skip_comment_or_whitespace(input)?;
+        combinators.push(combinator);
+        let (input, ()) = skip_comment_or_whitespace(i)?;
         i = input;
     }
 }
 
-fn parse_vss_selector_selectors(input: &str) -> IResult<&str, Vec<VSSSelector>> {
+fn parse_combinator(input: &str) -> VSSResult<'_, Combinator> {
+    alt((
+        map(tag(">"), |_| Combinator::Child),
+        map(tag("+"), |_| Combinator::AdjSibling),
+        map(tag("~"), |_| Combinator::Sibling),
+    ))
+    .parse(input)
+}
+
+fn parse_vss_selector_selectors(input: &str) -> VSSResult<'_, Vec<VSSSelector>> {
     macro_rules! make_identifier_rule {
         ($head:literal) => {
             concat!("^", $head, "(?:[_a-zA-Z]|-[a-zA-Z_-])[-_0-9a-zA-Z]*")
         };
     }
-    // TODO: Attributeに対応する
     static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(make_identifier_rule!("")).unwrap());
     static CLASS: LazyLock<Regex> =
         LazyLock::new(|| Regex::new(make_identifier_rule!("\\.")).unwrap());
@@ -127,51 +689,181 @@ fn parse_vss_selector_selectors(input: &str) -> IResult<&str, Vec<VSSSelector>>
     static PSEUDO_CLASS: LazyLock<Regex> =
         LazyLock::new(|| Regex::new(make_identifier_rule!(":{1,2}")).unwrap());
     terminated(
-        many1(map(
-            terminated(
-                alt((
-                    tag("*"),
-                    regex_matches(&TAG),
-                    regex_matches(&CLASS),
-                    regex_matches(&ID),
-                    regex_matches(&PSEUDO_CLASS),
-                )),
-                skip_comments,
-            ),
-            |selector| {
-                if selector == "*" {
-                    VSSSelector::All
-                } else if let Some(class_name) = selector.strip_prefix(".") {
-                    VSSSelector::Class(class_name.to_owned())
-                } else if let Some(id) = selector.strip_prefix("#") {
-                    VSSSelector::Id(id.to_owned())
-                } else if let Some(pseudo_class) = selector.strip_prefix(":") {
-                    VSSSelector::PseudoClass(pseudo_class.to_owned())
-                } else {
-                    VSSSelector::Tag(selector.to_owned())
-                }
-            },
+        many1(terminated(
+            alt((
+                parse_vss_selector_pseudo_class_function,
+                map(
+                    alt((
+                        tag("*"),
+                        regex_matches(&TAG),
+                        regex_matches(&CLASS),
+                        regex_matches(&ID),
+                        regex_matches(&PSEUDO_CLASS),
+                    )),
+                    |selector| {
+                        if selector == "*" {
+                            VSSSelector::All
+                        } else if let Some(class_name) = selector.strip_prefix(".") {
+                            VSSSelector::Class(class_name.to_owned())
+                        } else if let Some(id) = selector.strip_prefix("#") {
+                            VSSSelector::Id(id.to_owned())
+                        } else if let Some(pseudo_class) = selector.strip_prefix(":") {
+                            VSSSelector::PseudoClass(pseudo_class.to_owned())
+                        } else {
+                            VSSSelector::Tag(selector.to_owned())
+                        }
+                    },
+                ),
+                parse_vss_selector_attribute,
+            )),
+            skip_comments,
         )),
         skip_comment_or_whitespace,
     )
     .parse(input)
 }
 
+// :nth-child(<an+b>) or :not(<selectors>)
+fn parse_vss_selector_pseudo_class_function(input: &str) -> VSSResult<'_, VSSSelector> {
+    let (input, name) = alt((tag(":nth-child("), tag(":not("))).parse(input)?;
+    // `(` 以降は引数として解釈できなければエラーとする
+    let (input, _) = skip_comment_or_whitespace(input)?;
+    let (input, selector) = if name == ":not(" {
+        let Ok((input, selectors)) = parse_vss_selector_selectors(input) else {
+            return fail(input, FailureKind::InvalidSelector);
+        };
+        (input, VSSSelector::Not(selectors))
+    } else {
+        let Some((input, (a, b))) = parse_nth(input) else {
+            return fail(input, FailureKind::InvalidSelector);
+        };
+        let (input, _) = skip_comment_or_whitespace(input)?;
+        (input, VSSSelector::NthChild(a, b))
+    };
+    let Ok((input, _)) = tag::<_, _, ()>(")")(input) else {
+        return fail(input, FailureKind::InvalidSelector);
+    };
+    Ok((input, selector))
+}
+
+/// `odd`, `even`, `3`, `2n+1`, `-n + 3` のような `an+b` の記法を `(a, b)` として読み取る
+fn parse_nth(input: &str) -> Option<(&str, (i32, i32))> {
+    static NTH: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^(?:(odd)|(even)|([+-]?[0-9]*)n(?:\s*([+-])\s*([0-9]+))?|([+-]?[0-9]+))")
+            .unwrap()
+    });
+    let captures = NTH.captures(input)?;
+    let rest = &input[captures[0].len()..];
+    // `evenly` のように識別子の一部であれば `an+b` とはみなさない
+    if rest.starts_with(|c: char| c == '-' || c == '_' || c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let nth = if captures.get(1).is_some() {
+        (2, 1)
+    } else if captures.get(2).is_some() {
+        (2, 0)
+    } else if let Some(b) = captures.get(6) {
+        (0, b.as_str().parse().ok()?)
+    } else {
+        let a = match &captures[3] {
+            "" | "+" => 1,
+            "-" => -1,
+            a => a.parse().ok()?,
+        };
+        let b = match (captures.get(4), captures.get(5)) {
+            (Some(sign), Some(b)) => {
+                let b: i32 = b.as_str().parse().ok()?;
+                if sign.as_str() == "-" { -b } else { b }
+            }
+            _ => 0,
+        };
+        (a, b)
+    };
+    Some((rest, nth))
+}
+
+// [<name>] or [<name><operator><value>]
+fn parse_vss_selector_attribute(input: &str) -> VSSResult<'_, VSSSelector> {
+    static NAME: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new("^[_a-zA-Z][-_0-9a-zA-Z]*").unwrap());
+    static IDENTIFIER_VALUE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new("^(?:[_a-zA-Z]|-[a-zA-Z_-])[-_0-9a-zA-Z]*").unwrap());
+    static QUOTE_STRING: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"^(?:"(?:[^\\"]|\\.)*"|'(?:[^\\']|\\.)*')"#).unwrap());
+    let (input, _) = tag("[")(input)?;
+    // `[` 以降はアトリビュートセレクタとして解釈できなければエラーとする
+    let (input, _) = skip_comment_or_whitespace(input)?;
+    let Ok((input, name)) = regex_matches(&NAME)(input) else {
+        return fail(input, FailureKind::InvalidSelector);
+    };
+    let (input, _) = skip_comment_or_whitespace(input)?;
+    if let Ok((input, _)) = tag::<_, _, ()>("]")(input) {
+        return Ok((
+            input,
+            VSSSelector::Attribute(name.to_owned(), VSSSelectorAttributeValue::None),
+        ));
+    }
+    let Ok((input, operator)) = alt((
+        tag::<_, _, ()>("="),
+        tag("~="),
+        tag("^="),
+        tag("$="),
+        tag("*="),
+    ))
+    .parse(input) else {
+        return fail(input, FailureKind::InvalidSelector);
+    };
+    let (input, _) = skip_comment_or_whitespace(input)?;
+    let (input, value) = if let Ok((input, quoted)) = regex_matches(&QUOTE_STRING)(input) {
+        (input, unescape(&quoted[1..quoted.len() - 1]))
+    } else if let Ok((input, identifier)) = regex_matches(&IDENTIFIER_VALUE)(input) {
+        (input, identifier.to_owned())
+    } else {
+        return fail(input, FailureKind::InvalidSelector);
+    };
+    let (input, _) = skip_comment_or_whitespace(input)?;
+    let Ok((input, _)) = tag::<_, _, ()>("]")(input) else {
+        return fail(input, FailureKind::InvalidSelector);
+    };
+    let value = match operator {
+        "=" => VSSSelectorAttributeValue::Equal(value),
+        "~=" => VSSSelectorAttributeValue::Contain(value),
+        "^=" => VSSSelectorAttributeValue::StartWith(value),
+        "$=" => VSSSelectorAttributeValue::EndWith(value),
+        "*=" => VSSSelectorAttributeValue::Include(value),
+        _ => unreachable!(),
+    };
+    Ok((input, VSSSelector::Attribute(name.to_owned(), value)))
+}
+
+/// クォート内のバックスラッシュによるエスケープを解除する
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.extend(chars.next()),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
 // <property>: <value>
-fn parse_vss_rule(input: &str) -> IResult<&str, Rule> {
+fn parse_vss_rule<'a>(locator: &Locator, input: &'a str) -> VSSResult<'a, Rule> {
+    // `--` で始まるカスタムプロパティは数字や `_` も使える
+    static PROPERTY: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^(?:--[-_a-zA-Z0-9]+|[a-zA-Z-]+)").unwrap());
     let (input, _) = skip_comment_or_whitespace(input)?;
-    let (input, property) = map(
-        regex_matches(&Regex::new(r"^[a-zA-Z-]+").unwrap()),
-        |s: &str| s.to_owned(),
-    )
-    .parse(input)?;
+    let start = input;
+    let (input, property) = map(regex_matches(&PROPERTY), |s: &str| s.to_owned()).parse(input)?;
     let (input, _) = skip_comment_or_whitespace(input)?;
     let (input, _) = tag(":")(input)?;
     let (input, _) = skip_comment_or_whitespace(input)?;
     static QUOTE_STRING: LazyLock<Regex> =
         LazyLock::new(|| Regex::new(r#"^"((?:[^\\"]+|\\.)*)""#).unwrap());
     static COMMON_VALUE: LazyLock<Regex> =
-        LazyLock::new(|| Regex::new("^[a-zA-Z0-9-()%.#,]").unwrap());
+        LazyLock::new(|| Regex::new("^[a-zA-Z0-9-()%.#,_]").unwrap());
     static SPACES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"^\s+"#).unwrap());
     let mut iter = iterator(
         input,
@@ -201,11 +893,33 @@ fn parse_vss_rule(input: &str) -> IResult<&str, Rule> {
         acc
     });
     let (input, _) = iter.finish()?;
+    static IMPORTANT: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^!\s*(?i:important)").unwrap());
+    let (input, important) = match regex_matches(&IMPORTANT)(input) {
+        Ok((input, _)) => (input, true),
+        Err(_) => (input, false),
+    };
+    let value = if important {
+        value.trim_end().to_owned()
+    } else {
+        value
+    };
+    // 値の末尾の空白は範囲に含めない
+    let source = start[..start.len() - input.len()].trim_end();
+    let span = locator.span(start, &start[source.len()..]);
 
-    Ok((input, Rule { property, value }))
+    Ok((
+        input,
+        Rule {
+            property,
+            value,
+            important,
+            span,
+        },
+    ))
 }
 
-fn skip_comment_or_whitespace(mut input: &str) -> IResult<&str, ()> {
+fn skip_comment_or_whitespace(mut input: &str) -> VSSResult<'_, ()> {
     let mut len = input.len();
     loop {
         (input, _) = alt((
@@ -215,8 +929,7 @@ fn skip_comment_or_whitespace(mut input: &str) -> IResult<&str, ()> {
             skip_comments,
             success(()),
         ))
-        .parse(input)
-        .unwrap();
+        .parse(input)?;
         if input.len() == len {
             return Ok((input, ()));
         }
@@ -224,33 +937,28 @@ fn skip_comment_or_whitespace(mut input: &str) -> IResult<&str, ()> {
     }
 }
 
-fn skip_comments(input: &str) -> IResult<&str, ()> {
+fn skip_comments(input: &str) -> VSSResult<'_, ()> {
     let mut i = input;
     loop {
-        let Ok((mut input, _)) = tag::<_, _, ()>("/*")(i) else {
+        let Ok((input, _)) = tag::<_, _, ()>("/*")(i) else {
             return Ok((i, ()));
         };
-        loop {
-            (input, _) = nom::bytes::complete::is_not("*")(input)?;
-            let Ok((input, _)) = tag::<_, _, ()>("*/")(input) else {
-                (input, _) = tag("*")(input)?;
-                continue;
-            };
-            i = input;
-            break;
-        }
+        let Some(end) = input.find("*/") else {
+            return fail(i, FailureKind::UnterminatedComment);
+        };
+        i = &input[end + 2..];
     }
 }
 
-fn regex_matches(regex: &Regex) -> impl for<'a> Fn(&'a str) -> IResult<&'a str, &'a str> + '_ {
+fn regex_matches(regex: &Regex) -> impl for<'a> Fn(&'a str) -> VSSResult<'a, &'a str> + '_ {
     move |input| {
         if let Some(capture) = regex.find(input) {
             let matches_str = capture.as_str();
             Ok((&input[matches_str.len()..], matches_str))
         } else {
-            Err(nom::Err::Error(nom::error::Error::new(
+            Err(nom::Err::Error(Failure::from_error_kind(
                 input,
-                nom::error::ErrorKind::RegexpMatch,
+                ErrorKind::RegexpMatch,
             )))
         }
     }
@@ -259,7 +967,16 @@ fn regex_matches(regex: &Regex) -> impl for<'a> Fn(&'a str) -> IResult<&'a str,
 #[cfg(test)]
 mod tests {
     use super::*;
-    use vsml_ast::vss::{Rule, VSSItem, VSSSelector, VSSSelectorTree};
+    use vsml_ast::vss::{Rule, VSSItem, VSSSelector, VSSSelectorAttributeValue, VSSSelectorTree};
+
+    fn parse_items(vss: &str, origin: Position) -> Result<Vec<VSSItem>, VSSParseError> {
+        parse(vss, SourceId::VSML, origin).map(|style_sheet| style_sheet.items)
+    }
+
+    fn parse_vss_selector(input: &str) -> VSSResult<'_, VSSSelectorTree> {
+        let (input, chain) = parse_vss_selector_chain(input, false)?;
+        Ok((input, chain.into_tree()))
+    }
 
     #[test]
     fn test_skip_comment() {
@@ -272,12 +989,13 @@ mod tests {
             Ok((" /* 10 * 2 = 20 */", ()))
         );
         assert!(skip_comments("/* 10 * 2 = 20").is_err());
+        assert_eq!(skip_comments("/**/hoge"), Ok(("hoge", ())));
     }
 
     #[test]
     fn test_parse() {
         assert_eq!(
-            parse(
+            parse_items(
                 "
             seq {
               duration: 1s;
@@ -289,6 +1007,7 @@ mod tests {
             #main-frame {
               width: 100rh;
             }",
+                Position::START,
             ),
             Ok(vec![
                 VSSItem {
@@ -297,8 +1016,11 @@ mod tests {
                     )])],
                     rules: vec![Rule {
                         property: "duration".to_string(),
-                        value: "1s".to_string()
-                    },]
+                        value: "1s".to_string(),
+                        important: false,
+                        span: Span::new(Position::new(33, 3, 15), Position::new(45, 3, 27)),
+                    },],
+                    span: Span::new(Position::new(13, 2, 13), Position::new(60, 4, 14)),
                 },
                 VSSItem {
                     selectors: vec![VSSSelectorTree::Descendant(
@@ -310,13 +1032,18 @@ mod tests {
                     rules: vec![
                         Rule {
                             property: "font-size".to_string(),
-                            value: "20px".to_string()
+                            value: "20px".to_string(),
+                            important: false,
+                            span: Span::new(Position::new(114, 6, 15), Position::new(129, 6, 30)),
                         },
                         Rule {
                             property: "font-border-color".to_string(),
-                            value: "red".to_string()
+                            value: "red".to_string(),
+                            important: false,
+                            span: Span::new(Position::new(145, 7, 15), Position::new(167, 7, 37)),
                         },
-                    ]
+                    ],
+                    span: Span::new(Position::new(73, 5, 13), Position::new(182, 8, 14)),
                 },
                 VSSItem {
                     selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Id(
@@ -324,10 +1051,501 @@ mod tests {
                     )])],
                     rules: vec![Rule {
                         property: "width".to_string(),
-                        value: "100rh".to_string()
-                    },]
+                        value: "100rh".to_string(),
+                        important: false,
+                        span: Span::new(Position::new(223, 10, 15), Position::new(235, 10, 27)),
+                    },],
+                    span: Span::new(Position::new(195, 9, 13), Position::new(250, 11, 14)),
                 }
             ])
         );
     }
+
+    #[test]
+    fn test_parse_attribute_selector() {
+        assert_eq!(
+            parse_vss_selector_selectors("vid[src]"),
+            Ok((
+                "",
+                vec![
+                    VSSSelector::Tag("vid".to_string()),
+                    VSSSelector::Attribute("src".to_string(), VSSSelectorAttributeValue::None),
+                ]
+            ))
+        );
+        assert_eq!(
+            parse_vss_selector_selectors(
+                r#"[src = "video.mp4"][class~=main][src^='a\'b'][src$=".mp4"][src*=deo] "#
+            ),
+            Ok((
+                "",
+                vec![
+                    VSSSelector::Attribute(
+                        "src".to_string(),
+                        VSSSelectorAttributeValue::Equal("video.mp4".to_string())
+                    ),
+                    VSSSelector::Attribute(
+                        "class".to_string(),
+                        VSSSelectorAttributeValue::Contain("main".to_string())
+                    ),
+                    VSSSelector::Attribute(
+                        "src".to_string(),
+                        VSSSelectorAttributeValue::StartWith("a'b".to_string())
+                    ),
+                    VSSSelector::Attribute(
+                        "src".to_string(),
+                        VSSSelectorAttributeValue::EndWith(".mp4".to_string())
+                    ),
+                    VSSSelector::Attribute(
+                        "src".to_string(),
+                        VSSSelectorAttributeValue::Include("deo".to_string())
+                    ),
+                ]
+            ))
+        );
+        assert_eq!(
+            parse_items("vid[src=1] {}", Position::START),
+            Err(VSSParseError::InvalidSelector(
+                Position::new(8, 1, 9),
+                "1]".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_items("vid[src {}", Position::START),
+            Err(VSSParseError::InvalidSelector(
+                Position::new(8, 1, 9),
+                "{".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_pseudo_class_selector() {
+        assert_eq!(
+            parse_vss_selector_selectors("prl:first-child:not(.bgm#main) "),
+            Ok((
+                "",
+                vec![
+                    VSSSelector::Tag("prl".to_string()),
+                    VSSSelector::PseudoClass("first-child".to_string()),
+                    VSSSelector::Not(vec![
+                        VSSSelector::Class("bgm".to_string()),
+                        VSSSelector::Id("main".to_string()),
+                    ]),
+                ]
+            ))
+        );
+        for (nth, expected) in [
+            ("odd", (2, 1)),
+            ("even", (2, 0)),
+            ("3", (0, 3)),
+            ("2n", (2, 0)),
+            ("2n+1", (2, 1)),
+            (" -n + 3 ", (-1, 3)),
+            ("+n-2", (1, -2)),
+        ] {
+            assert_eq!(
+                parse_vss_selector_selectors(&format!(":nth-child({nth})")),
+                Ok(("", vec![VSSSelector::NthChild(expected.0, expected.1)])),
+                "{nth}"
+            );
+        }
+        assert_eq!(
+            parse_items("prl:nth-child(evenly) {}", Position::START),
+            Err(VSSParseError::InvalidSelector(
+                Position::new(14, 1, 15),
+                "evenly)".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_items("prl:not() {}", Position::START),
+            Err(VSSParseError::InvalidSelector(
+                Position::new(8, 1, 9),
+                ")".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_sibling_selector() {
+        assert_eq!(
+            parse_vss_selector("txt + aud ~ vid {"),
+            Ok((
+                "{",
+                VSSSelectorTree::AdjSibling(
+                    vec![VSSSelector::Tag("txt".to_string())],
+                    Box::new(VSSSelectorTree::Sibling(
+                        vec![VSSSelector::Tag("aud".to_string())],
+                        Box::new(VSSSelectorTree::Selectors(vec![VSSSelector::Tag(
+                            "vid".to_string()
+                        )])),
+                    )),
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_important() {
+        assert_eq!(
+            parse_items("txt { font-color: red ! IMPORTANT; }", Position::START),
+            Ok(vec![VSSItem {
+                selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Tag(
+                    "txt".to_string()
+                )])],
+                rules: vec![Rule {
+                    property: "font-color".to_string(),
+                    value: "red".to_string(),
+                    important: true,
+                    span: Span::new(Position::new(6, 1, 7), Position::new(33, 1, 34)),
+                }],
+                span: Span::new(Position::new(0, 1, 1), Position::new(36, 1, 37)),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_parse_custom_property() {
+        assert_eq!(
+            parse_rules(
+                "--accent_1: #ffcc00; font-color: var(--accent_1, red)",
+                Position::START
+            ),
+            Ok(vec![
+                Rule {
+                    property: "--accent_1".to_string(),
+                    value: "#ffcc00".to_string(),
+                    important: false,
+                    span: Span::new(Position::new(0, 1, 1), Position::new(19, 1, 20)),
+                },
+                Rule {
+                    property: "font-color".to_string(),
+                    value: "var(--accent_1, red)".to_string(),
+                    important: false,
+                    span: Span::new(Position::new(21, 1, 22), Position::new(53, 1, 54)),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_import() {
+        assert_eq!(
+            parse(
+                "@import \"theme/base.vss\";\n@import 'colors.vss' ;\nseq { duration: 1s; }",
+                SourceId(1),
+                Position::START
+            ),
+            Ok(StyleSheet {
+                imports: vec!["theme/base.vss".to_string(), "colors.vss".to_string()],
+                items: vec![VSSItem {
+                    selectors: vec![VSSSelectorTree::Selectors(vec![VSSSelector::Tag(
+                        "seq".to_string()
+                    )])],
+                    rules: vec![Rule {
+                        property: "duration".to_string(),
+                        value: "1s".to_string(),
+                        important: false,
+                        span: Span::new(Position::new(55, 3, 7), Position::new(67, 3, 19))
+                            .with_source(SourceId(1)),
+                    }],
+                    span: Span::new(Position::new(49, 3, 1), Position::new(70, 3, 22))
+                        .with_source(SourceId(1)),
+                }],
+                keyframes: vec![],
+            })
+        );
+        assert_eq!(
+            parse("@import theme.vss;", SourceId::VSML, Position::START),
+            Err(VSSParseError::UnexpectedToken(
+                Position::new(8, 1, 9),
+                "theme.vss".to_string()
+            ))
+        );
+        // `@import` は他のルールより前にのみ記述できる
+        assert_eq!(
+            parse(
+                "seq {}\n@import \"theme.vss\";",
+                SourceId::VSML,
+                Position::START
+            ),
+            Err(VSSParseError::UnexpectedToken(
+                Position::new(7, 2, 1),
+                "@import".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_keyframes() {
+        let style_sheet = parse(
+            "txt { animation: slide 1s; }\n@keyframes slide {\n  from { x: 0; }\n  50%, 75.5% { x: 100px; font-color: red; }\n  to {}\n}",
+            SourceId::VSML,
+            Position::START,
+        )
+        .unwrap();
+        assert_eq!(style_sheet.items.len(), 1);
+        assert_eq!(
+            style_sheet.keyframes,
+            vec![VSSKeyframes {
+                name: "slide".to_string(),
+                keyframes: vec![
+                    VSSKeyframe {
+                        offsets: vec![0.0],
+                        rules: vec![Rule {
+                            property: "x".to_string(),
+                            value: "0".to_string(),
+                            important: false,
+                            span: Span::new(Position::new(57, 3, 10), Position::new(61, 3, 14)),
+                        }],
+                        span: Span::new(Position::new(50, 3, 3), Position::new(64, 3, 17)),
+                    },
+                    VSSKeyframe {
+                        offsets: vec![50.0, 75.5],
+                        rules: vec![
+                            Rule {
+                                property: "x".to_string(),
+                                value: "100px".to_string(),
+                                important: false,
+                                span: Span::new(Position::new(80, 4, 16), Position::new(88, 4, 24)),
+                            },
+                            Rule {
+                                property: "font-color".to_string(),
+                                value: "red".to_string(),
+                                important: false,
+                                span: Span::new(
+                                    Position::new(90, 4, 26),
+                                    Position::new(105, 4, 41)
+                                ),
+                            },
+                        ],
+                        span: Span::new(Position::new(67, 4, 3), Position::new(108, 4, 44)),
+                    },
+                    VSSKeyframe {
+                        offsets: vec![100.0],
+                        rules: vec![],
+                        span: Span::new(Position::new(111, 5, 3), Position::new(116, 5, 8)),
+                    },
+                ],
+                span: Span::new(Position::new(29, 2, 1), Position::new(118, 6, 2)),
+            }]
+        );
+
+        assert_eq!(
+            parse(
+                "@keyframes slide { 120% {} }",
+                SourceId::VSML,
+                Position::START
+            ),
+            Err(VSSParseError::InvalidSelector(
+                Position::new(19, 1, 20),
+                "120%".to_string()
+            ))
+        );
+        assert_eq!(
+            parse(
+                "@keyframes slide { from { x: 0; }",
+                SourceId::VSML,
+                Position::START
+            ),
+            Err(VSSParseError::MissingCloseBrace(Position::new(33, 1, 34)))
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_item() {
+        let items = parse_items(
+            ".scene-intro, #main {
+              font-size: 20px;
+              txt { duration: 1s; }
+              & > aud, &.active:first-child {
+                + vid { duration: 2s; }
+              }
+            }",
+            Position::START,
+        )
+        .unwrap();
+        let class = |name: &str| VSSSelector::Class(name.to_string());
+        let id = |name: &str| VSSSelector::Id(name.to_string());
+        let tag = |name: &str| VSSSelector::Tag(name.to_string());
+        let first_child = || VSSSelector::PseudoClass("first-child".to_string());
+        let selectors = items
+            .iter()
+            .map(|item| (item.selectors.clone(), item.rules.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            selectors,
+            vec![
+                (
+                    vec![
+                        VSSSelectorTree::Selectors(vec![class("scene-intro")]),
+                        VSSSelectorTree::Selectors(vec![id("main")]),
+                    ],
+                    1
+                ),
+                (
+                    vec![
+                        VSSSelectorTree::Descendant(
+                            vec![class("scene-intro")],
+                            Box::new(VSSSelectorTree::Selectors(vec![tag("txt")]))
+                        ),
+                        VSSSelectorTree::Descendant(
+                            vec![id("main")],
+                            Box::new(VSSSelectorTree::Selectors(vec![tag("txt")]))
+                        ),
+                    ],
+                    1
+                ),
+                (
+                    vec![
+                        VSSSelectorTree::Child(
+                            vec![class("scene-intro")],
+                            Box::new(VSSSelectorTree::Selectors(vec![tag("aud")]))
+                        ),
+                        VSSSelectorTree::Child(
+                            vec![id("main")],
+                            Box::new(VSSSelectorTree::Selectors(vec![tag("aud")]))
+                        ),
+                        VSSSelectorTree::Selectors(vec![
+                            class("scene-intro"),
+                            class("active"),
+                            first_child()
+                        ]),
+                        VSSSelectorTree::Selectors(vec![
+                            id("main"),
+                            class("active"),
+                            first_child()
+                        ]),
+                    ],
+                    0
+                ),
+                (
+                    vec![
+                        VSSSelectorTree::Child(
+                            vec![class("scene-intro")],
+                            Box::new(VSSSelectorTree::AdjSibling(
+                                vec![tag("aud")],
+                                Box::new(VSSSelectorTree::Selectors(vec![tag("vid")]))
+                            ))
+                        ),
+                        VSSSelectorTree::Child(
+                            vec![id("main")],
+                            Box::new(VSSSelectorTree::AdjSibling(
+                                vec![tag("aud")],
+                                Box::new(VSSSelectorTree::Selectors(vec![tag("vid")]))
+                            ))
+                        ),
+                        VSSSelectorTree::AdjSibling(
+                            vec![class("scene-intro"), class("active"), first_child()],
+                            Box::new(VSSSelectorTree::Selectors(vec![tag("vid")]))
+                        ),
+                        VSSSelectorTree::AdjSibling(
+                            vec![id("main"), class("active"), first_child()],
+                            Box::new(VSSSelectorTree::Selectors(vec![tag("vid")]))
+                        ),
+                    ],
+                    1
+                ),
+            ]
+        );
+        // ネストされたルールの範囲はそのセレクタから閉じ括弧まで
+        assert_eq!(
+            items[1].span,
+            Span::new(Position::new(67, 3, 15), Position::new(88, 3, 36))
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_item_with_nesting_selector_in_middle() {
+        assert_eq!(
+            parse_items("txt { .dark & { font-color: white; } }", Position::START).unwrap()[1]
+                .selectors,
+            vec![VSSSelectorTree::Descendant(
+                vec![VSSSelector::Class("dark".to_string())],
+                Box::new(VSSSelectorTree::Selectors(vec![VSSSelector::Tag(
+                    "txt".to_string()
+                )]))
+            )]
+        );
+        // `&` はネストされたルールでのみ使える
+        assert_eq!(
+            parse_items("& txt {}", Position::START),
+            Err(VSSParseError::InvalidSelector(
+                Position::new(0, 1, 1),
+                "&".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_unexpected_token() {
+        assert_eq!(
+            parse_items("seq {\n  duration 1s;\n}", Position::START),
+            Err(VSSParseError::UnexpectedToken(
+                Position::new(17, 2, 12),
+                "1s".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_items("seq { duration: 1s; }\n}", Position::START),
+            Err(VSSParseError::UnexpectedToken(
+                Position::new(22, 2, 1),
+                "}".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_unterminated_comment() {
+        assert_eq!(
+            parse_items("seq {\n  /* duration: 1s;\n}", Position::START),
+            Err(VSSParseError::UnterminatedComment(Position::new(8, 2, 3)))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_missing_brace() {
+        assert_eq!(
+            parse_items("seq duration: 1s; }", Position::START),
+            Err(VSSParseError::MissingOpenBrace(Position::new(12, 1, 13)))
+        );
+        assert_eq!(
+            parse_items("seq {\n  duration: 1s;\n", Position::START),
+            Err(VSSParseError::MissingCloseBrace(Position::new(22, 3, 1)))
+        );
+        assert_eq!(
+            parse_items("seq {\n  duration: 1s\n", Position::START),
+            Err(VSSParseError::MissingCloseBrace(Position::new(21, 3, 1)))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_invalid_selector() {
+        assert_eq!(
+            parse_items("seq, 1prl { duration: 1s; }", Position::START),
+            Err(VSSParseError::InvalidSelector(
+                Position::new(5, 1, 6),
+                "1prl".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_position_with_origin() {
+        // VSMLファイル中の14行目5列目からVSSが始まる場合
+        assert_eq!(
+            parse_items("seq {\n  duration 1s;\n}", Position::new(100, 14, 5)),
+            Err(VSSParseError::UnexpectedToken(
+                Position::new(117, 15, 12),
+                "1s".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_items("seq $ {}", Position::new(100, 14, 5)),
+            Err(VSSParseError::InvalidSelector(
+                Position::new(104, 14, 9),
+                "$".to_string()
+            ))
+        );
+    }
 }
@@ -1,10 +1,10 @@
 #[cfg(test)]
 mod tests;
 
-use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping, SwashCache, fontdb};
+use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping, SwashCache, Wrap, fontdb};
 use std::sync::RwLock;
 use vsml_common_image::Image as VsmlImage;
-use vsml_core::schemas::{Color, RectSize, TextData, TextStyleData};
+use vsml_core::schemas::{Color, LineBreak, RectSize, TextData, TextStyleData, WordBreak};
 
 #[derive(Debug)]
 struct TextBounds {
@@ -101,14 +101,7 @@ impl TextRendererContext {
         let TextData { text, style } = &text_data[0];
 
         // レイアウトを計算する
-        let font_family = Self::get_font_family_from_style(style);
-        let font_size = style.font_size;
-        let line_height =
-            calculate_line_height_from_font(&font_system, &vec![font_family], font_size);
-        let mut buffer = Buffer::new(&mut font_system, Metrics::new(font_size, line_height));
-        let attrs = Attrs::new().family(font_family);
-        buffer.set_text(&mut font_system, text, &attrs, Shaping::Advanced, None);
-        buffer.shape_until_scroll(&mut font_system, false);
+        let buffer = Self::layout_text(&mut font_system, text, style);
 
         // 描画サイズの取得
         let bounds = self.calculate_buffer_bounds(&mut font_system, &mut swash_cache, &buffer);
@@ -213,14 +206,7 @@ impl TextRendererContext {
         let TextData { text, style } = &text_data[0];
 
         // レイアウトを計算する
-        let font_family = Self::get_font_family_from_style(style);
-        let font_size = style.font_size;
-        let line_height =
-            calculate_line_height_from_font(&font_system, &vec![font_family], font_size);
-        let mut buffer = Buffer::new(&mut font_system, Metrics::new(font_size, line_height));
-        let attrs = Attrs::new().family(font_family);
-        buffer.set_text(&mut font_system, text, &attrs, Shaping::Advanced, None);
-        buffer.shape_until_scroll(&mut font_system, false);
+        let buffer = Self::layout_text(&mut font_system, text, style);
 
         // 描画サイズの取得
         let bounds = self.calculate_buffer_bounds(&mut font_system, &mut swash_cache, &buffer);
@@ -231,6 +217,34 @@ impl TextRendererContext {
         }
     }
 
+    /// テキストをshapingし、wrap_widthが指定されていれば折り返したBufferを返す
+    fn layout_text(font_system: &mut FontSystem, text: &str, style: &TextStyleData) -> Buffer {
+        let font_family = Self::get_font_family_from_style(style);
+        let font_size = style.font_size;
+        let line_height =
+            calculate_line_height_from_font(font_system, &vec![font_family], font_size);
+        let mut buffer = Buffer::new(font_system, Metrics::new(font_size, line_height));
+        buffer.set_wrap(font_system, Self::get_wrap_from_style(style));
+        buffer.set_size(font_system, style.wrap_width, None);
+        let attrs = Attrs::new().family(font_family);
+        buffer.set_text(font_system, text, &attrs, Shaping::Advanced, None);
+        buffer.shape_until_scroll(font_system, false);
+        buffer
+    }
+
+    /// TextStyleから折り返し方を取得
+    /// 日本語はUnicodeの改行規則で文字の間が改行位置になるため、word単位の折り返しでも文字ごとに折り返される
+    fn get_wrap_from_style(style: &TextStyleData) -> Wrap {
+        if style.wrap_width.is_none() {
+            return Wrap::None;
+        }
+        match (style.word_break, style.line_break) {
+            (WordBreak::BreakAll, _) | (_, LineBreak::Anywhere) => Wrap::Glyph,
+            (WordBreak::KeepAll, _) => Wrap::Word,
+            (WordBreak::Normal, _) => Wrap::WordOrGlyph,
+        }
+    }
+
     /// Bufferからテキストの境界を計算
     fn calculate_buffer_bounds(
         &self,
@@ -108,6 +108,7 @@ fn test_render_simple_text_vrt() {
             color: Color::WHITE,
             font_size: 32.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -138,6 +139,7 @@ fn test_render_text_with_color_vrt() {
             color: Color::from_rgb(255, 0, 0), // 赤色
             font_size: 48.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -168,6 +170,7 @@ fn test_render_text_with_alpha_vrt() {
             color: Color::from(0, 255, 0, 128),
             font_size: 40.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -198,6 +201,7 @@ fn test_render_text_with_line_break_vrt() {
             color: Color::WHITE,
             font_size: 48.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -228,6 +232,7 @@ fn test_render_text_different_sizes_vrt() {
             color: Color::WHITE,
             font_size: 16.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
     let small_texture = context.render_text(&small_text);
@@ -239,6 +244,7 @@ fn test_render_text_different_sizes_vrt() {
             color: Color::WHITE,
             font_size: 64.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
     let large_texture = context.render_text(&large_text);
@@ -276,6 +282,7 @@ fn test_render_japanese_text_vrt() {
             color: Color::from_rgb(0, 0, 255),
             font_size: 36.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -307,6 +314,7 @@ fn test_calculate_text_size_simple() {
             color: Color::WHITE,
             font_size: 32.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -327,6 +335,7 @@ fn test_calculate_text_size_empty() {
             color: Color::WHITE,
             font_size: 32.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -347,6 +356,7 @@ fn test_calculate_text_size_comparison() {
             color: Color::WHITE,
             font_size: 32.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -356,6 +366,7 @@ fn test_calculate_text_size_comparison() {
             color: Color::WHITE,
             font_size: 32.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -377,6 +388,7 @@ fn test_calculate_text_size_different_font_sizes() {
             color: Color::WHITE,
             font_size: 16.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -386,6 +398,7 @@ fn test_calculate_text_size_different_font_sizes() {
             color: Color::WHITE,
             font_size: 64.0,
             font_family: vec![],
+            ..Default::default()
         },
     }];
 
@@ -395,3 +408,36 @@ fn test_calculate_text_size_different_font_sizes() {
     assert!(large_size.width > small_size.width);
     assert!(large_size.height > small_size.height);
 }
+
+#[test]
+fn test_calculate_text_size_wrapped() {
+    let (device, queue) = create_gpu_context();
+    let context = TextRendererContext::new(device, queue);
+
+    let text = "吾輩は猫である。名前はまだ無い。どこで生れたかとんと見当がつかぬ。";
+    let unwrapped_text = vec![TextData {
+        text: text.to_string(),
+        style: TextStyleData {
+            color: Color::WHITE,
+            font_size: 32.0,
+            font_family: vec![],
+            ..Default::default()
+        },
+    }];
+    let wrapped_text = vec![TextData {
+        text: text.to_string(),
+        style: TextStyleData {
+            color: Color::WHITE,
+            font_size: 32.0,
+            font_family: vec![],
+            wrap_width: Some(200.0),
+            ..Default::default()
+        },
+    }];
+
+    let unwrapped_size = context.calculate_text_size(&unwrapped_text);
+    let wrapped_size = context.calculate_text_size(&wrapped_text);
+
+    assert!(wrapped_size.width <= 200.0);
+    assert!(wrapped_size.height > unwrapped_size.height);
+}